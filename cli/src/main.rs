@@ -57,6 +57,10 @@ The following options are supported:
         to specify a raw module to parse in addition to the raw modules
         specified by the --vanilla, --mods, and --installed flags.
 
+    --cache PATH        Cache the parsed raw files in a directory
+        Raw files which haven't changed since they were cached are not
+        parsed again. The directory is created if it doesn't exist.
+
     -v, --verbose       Increase the verbosity of the output
         Default log level: 'info'
 
@@ -112,6 +116,8 @@ struct Args {
     pub skip_info_files: bool,
     /// Whether or not to skip writing the parsed raws to the output file
     pub skip_raws: bool,
+    /// The directory to cache parsed raw files in (if any)
+    pub cache_directory: Option<PathBuf>,
 }
 
 impl std::default::Default for Args {
@@ -130,6 +136,7 @@ impl std::default::Default for Args {
             df_path: PathBuf::new(),
             raw_file_paths: Vec::new(),
             raw_module_paths: Vec::new(),
+            cache_directory: None,
         }
     }
 }
@@ -197,6 +204,9 @@ fn parse_args() -> Result<Args, lexopt::Error> {
             Short('m') | Long("module") => {
                 args.raw_module_paths.push(PathBuf::from(parser.value()?));
             }
+            Long("cache") => {
+                args.cache_directory = Some(PathBuf::from(parser.value()?));
+            }
 
             Short('v') | Long("verbose") => {
                 if args.log_level == Level::INFO {
@@ -429,6 +439,11 @@ pub fn main() -> Result<(), lexopt::Error> {
         options.attach_metadata_to_raws();
     }
 
+    // Set the directory to cache parsed raw files in
+    if let Some(cache_directory) = &args.cache_directory {
+        options.set_cache_directory(cache_directory);
+    }
+
     // Set whether to include the summary in the log or not
     if args.print_summary {
         options.log_summary();
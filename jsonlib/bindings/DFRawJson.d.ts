@@ -4006,6 +4006,10 @@ export type ConditionTag =
 	 * Condition of being a dead shrub
 	 */
 	| "shrubDead"
+	/**
+	 * Condition of being a tile of a tree
+	 */
+	| "treeTile"
 	/**
 	 * Condition of not being a child
 	 */
@@ -4094,6 +4098,22 @@ export type ConditionTag =
 	 * Condition of being a swapped tissue
 	 */
 	| "tissueSwap"
+	/**
+	 * Condition of being a specific body part (selects the body part for the `BP_` conditions)
+	 */
+	| "bodyPart"
+	/**
+	 * Condition of the body part being present
+	 */
+	| "bodyPartPresent"
+	/**
+	 * Condition of the body part being scarred
+	 */
+	| "bodyPartScarred"
+	/**
+	 * Condition of the body part having an appearance modifier in a range
+	 */
+	| "bodyPartAppearanceModifierRange"
 	/**
 	 * Condition of being a specific layer (start layer definition)
 	 */
@@ -9728,11 +9748,52 @@ export type Caste = {
 
 /**
  * A struct representing a color in the format "foreground:background:brightness".
+ *
+ * The RGB colors are worked out whenever a color is made (including when it is deserialized), so
+ * they always match the color indices.
  */
 export type Color = {
 	foreground: number;
 	background: number;
 	brightness: number;
+	/**
+	 * The RGB foreground color with the game's default colors (see `get_foreground_rgb` for
+	 * other palettes)
+	 */
+	foregroundRgb: [number, number, number];
+	/**
+	 * The RGB background color with the game's default colors (see `get_background_rgb` for
+	 * other palettes)
+	 */
+	backgroundRgb: [number, number, number];
+};
+
+/**
+ * The 16 colors (as RGB) used to draw tiles, in the game's order: black, blue, green, cyan, red,
+ * magenta, brown, light gray, then the bright versions (dark gray, light blue, ... white).
+ *
+ * A `Color`'s foreground index plus 8 if it is bright picks the foreground color, and its background
+ * index picks the background color. The default is the game's default `colors.txt`.
+ */
+export type ColorPalette = {
+	colors: [
+		[number, number, number],
+		[number, number, number],
+		[number, number, number],
+		[number, number, number],
+		[number, number, number],
+		[number, number, number],
+		[number, number, number],
+		[number, number, number],
+		[number, number, number],
+		[number, number, number],
+		[number, number, number],
+		[number, number, number],
+		[number, number, number],
+		[number, number, number],
+		[number, number, number],
+		[number, number, number],
+	];
 };
 
 /**
@@ -9864,11 +9925,29 @@ export type Creature = {
  * A creature effect.
  */
 export type CreatureEffect = {
+	/**
+	 * The effect (e.g. `CE_PAIN` is `Pain`)
+	 */
+	effect: CreatureEffectTag;
+	/**
+	 * The arguments specific to the effect, before its properties (e.g. `STRENGTH:150:0` for
+	 * `CE_PHYS_ATT_CHANGE`, or `FLIER` for `CE_ADD_TAG`)
+	 */
+	arguments?: string[] | null;
 	severity: number;
 	probability: number;
-	affectedBodyPartsByCategory?: string[] | null;
-	affectedBodyPartsByType?: string[] | null;
-	affectedBodyPartsByToken?: string[] | null;
+	/**
+	 * The targeted body parts and tissue layers, from `BP:BY_CATEGORY:<category>:<tissue>`
+	 */
+	affectedBodyPartsByCategory?: [string, string][] | null;
+	/**
+	 * The targeted body parts and tissue layers, from `BP:BY_TYPE:<type>:<tissue>`
+	 */
+	affectedBodyPartsByType?: [string, string][] | null;
+	/**
+	 * The targeted body parts and tissue layers, from `BP:BY_TOKEN:<token>:<tissue>`
+	 */
+	affectedBodyPartsByToken?: [string, string][] | null;
 	tags?: CreatureEffectPropertyTag[] | null;
 	start: number;
 	peak: number;
@@ -10045,7 +10124,7 @@ export type Graphic = {
 	casteIdentifier?: string | null;
 	kind: GraphicTypeTag;
 	sprites?: SpriteGraphic[] | null;
-	layers?: [string, SpriteLayer[]][] | null;
+	layers?: [string, SpriteLayerGroup[]][] | null;
 	growths?: [string, SpriteGraphic[]][] | null;
 	customExtensions?: CustomGraphicExtension[] | null;
 	tags?: string[] | null;
@@ -10468,6 +10547,7 @@ export type SpriteGraphic = {
 	colorPalletSwap?: number | null;
 	targetIdentifier?: string | null;
 	extraDescriptor?: string | null;
+	growthStage?: number | null;
 };
 
 /**
@@ -10482,6 +10562,19 @@ export type SpriteLayer = {
 	conditions?: [ConditionTag, string][] | null;
 };
 
+/**
+ * A group of `SpriteLayer`s in a layer set, with the conditions they share.
+ *
+ * Layers between `[LAYER_GROUP]` and `[END_LAYER_GROUP]` (or the next `[LAYER_GROUP]`) form a
+ * group. Conditions given before the group's first layer, or after an `LG_CONDITION_BP`, belong to
+ * the group and apply to every layer in it. Layers which aren't in a `LAYER_GROUP` are kept in
+ * groups without conditions, so the drawing order of the layer set is preserved.
+ */
+export type SpriteLayerGroup = {
+	conditions?: [ConditionTag, string][] | null;
+	layers: SpriteLayer[];
+};
+
 /**
  * Represents the name of a materials 3 states (solid, liquid, gas)
  */
@@ -10520,6 +10613,13 @@ export type Syndrome = {
 	 */
 	concentrationAdded?: [number, number] | null;
 	tags?: SyndromeTag[] | null;
+	/**
+	 * The creature effects (`CE_*` tokens) of the syndrome, in the order they are listed
+	 */
+	effects?: CreatureEffect[] | null;
+	/**
+	 * The values of `CE` tokens (e.g. `PERIODIC` or `COUNTER_TRIGGER` conditions)
+	 */
 	conditions?: string[] | null;
 };
 
@@ -10570,12 +10670,27 @@ export type Temperatures = {
 
 /**
  * Representation of a character tile (literally a single character) that is used in DF Classic
+ *
+ * The Unicode characters are worked out whenever a character is set (including when the tile is
+ * deserialized), so they always match the characters.
  */
 export type Tile = {
 	character: string;
+	/**
+	 * The Unicode character the game draws for `character`
+	 */
+	unicodeCharacter?: string | null;
 	altCharacter?: string | null;
+	/**
+	 * The Unicode character the game draws for `alt_character`
+	 */
+	unicodeAltCharacter?: string | null;
 	color?: Color | null;
 	glowCharacter?: string | null;
+	/**
+	 * The Unicode character the game draws for `glow_character`
+	 */
+	unicodeGlowCharacter?: string | null;
 	glowColor?: Color | null;
 };
 
@@ -11048,6 +11163,16 @@ export type ParserOptions = {
 	 * Default: false
 	 */
 	logSummary: boolean;
+	/**
+	 * Optionally specify a directory to cache the results of parsing each raw file in.
+	 *
+	 * When set, the parse results for each raw file are saved in this directory, and re-used on
+	 * the next parse if the file hasn't changed (same size and modified time, or same contents).
+	 * Only changed files are read again. The creature resolution steps always run again.
+	 *
+	 * Default: None
+	 */
+	cacheDirectory: string | null;
 };
 
 /**
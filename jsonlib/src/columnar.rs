@@ -0,0 +1,527 @@
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use arrow_array::{ArrayRef, Int64Array, RecordBatch, StringArray};
+use arrow_ipc::writer::FileWriter;
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+use dfraw_parser::{
+    metadata::ObjectType, traits::RawObject, BodySize, Caste, Creature, Inorganic, Material,
+    MaterialMechanics, MaterialTemplate, MechanicalProperties, Name, ParserError, Plant,
+    PlantGrowth, Temperatures,
+};
+use tracing::info;
+
+/// How a column reads its value from a row.
+enum Getter<R> {
+    Text(fn(&R) -> Option<String>),
+    Integer(fn(&R) -> Option<i64>),
+}
+
+/// A column of a table, read from each row with one of the raws' getters.
+struct Column<R> {
+    name: &'static str,
+    getter: Getter<R>,
+}
+
+const fn text<R>(name: &'static str, getter: fn(&R) -> Option<String>) -> Column<R> {
+    Column {
+        name,
+        getter: Getter::Text(getter),
+    }
+}
+
+const fn integer<R>(name: &'static str, getter: fn(&R) -> Option<i64>) -> Column<R> {
+    Column {
+        name,
+        getter: Getter::Integer(getter),
+    }
+}
+
+/// A row for a top-level raw.
+struct RawRow<'a, T> {
+    raw: &'a T,
+}
+
+/// A row for a caste, with the creature it belongs to.
+struct CasteRow<'a> {
+    creature: &'a Creature,
+    caste: &'a Caste,
+}
+
+/// A row for a material, with the raw which defines it.
+struct MaterialRow<'a> {
+    owner_type: String,
+    owner: &'a dyn RawObject,
+    material: &'a Material,
+}
+
+/// A row for a plant growth, with the plant it belongs to.
+struct GrowthRow<'a> {
+    plant: &'a Plant,
+    growth: &'a PlantGrowth,
+}
+
+fn non_empty(text: &str) -> Option<String> {
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn min(range: Option<[u32; 2]>) -> Option<i64> {
+    range.map(|[min, _]| i64::from(min))
+}
+
+fn max(range: Option<[u32; 2]>) -> Option<i64> {
+    range.map(|[_, max]| i64::from(max))
+}
+
+fn count<T>(items: &[T]) -> Option<i64> {
+    i64::try_from(items.len()).ok()
+}
+
+/// Columns shared by every top-level raw.
+fn raw_columns<'a, T: RawObject>() -> Vec<Column<RawRow<'a, T>>> {
+    vec![
+        text("object_id", |row| Some(row.raw.get_object_id().to_string())),
+        text("identifier", |row| {
+            Some(row.raw.get_identifier().to_string())
+        }),
+        text("module", |row| {
+            Some(row.raw.get_metadata().get_module_name().to_string())
+        }),
+        text("raw_file", |row| {
+            Some(row.raw.get_metadata().get_raw_file_path().to_string())
+        }),
+    ]
+}
+
+fn creature_columns<'a>() -> Vec<Column<RawRow<'a, Creature>>> {
+    let columns: Vec<Column<RawRow<Creature>>> = vec![
+        text("name", |row| non_empty(row.raw.get_name())),
+        integer("frequency", |row| row.raw.get_frequency().map(i64::from)),
+        integer("cluster_number_min", |row| {
+            min(row.raw.get_cluster_number())
+        }),
+        integer("cluster_number_max", |row| {
+            max(row.raw.get_cluster_number())
+        }),
+        integer("population_number_min", |row| {
+            min(row.raw.get_population_number())
+        }),
+        integer("population_number_max", |row| {
+            max(row.raw.get_population_number())
+        }),
+        integer("underground_depth_min", |row| {
+            min(row.raw.get_underground_depth())
+        }),
+        integer("underground_depth_max", |row| {
+            max(row.raw.get_underground_depth())
+        }),
+        integer("caste_count", |row| count(row.raw.get_castes())),
+    ];
+    raw_columns().into_iter().chain(columns).collect()
+}
+
+fn caste_columns<'a>() -> Vec<Column<CasteRow<'a>>> {
+    vec![
+        text("creature_object_id", |row| {
+            Some(row.creature.get_object_id().to_string())
+        }),
+        text("creature_identifier", |row| {
+            Some(row.creature.get_identifier().to_string())
+        }),
+        text("identifier", |row| {
+            Some(row.caste.get_identifier().to_string())
+        }),
+        text("name", |row| {
+            row.caste
+                .get_caste_name()
+                .map(Name::get_singular)
+                .and_then(non_empty)
+        }),
+        // The last body size is the adult size
+        integer("body_size", |row| {
+            row.caste
+                .get_body_sizes()
+                .last()
+                .map(|size| i64::from(BodySize::get_size_cm3(size)))
+        }),
+        integer("clutch_size_min", |row| min(row.caste.get_clutch_size())),
+        integer("clutch_size_max", |row| max(row.caste.get_clutch_size())),
+        integer("litter_size_min", |row| min(row.caste.get_litter_size())),
+        integer("litter_size_max", |row| max(row.caste.get_litter_size())),
+        integer("max_age_min", |row| min(row.caste.get_max_age())),
+        integer("max_age_max", |row| max(row.caste.get_max_age())),
+        integer("baby", |row| row.caste.get_baby().map(i64::from)),
+        integer("child", |row| row.caste.get_child().map(i64::from)),
+        integer("difficulty", |row| {
+            row.caste.get_difficulty().map(i64::from)
+        }),
+        integer("egg_size", |row| row.caste.get_egg_size().map(i64::from)),
+        integer("grass_trample", |row| {
+            row.caste.get_grass_trample().map(i64::from)
+        }),
+        integer("grazer", |row| row.caste.get_grazer().map(i64::from)),
+        integer("low_light_vision", |row| {
+            row.caste.get_low_light_vision().map(i64::from)
+        }),
+        integer("pet_value", |row| row.caste.get_pet_value().map(i64::from)),
+        integer("pop_ratio", |row| row.caste.get_pop_ratio().map(i64::from)),
+        integer("change_body_size_percentage", |row| {
+            row.caste.get_change_body_size_percentage().map(i64::from)
+        }),
+        integer("tag_count", |row| count(row.caste.get_tags())),
+        text("description", |row| {
+            row.caste.get_description().map(String::from)
+        }),
+    ]
+}
+
+/// Read one of a material's mechanical properties for a type of stress.
+fn stress(
+    row: &MaterialRow,
+    kind: fn(&MaterialMechanics) -> Option<&MechanicalProperties>,
+    value: fn(&MechanicalProperties) -> i32,
+) -> Option<i64> {
+    row.material
+        .get_mechanical_properties()
+        .and_then(kind)
+        .map(|properties| i64::from(value(properties)))
+}
+
+/// Read one of a material's mechanical properties.
+fn mechanics(row: &MaterialRow, value: fn(&MaterialMechanics) -> Option<i32>) -> Option<i64> {
+    row.material
+        .get_mechanical_properties()
+        .and_then(value)
+        .map(i64::from)
+}
+
+/// Read one of a material's temperatures.
+fn temperature(row: &MaterialRow, value: fn(&Temperatures) -> Option<u32>) -> Option<i64> {
+    row.material
+        .get_temperatures()
+        .and_then(value)
+        .map(i64::from)
+}
+
+#[allow(clippy::too_many_lines)]
+fn material_columns<'a>() -> Vec<Column<MaterialRow<'a>>> {
+    use MaterialMechanics as M;
+    use MechanicalProperties as P;
+    vec![
+        text("owner_type", |row| Some(row.owner_type.clone())),
+        text("owner_object_id", |row| {
+            Some(row.owner.get_object_id().to_string())
+        }),
+        text("owner_identifier", |row| {
+            Some(row.owner.get_identifier().to_string())
+        }),
+        text("name", |row| non_empty(row.material.get_name())),
+        text("material_type", |row| {
+            row.material
+                .get_material_type()
+                .map(|material_type| format!("{material_type:?}"))
+        }),
+        integer("value", |row| row.material.get_value().map(i64::from)),
+        integer("liquid_density", |row| {
+            row.material.get_liquid_density().map(i64::from)
+        }),
+        integer("molar_mass", |row| {
+            row.material.get_molar_mass().map(i64::from)
+        }),
+        integer("impact_yield", |row| {
+            stress(row, M::get_impact, P::get_yield)
+        }),
+        integer("impact_fracture", |row| {
+            stress(row, M::get_impact, P::get_fracture)
+        }),
+        integer("impact_elasticity", |row| {
+            stress(row, M::get_impact, P::get_elasticity)
+        }),
+        integer("compressive_yield", |row| {
+            stress(row, M::get_compressive, P::get_yield)
+        }),
+        integer("compressive_fracture", |row| {
+            stress(row, M::get_compressive, P::get_fracture)
+        }),
+        integer("compressive_elasticity", |row| {
+            stress(row, M::get_compressive, P::get_elasticity)
+        }),
+        integer("tensile_yield", |row| {
+            stress(row, M::get_tensile, P::get_yield)
+        }),
+        integer("tensile_fracture", |row| {
+            stress(row, M::get_tensile, P::get_fracture)
+        }),
+        integer("tensile_elasticity", |row| {
+            stress(row, M::get_tensile, P::get_elasticity)
+        }),
+        integer("torsion_yield", |row| {
+            stress(row, M::get_torsion, P::get_yield)
+        }),
+        integer("torsion_fracture", |row| {
+            stress(row, M::get_torsion, P::get_fracture)
+        }),
+        integer("torsion_elasticity", |row| {
+            stress(row, M::get_torsion, P::get_elasticity)
+        }),
+        integer("shear_yield", |row| stress(row, M::get_shear, P::get_yield)),
+        integer("shear_fracture", |row| {
+            stress(row, M::get_shear, P::get_fracture)
+        }),
+        integer("shear_elasticity", |row| {
+            stress(row, M::get_shear, P::get_elasticity)
+        }),
+        integer("bending_yield", |row| {
+            stress(row, M::get_bending, P::get_yield)
+        }),
+        integer("bending_fracture", |row| {
+            stress(row, M::get_bending, P::get_fracture)
+        }),
+        integer("bending_elasticity", |row| {
+            stress(row, M::get_bending, P::get_elasticity)
+        }),
+        integer("max_edge", |row| mechanics(row, M::get_max_edge)),
+        integer("solid_density", |row| mechanics(row, M::get_solid_density)),
+        integer("specific_heat", |row| {
+            temperature(row, Temperatures::get_specific_heat)
+        }),
+        integer("ignition_point", |row| {
+            temperature(row, Temperatures::get_ignition_point)
+        }),
+        integer("melting_point", |row| {
+            temperature(row, Temperatures::get_melting_point)
+        }),
+        integer("boiling_point", |row| {
+            temperature(row, Temperatures::get_boiling_point)
+        }),
+        integer("heat_damage_point", |row| {
+            temperature(row, Temperatures::get_heat_damage_point)
+        }),
+        integer("cold_damage_point", |row| {
+            temperature(row, Temperatures::get_cold_damage_point)
+        }),
+        integer("material_fixed_temperature", |row| {
+            temperature(row, Temperatures::get_material_fixed_temperature)
+        }),
+    ]
+}
+
+fn plant_columns<'a>() -> Vec<Column<RawRow<'a, Plant>>> {
+    let columns: Vec<Column<RawRow<Plant>>> = vec![
+        text("name", |row| non_empty(row.raw.get_name())),
+        integer("frequency", |row| row.raw.get_frequency().map(i64::from)),
+        integer("underground_depth_min", |row| {
+            min(row.raw.get_underground_depth())
+        }),
+        integer("underground_depth_max", |row| {
+            max(row.raw.get_underground_depth())
+        }),
+        integer("growth_count", |row| count(row.raw.get_growths())),
+        integer("material_count", |row| count(row.raw.get_materials())),
+    ];
+    raw_columns().into_iter().chain(columns).collect()
+}
+
+fn plant_growth_columns<'a>() -> Vec<Column<GrowthRow<'a>>> {
+    vec![
+        text("plant_object_id", |row| {
+            Some(row.plant.get_object_id().to_string())
+        }),
+        text("plant_identifier", |row| {
+            Some(row.plant.get_identifier().to_string())
+        }),
+        text("name", |row| {
+            non_empty(row.growth.get_name().get_singular())
+        }),
+        text("growth_type", |row| {
+            Some(format!("{:?}", row.growth.get_growth_type()))
+        }),
+        text("item", |row| non_empty(row.growth.get_item())),
+        integer("density", |row| row.growth.get_density().map(i64::from)),
+        integer("timing_start", |row| min(row.growth.get_timing())),
+        integer("timing_end", |row| max(row.growth.get_timing())),
+        integer("trunk_height_percentage_min", |row| {
+            row.growth
+                .get_trunk_height_percentage()
+                .map(|[min, _]| i64::from(min))
+        }),
+        integer("trunk_height_percentage_max", |row| {
+            row.growth
+                .get_trunk_height_percentage()
+                .map(|[_, max]| i64::from(max))
+        }),
+        text("print", |row| row.growth.get_print().map(String::from)),
+        integer("tag_count", |row| count(row.growth.get_tags())),
+    ]
+}
+
+/// The flattened tables, one row per object, with every column a plain string or integer.
+pub struct ColumnarTables {
+    /// One row per creature
+    pub creatures: RecordBatch,
+    /// One row per caste, with its creature's object id and identifier
+    pub castes: RecordBatch,
+    /// One row per material (of plants, inorganics and material templates), with its mechanical
+    /// properties and temperatures
+    pub materials: RecordBatch,
+    /// One row per plant
+    pub plants: RecordBatch,
+    /// One row per plant growth, with its timing
+    pub plant_growths: RecordBatch,
+}
+
+impl ColumnarTables {
+    /// The tables with their names (which are used as the file names when writing them).
+    #[must_use]
+    pub const fn tables(&self) -> [(&'static str, &RecordBatch); 5] {
+        [
+            ("creatures", &self.creatures),
+            ("castes", &self.castes),
+            ("materials", &self.materials),
+            ("plants", &self.plants),
+            ("plant_growths", &self.plant_growths),
+        ]
+    }
+}
+
+/// Flatten parsed raws into columnar tables.
+///
+/// # Arguments
+///
+/// * `raws` - The parsed raws (creatures, plants, inorganics and material templates are used).
+///
+/// # Returns
+///
+/// * `ColumnarTables` - The creatures, castes, materials, plants and plant growths tables.
+///
+/// # Errors
+///
+/// * `ParserError::Export` - If the tables can't be built.
+pub fn build_tables(raws: &[Box<dyn RawObject>]) -> Result<ColumnarTables, ParserError> {
+    let mut creatures = Vec::new();
+    let mut castes = Vec::new();
+    let mut materials = Vec::new();
+    let mut plants = Vec::new();
+    let mut plant_growths = Vec::new();
+
+    for raw in raws {
+        let any = raw.as_any();
+        if let Some(creature) = any.downcast_ref::<Creature>() {
+            creatures.push(RawRow { raw: creature });
+            castes.extend(
+                creature
+                    .get_castes()
+                    .iter()
+                    .map(|caste| CasteRow { creature, caste }),
+            );
+        } else if let Some(plant) = any.downcast_ref::<Plant>() {
+            plants.push(RawRow { raw: plant });
+            plant_growths.extend(
+                plant
+                    .get_growths()
+                    .iter()
+                    .map(|growth| GrowthRow { plant, growth }),
+            );
+            materials.extend(plant.get_materials().iter().map(|material| MaterialRow {
+                owner_type: ObjectType::Plant.to_string(),
+                owner: raw.as_ref(),
+                material,
+            }));
+        } else {
+            let material = if let Some(inorganic) = any.downcast_ref::<Inorganic>() {
+                inorganic.get_material()
+            } else if let Some(material_template) = any.downcast_ref::<MaterialTemplate>() {
+                material_template.get_material()
+            } else {
+                continue;
+            };
+            materials.push(MaterialRow {
+                owner_type: raw.get_type().to_string(),
+                owner: raw.as_ref(),
+                material,
+            });
+        }
+    }
+
+    Ok(ColumnarTables {
+        creatures: to_record_batch(&creatures, &creature_columns())?,
+        castes: to_record_batch(&castes, &caste_columns())?,
+        materials: to_record_batch(&materials, &material_columns())?,
+        plants: to_record_batch(&plants, &plant_columns())?,
+        plant_growths: to_record_batch(&plant_growths, &plant_growth_columns())?,
+    })
+}
+
+/// Flatten parsed raws into columnar tables, and write each table as an Arrow IPC file
+/// (`creatures.arrow`, `castes.arrow`, `materials.arrow`, `plants.arrow` and `plant_growths.arrow`).
+///
+/// The files can be read with `pandas.read_feather` or `polars.read_ipc`.
+///
+/// # Arguments
+///
+/// * `raws` - The parsed raws.
+/// * `out_directory` - The directory to write the files to (it is created if needed).
+///
+/// # Returns
+///
+/// * `Vec<PathBuf>` - The paths of the written files.
+///
+/// # Errors
+///
+/// * `ParserError::Io` - If the directory or a file can't be created.
+/// * `ParserError::Export` - If the tables can't be built or written.
+pub fn write_arrow_ipc<P: AsRef<Path>>(
+    raws: &[Box<dyn RawObject>],
+    out_directory: &P,
+) -> Result<Vec<PathBuf>, ParserError> {
+    let tables = build_tables(raws)?;
+    std::fs::create_dir_all(out_directory)?;
+
+    let mut paths = Vec::new();
+    for (name, batch) in tables.tables() {
+        let path = out_directory.as_ref().join(format!("{name}.arrow"));
+        let file = File::create(&path)?;
+        let mut writer = FileWriter::try_new(file, &batch.schema()).map_err(export_error)?;
+        writer.write(batch).map_err(export_error)?;
+        writer.finish().map_err(export_error)?;
+        info!(
+            "write_arrow_ipc: Wrote {} rows to {}",
+            batch.num_rows(),
+            path.display()
+        );
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn export_error(error: ArrowError) -> ParserError {
+    ParserError::Export(error.to_string())
+}
+
+/// Build a table from rows, reading each column with its getter.
+fn to_record_batch<R>(rows: &[R], columns: &[Column<R>]) -> Result<RecordBatch, ParserError> {
+    let fields: Vec<Field> = columns
+        .iter()
+        .map(|column| match column.getter {
+            Getter::Text(_) => Field::new(column.name, DataType::Utf8, true),
+            Getter::Integer(_) => Field::new(column.name, DataType::Int64, true),
+        })
+        .collect();
+    let arrays: Vec<ArrayRef> = columns
+        .iter()
+        .map(|column| match column.getter {
+            Getter::Text(getter) => {
+                Arc::new(rows.iter().map(getter).collect::<StringArray>()) as ArrayRef
+            }
+            Getter::Integer(getter) => {
+                Arc::new(rows.iter().map(getter).collect::<Int64Array>()) as ArrayRef
+            }
+        })
+        .collect();
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays).map_err(export_error)
+}
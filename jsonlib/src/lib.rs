@@ -93,6 +93,46 @@ mod tauri_lib;
 /// - `raws_to_string`: Converts a vector of raw objects to a JSON string representation.
 pub mod util;
 
+#[cfg(feature = "sqlite")]
+/// Export parsed raws into a `SQLite` database (requires the `sqlite` feature).
+///
+/// The database has a normalized schema, with tables for modules, creatures, castes, caste tags,
+/// biomes, plants, materials, inorganics, entities and positions, linked by foreign keys. This makes
+/// it possible to query the raws with SQL, for example all castes that fly and are larger than
+/// 70000 cm3:
+///
+/// ```sql
+/// SELECT creatures.identifier, castes.identifier FROM castes
+/// JOIN creatures ON creatures.id = castes.creature_id
+/// JOIN caste_tags ON caste_tags.caste_id = castes.id
+/// WHERE caste_tags.token = 'FLIER' AND castes.body_size > 70000;
+/// ```
+///
+/// The main functions:
+///
+/// - `write_to_sqlite`: Writes a `ParseResult` into a new database file.
+/// - `write_to_connection`: Writes a `ParseResult` into an open connection.
+pub mod sqlite;
+
+#[cfg(feature = "arrow")]
+/// Export parsed raws as flat, columnar tables in Arrow IPC files (requires the `arrow` feature).
+///
+/// Creatures, castes (with their numeric ranges as `_min`/`_max` columns), materials (with their
+/// mechanical properties and temperatures), plants and plant growths (with their timing) are each
+/// written to their own file, which can be loaded directly into pandas or polars:
+///
+/// ```python
+/// import polars as pl
+/// castes = pl.read_ipc("out/castes.arrow")
+/// castes.filter(pl.col("body_size") > 70000)
+/// ```
+///
+/// The main functions:
+///
+/// - `build_tables`: Flattens raws into Arrow `RecordBatch`es.
+/// - `write_arrow_ipc`: Writes the tables as `.arrow` files into a directory.
+pub mod columnar;
+
 #[cfg(feature = "tauri")]
 pub use tauri_lib::ProgressDetails;
 #[cfg(feature = "tauri")]
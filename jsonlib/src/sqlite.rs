@@ -0,0 +1,411 @@
+use std::{collections::HashMap, path::Path};
+
+use dfraw_parser::{
+    metadata::ObjectType,
+    search_index::{names_of, BIOME_TOKEN_NAMES, CASTE_TOKEN_NAMES},
+    tags::{BiomeTag, CasteTag},
+    traits::RawObject,
+    BodySize, Creature, Entity, Inorganic, Material, Name, ParseResult, ParserError, Plant,
+};
+use rusqlite::{params, Connection, Transaction};
+use tracing::info;
+
+/// The schema of the exported database.
+///
+/// Every table has an integer `id` primary key, and child tables point at their parent with a
+/// foreign key (`castes.creature_id`, `positions.entity_id`, ...). Raws point at the module they
+/// were parsed from with `module_id` (which is `NULL` if the module's info file wasn't parsed).
+pub const SCHEMA: &str = "
+CREATE TABLE modules (
+    id INTEGER PRIMARY KEY,
+    object_id TEXT NOT NULL UNIQUE,
+    identifier TEXT NOT NULL,
+    name TEXT NOT NULL,
+    version TEXT NOT NULL,
+    location TEXT NOT NULL,
+    directory TEXT NOT NULL,
+    description TEXT NOT NULL
+);
+CREATE TABLE creatures (
+    id INTEGER PRIMARY KEY,
+    module_id INTEGER REFERENCES modules(id),
+    object_id TEXT NOT NULL,
+    identifier TEXT NOT NULL,
+    name TEXT NOT NULL,
+    raw_file TEXT NOT NULL
+);
+CREATE TABLE castes (
+    id INTEGER PRIMARY KEY,
+    creature_id INTEGER NOT NULL REFERENCES creatures(id),
+    identifier TEXT NOT NULL,
+    name TEXT,
+    description TEXT,
+    body_size INTEGER
+);
+CREATE TABLE caste_tags (
+    id INTEGER PRIMARY KEY,
+    caste_id INTEGER NOT NULL REFERENCES castes(id),
+    token TEXT NOT NULL,
+    value TEXT
+);
+CREATE TABLE biomes (
+    id INTEGER PRIMARY KEY,
+    token TEXT NOT NULL UNIQUE
+);
+CREATE TABLE creature_biomes (
+    creature_id INTEGER NOT NULL REFERENCES creatures(id),
+    biome_id INTEGER NOT NULL REFERENCES biomes(id),
+    PRIMARY KEY (creature_id, biome_id)
+);
+CREATE TABLE plants (
+    id INTEGER PRIMARY KEY,
+    module_id INTEGER REFERENCES modules(id),
+    object_id TEXT NOT NULL,
+    identifier TEXT NOT NULL,
+    name TEXT NOT NULL,
+    raw_file TEXT NOT NULL
+);
+CREATE TABLE plant_biomes (
+    plant_id INTEGER NOT NULL REFERENCES plants(id),
+    biome_id INTEGER NOT NULL REFERENCES biomes(id),
+    PRIMARY KEY (plant_id, biome_id)
+);
+CREATE TABLE inorganics (
+    id INTEGER PRIMARY KEY,
+    module_id INTEGER REFERENCES modules(id),
+    object_id TEXT NOT NULL,
+    identifier TEXT NOT NULL,
+    raw_file TEXT NOT NULL
+);
+CREATE TABLE materials (
+    id INTEGER PRIMARY KEY,
+    plant_id INTEGER REFERENCES plants(id),
+    inorganic_id INTEGER REFERENCES inorganics(id),
+    name TEXT NOT NULL,
+    material_type TEXT,
+    value INTEGER
+);
+CREATE TABLE entities (
+    id INTEGER PRIMARY KEY,
+    module_id INTEGER REFERENCES modules(id),
+    creature_id INTEGER REFERENCES creatures(id),
+    object_id TEXT NOT NULL,
+    identifier TEXT NOT NULL,
+    creature TEXT,
+    raw_file TEXT NOT NULL
+);
+CREATE TABLE positions (
+    id INTEGER PRIMARY KEY,
+    entity_id INTEGER NOT NULL REFERENCES entities(id),
+    identifier TEXT NOT NULL,
+    name TEXT
+);
+";
+
+/// Export parsed raws into a new `SQLite` database file (see `SCHEMA` for the tables).
+///
+/// Arguments:
+///
+/// * `result`: The parsed raws and module info files.
+/// * `out_filepath`: The path of the database to create. Any existing file is replaced.
+///
+/// # Errors
+///
+/// * `ParserError::Io` - If an existing file can't be removed.
+/// * `ParserError::Export` - If the database can't be written.
+pub fn write_to_sqlite<P: AsRef<Path>>(
+    result: &ParseResult,
+    out_filepath: &P,
+) -> Result<(), ParserError> {
+    let out_filepath = out_filepath.as_ref();
+    if out_filepath.exists() {
+        std::fs::remove_file(out_filepath)?;
+    }
+    let mut connection = Connection::open(out_filepath).map_err(export_error)?;
+    write_to_connection(result, &mut connection)?;
+    info!(
+        "write_to_sqlite: Wrote {} raws to {}",
+        result.raws.len(),
+        out_filepath.display()
+    );
+    Ok(())
+}
+
+/// Export parsed raws into an open `SQLite` connection (e.g. an in-memory database). The tables
+/// from `SCHEMA` are created, so they must not already exist.
+///
+/// Arguments:
+///
+/// * `result`: The parsed raws and module info files.
+/// * `connection`: The database connection.
+///
+/// # Errors
+///
+/// * `ParserError::Export` - If the tables can't be created or written.
+pub fn write_to_connection(
+    result: &ParseResult,
+    connection: &mut Connection,
+) -> Result<(), ParserError> {
+    connection
+        .execute_batch("PRAGMA foreign_keys = ON;")
+        .map_err(export_error)?;
+    let transaction = connection.transaction().map_err(export_error)?;
+    transaction.execute_batch(SCHEMA).map_err(export_error)?;
+
+    let mut writer = SqliteWriter::new(&transaction);
+    writer.write_modules(result)?;
+    // Creatures go first, so that entities can point at them
+    for object_type in [
+        ObjectType::Creature,
+        ObjectType::Plant,
+        ObjectType::Inorganic,
+        ObjectType::Entity,
+    ] {
+        for raw in result
+            .raws
+            .iter()
+            .filter(|raw| raw.get_type() == &object_type)
+        {
+            writer.write_raw(raw.as_ref())?;
+        }
+    }
+
+    transaction.commit().map_err(export_error)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn export_error(error: rusqlite::Error) -> ParserError {
+    ParserError::Export(error.to_string())
+}
+
+/// Writes rows, keeping track of the ids of rows which others point at.
+struct SqliteWriter<'a> {
+    transaction: &'a Transaction<'a>,
+    /// Module ids, by module object id
+    modules: HashMap<String, i64>,
+    /// Creature ids, by creature identifier (a later module's creature replaces an earlier one)
+    creatures: HashMap<String, i64>,
+    /// Biome ids, by token
+    biomes: HashMap<String, i64>,
+}
+
+impl<'a> SqliteWriter<'a> {
+    fn new(transaction: &'a Transaction<'a>) -> Self {
+        Self {
+            transaction,
+            modules: HashMap::new(),
+            creatures: HashMap::new(),
+            biomes: HashMap::new(),
+        }
+    }
+
+    fn insert(&self, sql: &str, params: impl rusqlite::Params) -> Result<i64, ParserError> {
+        self.transaction
+            .execute(sql, params)
+            .map_err(export_error)?;
+        Ok(self.transaction.last_insert_rowid())
+    }
+
+    fn write_modules(&mut self, result: &ParseResult) -> Result<(), ParserError> {
+        for info_file in &result.info_files {
+            let id = self.insert(
+                "INSERT OR REPLACE INTO modules (object_id, identifier, name, version, location, directory, description)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    info_file.get_object_id(),
+                    info_file.get_identifier(),
+                    info_file.get_name(),
+                    info_file.get_version(),
+                    info_file.get_location().to_string(),
+                    info_file.get_parent_directory(),
+                    info_file.get_description(),
+                ],
+            )?;
+            self.modules.insert(info_file.get_object_id(), id);
+        }
+        Ok(())
+    }
+
+    fn write_raw(&mut self, raw: &dyn RawObject) -> Result<(), ParserError> {
+        let any = raw.as_any();
+        if let Some(creature) = any.downcast_ref::<Creature>() {
+            self.write_creature(creature)
+        } else if let Some(plant) = any.downcast_ref::<Plant>() {
+            self.write_plant(plant)
+        } else if let Some(inorganic) = any.downcast_ref::<Inorganic>() {
+            self.write_inorganic(inorganic)
+        } else if let Some(entity) = any.downcast_ref::<Entity>() {
+            self.write_entity(entity)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn module_id(&self, raw: &dyn RawObject) -> Option<i64> {
+        self.modules
+            .get(raw.get_metadata().get_module_object_id())
+            .copied()
+    }
+
+    fn biome_id(&mut self, biome: &BiomeTag) -> Result<i64, ParserError> {
+        let token = names_of(&BIOME_TOKEN_NAMES, biome)
+            .first()
+            .map_or_else(|| format!("{biome:?}"), ToString::to_string);
+        if let Some(id) = self.biomes.get(&token) {
+            return Ok(*id);
+        }
+        let id = self.insert("INSERT INTO biomes (token) VALUES (?1)", params![token])?;
+        self.biomes.insert(token, id);
+        Ok(id)
+    }
+
+    fn write_creature(&mut self, creature: &Creature) -> Result<(), ParserError> {
+        let creature_id = self.insert(
+            "INSERT INTO creatures (module_id, object_id, identifier, name, raw_file)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                self.module_id(creature),
+                creature.get_object_id(),
+                creature.get_identifier(),
+                creature.get_name(),
+                creature.get_metadata().get_raw_file_path(),
+            ],
+        )?;
+        self.creatures
+            .insert(creature.get_identifier().to_string(), creature_id);
+
+        for caste in creature.get_castes() {
+            let caste_id = self.insert(
+                "INSERT INTO castes (creature_id, identifier, name, description, body_size)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    creature_id,
+                    caste.get_identifier(),
+                    caste.get_caste_name().map(Name::get_singular),
+                    caste.get_description(),
+                    // The last body size is the adult size
+                    caste.get_body_sizes().last().map(BodySize::get_size_cm3),
+                ],
+            )?;
+            for tag in caste.get_tags() {
+                let (token, value) = caste_tag_token_and_value(tag);
+                self.insert(
+                    "INSERT INTO caste_tags (caste_id, token, value) VALUES (?1, ?2, ?3)",
+                    params![caste_id, token, value],
+                )?;
+            }
+        }
+
+        for biome in creature.get_biomes() {
+            let biome_id = self.biome_id(&biome)?;
+            self.insert(
+                "INSERT OR IGNORE INTO creature_biomes (creature_id, biome_id) VALUES (?1, ?2)",
+                params![creature_id, biome_id],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_plant(&mut self, plant: &Plant) -> Result<(), ParserError> {
+        let plant_id = self.insert(
+            "INSERT INTO plants (module_id, object_id, identifier, name, raw_file)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                self.module_id(plant),
+                plant.get_object_id(),
+                plant.get_identifier(),
+                plant.get_name(),
+                plant.get_metadata().get_raw_file_path(),
+            ],
+        )?;
+        for material in plant.get_materials() {
+            self.write_material(material, Some(plant_id), None)?;
+        }
+        for biome in plant.get_biomes() {
+            let biome_id = self.biome_id(&biome)?;
+            self.insert(
+                "INSERT OR IGNORE INTO plant_biomes (plant_id, biome_id) VALUES (?1, ?2)",
+                params![plant_id, biome_id],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_inorganic(&self, inorganic: &Inorganic) -> Result<(), ParserError> {
+        let inorganic_id = self.insert(
+            "INSERT INTO inorganics (module_id, object_id, identifier, raw_file)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                self.module_id(inorganic),
+                inorganic.get_object_id(),
+                inorganic.get_identifier(),
+                inorganic.get_metadata().get_raw_file_path(),
+            ],
+        )?;
+        self.write_material(inorganic.get_material(), None, Some(inorganic_id))
+    }
+
+    fn write_material(
+        &self,
+        material: &Material,
+        plant_id: Option<i64>,
+        inorganic_id: Option<i64>,
+    ) -> Result<(), ParserError> {
+        self.insert(
+            "INSERT INTO materials (plant_id, inorganic_id, name, material_type, value)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                plant_id,
+                inorganic_id,
+                material.get_name(),
+                material
+                    .get_material_type()
+                    .map(|material_type| format!("{material_type:?}")),
+                material.get_value(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn write_entity(&self, entity: &Entity) -> Result<(), ParserError> {
+        let entity_id = self.insert(
+            "INSERT INTO entities (module_id, creature_id, object_id, identifier, creature, raw_file)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                self.module_id(entity),
+                entity
+                    .get_creature()
+                    .and_then(|creature| self.creatures.get(creature)),
+                entity.get_object_id(),
+                entity.get_identifier(),
+                entity.get_creature(),
+                entity.get_metadata().get_raw_file_path(),
+            ],
+        )?;
+        for position in entity.get_positions() {
+            self.insert(
+                "INSERT INTO positions (entity_id, identifier, name) VALUES (?1, ?2, ?3)",
+                params![
+                    entity_id,
+                    position.get_identifier(),
+                    position.get_name().map(Name::get_singular),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The raw token of a caste tag (e.g. `FLIER`), and its value as JSON (`None` for flags).
+fn caste_tag_token_and_value(tag: &CasteTag) -> (String, Option<String>) {
+    // The first name by sort order, so the same tag is always stored with the same token
+    let token = names_of(&CASTE_TOKEN_NAMES, tag)
+        .first()
+        .map_or_else(|| format!("{tag:?}"), ToString::to_string);
+    // Tags with values serialize as `{ "Variant": value }`, flags as `"Variant"`
+    let value = match serde_json::to_value(tag) {
+        Ok(serde_json::Value::Object(map)) => map.values().next().map(ToString::to_string),
+        _ => None,
+    };
+    (token, value)
+}
@@ -366,6 +366,14 @@ fn generate_ts_bindings() {
                 String::new()
             }
         },
+        match export::<dfraw_parser::ColorPalette>(&config) {
+            Ok(x) => x,
+            Err(e) => {
+                eprintln!("Failed to export dfraw_parser::ColorPalette");
+                eprintln!("{e:?}");
+                String::new()
+            }
+        },
         match export::<dfraw_parser::Creature>(&config) {
             Ok(x) => x,
             Err(e) => {
@@ -558,6 +566,14 @@ fn generate_ts_bindings() {
                 String::new()
             }
         },
+        match export::<dfraw_parser::SpriteLayerGroup>(&config) {
+            Ok(x) => x,
+            Err(e) => {
+                eprintln!("Failed to export dfraw_parser::SpriteLayerGroup");
+                eprintln!("{e:?}");
+                String::new()
+            }
+        },
         match export::<dfraw_parser::StateNames>(&config) {
             Ok(x) => x,
             Err(e) => {
@@ -0,0 +1,67 @@
+#![cfg(feature = "arrow")]
+
+use dfraw_json_parser::columnar::build_tables;
+use dfraw_parser::{metadata::RawMetadata, traits::RawObject, Creature, Inorganic, Plant};
+
+fn parse_into<T: RawObject>(mut raw: T, tokens: &str) -> Box<dyn RawObject> {
+    for token in tokens
+        .split(['[', ']'])
+        .filter(|token| !token.trim().is_empty())
+    {
+        let (key, value) = token.split_once(':').unwrap_or((token, ""));
+        raw.parse_tag(key, value);
+    }
+    Box::new(raw)
+}
+
+const MATERIAL_TOKENS: &str = "[STATE_NAME_ADJ:ALL_SOLID:rock][MATERIAL_VALUE:5]
+    [LIQUID_DENSITY:2500][MOLAR_MASS:60]
+    [IMPACT_YIELD:10][IMPACT_FRACTURE:20][IMPACT_ELASTICITY:30]
+    [COMPRESSIVE_YIELD:10][COMPRESSIVE_FRACTURE:20][COMPRESSIVE_ELASTICITY:30]
+    [TENSILE_YIELD:10][TENSILE_FRACTURE:20][TENSILE_ELASTICITY:30]
+    [TORSION_YIELD:10][TORSION_FRACTURE:20][TORSION_ELASTICITY:30]
+    [SHEAR_YIELD:10][SHEAR_FRACTURE:20][SHEAR_ELASTICITY:30]
+    [BENDING_YIELD:10][BENDING_FRACTURE:20][BENDING_ELASTICITY:30]
+    [MAX_EDGE:100][SOLID_DENSITY:2600]
+    [SPEC_HEAT:800][IGNITE_POINT:11000][MELTING_POINT:12000][BOILING_POINT:14000]
+    [HEATDAM_POINT:11500][COLDDAM_POINT:9000][MAT_FIXED_TEMP:10000]";
+
+#[test]
+fn every_column_is_read_from_the_raws() {
+    let metadata = RawMetadata::default();
+    let raws = vec![
+        parse_into(
+            Creature::new("TOAD", &metadata),
+            "[NAME:toad:toads:toad][FREQUENCY:10][CLUSTER_NUMBER:1:3][POPULATION_NUMBER:5:10]
+            [UNDERGROUND_DEPTH:1:2]
+            [DESCRIPTION:A small toad.][CASTE_NAME:toad:toads:toad][BODY_SIZE:0:0:100]
+            [CLUTCH_SIZE:1:2][LITTER_SIZE:1:2][MAXAGE:1:2][BABY:1][CHILD:2][DIFFICULTY:1]
+            [EGG_SIZE:1][GRASS_TRAMPLE:1][GRAZER:1][LOW_LIGHT_VISION:1][PETVALUE:10][POP_RATIO:1]
+            [CHANGE_BODY_SIZE_PERC:50][FLIER]",
+        ),
+        parse_into(
+            Plant::new("BERRY", &metadata),
+            "[NAME:berry:berries:berry][FREQUENCY:10][UNDERGROUND_DEPTH:1:2]
+            [GROWTH:FRUIT][GROWTH_NAME:berry:berries][GROWTH_ITEM:PLANT_GROWTH:NONE:LOCAL_PLANT_MAT:FRUIT]
+            [GROWTH_DENSITY:1000][GROWTH_TIMING:1:2][GROWTH_TRUNK_HEIGHT_PERC:1:2]
+            [GROWTH_PRINT:'%':'%':4:0:0:ALL:1][GROWTH_HAS_SEED]
+            [MATERIAL:STONE:BERRY_STONE]",
+        ),
+        parse_into(
+            Inorganic::new("ROCK", &metadata),
+            &format!("[USE_MATERIAL_TEMPLATE:STONE_TEMPLATE]{MATERIAL_TOKENS}"),
+        ),
+    ];
+
+    let tables = build_tables(&raws).expect("Failed to build the tables");
+    for (name, batch) in tables.tables() {
+        assert!(batch.num_rows() > 0, "{name} has no rows");
+        for (field, column) in batch.schema().fields().iter().zip(batch.columns()) {
+            assert!(
+                column.null_count() < column.len(),
+                "{name}.{} has no data",
+                field.name()
+            );
+        }
+    }
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "sqlite")]
+
+use dfraw_json_parser::sqlite::write_to_connection;
+use dfraw_parser::{metadata::RawMetadata, traits::RawObject, Creature, ParseResult};
+use rusqlite::Connection;
+
+fn creature(identifier: &str, tokens: &str) -> Box<dyn RawObject> {
+    let mut creature = Creature::new(identifier, &RawMetadata::default());
+    for token in tokens
+        .split(['[', ']'])
+        .filter(|token| !token.trim().is_empty())
+    {
+        let (key, value) = token.split_once(':').unwrap_or((token, ""));
+        creature.parse_tag(key, value);
+    }
+    Box::new(creature)
+}
+
+fn export(raws: Vec<Box<dyn RawObject>>) -> Connection {
+    let result = ParseResult {
+        raws,
+        info_files: Vec::new(),
+    };
+    let mut connection = Connection::open_in_memory().expect("Failed to open the database");
+    write_to_connection(&result, &mut connection).expect("Failed to write the database");
+    connection
+}
+
+#[test]
+fn castes_can_be_queried_by_tag_and_body_size() {
+    let connection = export(vec![
+        creature(
+            "EAGLE",
+            "[NAME:eagle:eagles:eagle][BIOME:MOUNTAIN][BODY_SIZE:0:0:5000][FLIER]",
+        ),
+        creature(
+            "ROC",
+            "[NAME:roc:rocs:roc][BIOME:MOUNTAIN][BODY_SIZE:0:0:100][BODY_SIZE:5:0:500000][FLIER]",
+        ),
+        creature(
+            "ELEPHANT",
+            "[NAME:elephant:elephants:elephant][BODY_SIZE:0:0:5000000][NATURAL_ANIMAL]",
+        ),
+    ]);
+
+    let mut statement = connection
+        .prepare(
+            "SELECT creatures.identifier FROM castes
+             JOIN creatures ON creatures.id = castes.creature_id
+             JOIN caste_tags ON caste_tags.caste_id = castes.id
+             WHERE caste_tags.token = 'FLIER' AND castes.body_size > 70000",
+        )
+        .expect("Failed to prepare the query");
+    let identifiers = statement
+        .query_map([], |row| row.get::<_, String>(0))
+        .expect("Failed to run the query")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Failed to read the rows");
+    assert_eq!(identifiers, vec![String::from("ROC")]);
+
+    let biome_creatures: i64 = connection
+        .query_row(
+            "SELECT COUNT(*) FROM creature_biomes
+             JOIN biomes ON biomes.id = creature_biomes.biome_id
+             WHERE biomes.token = 'MOUNTAIN'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("Failed to count the creatures");
+    assert_eq!(biome_creatures, 2);
+}
+
+#[test]
+fn tags_with_several_tokens_are_stored_with_the_first_by_name() {
+    let connection = export(vec![creature(
+        "ELEPHANT",
+        "[NAME:elephant:elephants:elephant][NATURAL_ANIMAL][MISCHIEVOUS]",
+    )]);
+
+    let mut statement = connection
+        .prepare("SELECT token FROM caste_tags ORDER BY token")
+        .expect("Failed to prepare the query");
+    let tokens = statement
+        .query_map([], |row| row.get::<_, String>(0))
+        .expect("Failed to run the query")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Failed to read the rows");
+    assert_eq!(
+        tokens,
+        vec![String::from("MISCHIEVIOUS"), String::from("NATURAL")]
+    );
+}
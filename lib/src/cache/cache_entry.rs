@@ -0,0 +1,51 @@
+use std::{path::PathBuf, time::SystemTime};
+
+use crate::{
+    reader::{FileParseResult, UnprocessedRaw},
+    traits::RawObject,
+};
+
+/// The cached parse result for a single raw file.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheEntry {
+    /// The version of the parser which wrote the entry.
+    pub parser_version: String,
+    /// The path of the raw file.
+    pub path: PathBuf,
+    /// The size of the raw file (in bytes).
+    pub size: u64,
+    /// The modified time of the raw file (if the platform supports it).
+    pub modified: Option<SystemTime>,
+    /// The hash of the raw file's contents.
+    pub content_hash: u64,
+    /// The hash of everything else which affects the parse result (the options and `info.txt`).
+    pub context_hash: u64,
+    /// The parsed raws from the file.
+    pub parsed_raws: Vec<Box<dyn RawObject>>,
+    /// The unprocessed raws from the file.
+    pub unprocessed_raws: Vec<UnprocessedRaw>,
+}
+
+impl CacheEntry {
+    /// The version of the parser, which is stored with each entry.
+    pub const PARSER_VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    /// Set whether the metadata of the cached raws is hidden (the flag isn't serialized, so it is
+    /// lost when the entry is read).
+    pub fn set_metadata_hidden(&mut self, hidden: bool) {
+        for raw in &mut self.parsed_raws {
+            raw.set_metadata_hidden(hidden);
+        }
+        for raw in &mut self.unprocessed_raws {
+            raw.set_metadata_hidden(hidden);
+        }
+    }
+    /// Convert the entry into the parse result it was made from.
+    pub fn into_file_parse_result(self) -> FileParseResult {
+        FileParseResult {
+            parsed_raws: self.parsed_raws,
+            unprocessed_raws: self.unprocessed_raws,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! A persistent cache of raw file parse results, so unchanged raw files don't need to be parsed again.
+//!
+//! Each raw file's `FileParseResult` (including its `UnprocessedRaw`s) is saved in a cache directory,
+//! keyed by the path of the raw file. An entry is re-used when the raw file has the same size and
+//! modified time as when it was cached, or (if those changed) when its contents hash the same.
+//! Entries are also discarded if the options which affect parsing or the module's `info.txt`
+//! changed, or if they were written by a different version of the parser.
+//!
+//! Only the per-file parsing is cached. Creature resolution (copy tags from, creature variations)
+//! is always done again, since it depends on every file.
+//!
+//! The cache is used by `parse`, `parse_iter` and `parse_module` when `ParserOptions::cache_directory`
+//! is set. It can also be used directly:
+//!
+//! ```rust
+//! use dfraw_parser::{cache::ParseCache, metadata::ParserOptions};
+//!
+//! let options = ParserOptions::default();
+//! let cache = ParseCache::new("path/to/cache");
+//!
+//! // Parses the file the first time, and reads it from the cache after that.
+//! let result = cache.parse_raw_file(&"path/to/creature_standard.txt", &options);
+//! # assert!(result.is_err());
+//! ```
+
+mod cache_entry;
+mod parse_cache;
+
+pub use parse_cache::ParseCache;
+
+use std::path::Path;
+
+use crate::{
+    metadata::ParserOptions,
+    reader::{self, FileParseResult},
+    ParserError,
+};
+
+/// Parse a raw file, using the cache in `options.cache_directory` if one is set.
+///
+/// # Errors
+///
+/// * `ParserError` - Any error from parsing the raw file.
+pub(crate) fn parse_raw_file<P: AsRef<Path>>(
+    raw_file_path: &P,
+    options: &ParserOptions,
+) -> Result<FileParseResult, ParserError> {
+    options.cache_directory.as_ref().map_or_else(
+        || reader::parse_raw_file(raw_file_path, options),
+        |cache_directory| ParseCache::new(cache_directory).parse_raw_file(raw_file_path, options),
+    )
+}
@@ -0,0 +1,208 @@
+use std::{
+    fs,
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use tracing::{debug, warn};
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::{
+    metadata::ParserOptions,
+    reader::{parse_raw_file, FileParseResult},
+    ParserError,
+};
+
+use super::cache_entry::CacheEntry;
+
+/// A directory of cached raw file parse results.
+///
+/// Problems reading or writing the cache are logged and otherwise ignored; the raw file is just
+/// parsed as if there was no cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCache {
+    /// The directory the cache entries are stored in.
+    directory: PathBuf,
+}
+
+impl ParseCache {
+    /// Create a cache which stores its entries in the given directory.
+    ///
+    /// The directory is created when the first entry is written.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory to store the cache entries in.
+    pub fn new<P: AsRef<Path>>(directory: P) -> Self {
+        Self {
+            directory: directory.as_ref().to_path_buf(),
+        }
+    }
+    /// The directory the cache entries are stored in.
+    #[must_use]
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+    /// Parse a raw file, re-using the cached result if the file hasn't changed.
+    ///
+    /// The result is the same as `parse_raw_file`. If the file was parsed, the result is saved to
+    /// the cache for next time.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw_file_path` - The path to the raw file to parse.
+    /// * `options` - The parser options to use when parsing the raw file.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError` - Any error from parsing the raw file (errors are not cached).
+    pub fn parse_raw_file<P: AsRef<Path>>(
+        &self,
+        raw_file_path: &P,
+        options: &ParserOptions,
+    ) -> Result<FileParseResult, ParserError> {
+        let raw_file_path = raw_file_path.as_ref();
+        let Ok(file_metadata) = fs::metadata(raw_file_path) else {
+            // Let the parser report the problem with the file.
+            return parse_raw_file(&raw_file_path, options);
+        };
+        let size = file_metadata.len();
+        let modified = file_metadata.modified().ok();
+        let context = context_hash(raw_file_path, options);
+        let entry_path = self.entry_path(raw_file_path);
+
+        let cached = Self::read_entry(&entry_path, options)
+            .filter(|entry| entry.path == raw_file_path && entry.context_hash == context);
+
+        // Unchanged size and modified time: trust the cache without reading the file.
+        let cached = match cached {
+            Some(entry)
+                if entry.size == size && modified.is_some() && entry.modified == modified =>
+            {
+                debug!("Using cached parse result for {}", raw_file_path.display());
+                return Ok(entry.into_file_parse_result());
+            }
+            cached => cached,
+        };
+
+        let content_hash = xxh3_64(&fs::read(raw_file_path)?);
+
+        // Same contents (e.g. the file was touched or copied): update the entry's size and time.
+        if let Some(mut entry) = cached.filter(|entry| entry.content_hash == content_hash) {
+            debug!(
+                "Using cached parse result for {} (contents unchanged)",
+                raw_file_path.display()
+            );
+            entry.size = size;
+            entry.modified = modified;
+            self.write_entry(&entry_path, &entry);
+            return Ok(entry.into_file_parse_result());
+        }
+
+        debug!("Parsing {} (not cached)", raw_file_path.display());
+        let result = parse_raw_file(&raw_file_path, options)?;
+        let entry = CacheEntry {
+            parser_version: CacheEntry::PARSER_VERSION.to_string(),
+            path: raw_file_path.to_path_buf(),
+            size,
+            modified,
+            content_hash,
+            context_hash: context,
+            parsed_raws: result.parsed_raws,
+            unprocessed_raws: result.unprocessed_raws,
+        };
+        self.write_entry(&entry_path, &entry);
+
+        Ok(entry.into_file_parse_result())
+    }
+    /// Remove every entry from the cache.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Io` - If an entry can't be removed.
+    pub fn clear(&self) -> Result<(), ParserError> {
+        if !self.directory.is_dir() {
+            return Ok(());
+        }
+        for dir_entry in fs::read_dir(&self.directory)? {
+            let path = dir_entry?.path();
+            if path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+            {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+    /// The path of the cache entry for a raw file.
+    fn entry_path(&self, raw_file_path: &Path) -> PathBuf {
+        let path_hash = xxh3_64(raw_file_path.to_string_lossy().as_bytes());
+        self.directory.join(format!("{path_hash:016x}.json"))
+    }
+    /// Read a cache entry, if there is a usable one.
+    fn read_entry(entry_path: &Path, options: &ParserOptions) -> Option<CacheEntry> {
+        let file = fs::File::open(entry_path).ok()?;
+        let mut entry: CacheEntry = serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| debug!("Discarding unreadable cache entry {entry_path:?}: {e}"))
+            .ok()?;
+        if entry.parser_version != CacheEntry::PARSER_VERSION {
+            return None;
+        }
+        // Metadata's `hidden` flag isn't serialized; it always comes from the options.
+        entry.set_metadata_hidden(!options.attach_metadata_to_raws);
+        Some(entry)
+    }
+    /// Write a cache entry, logging (and otherwise ignoring) any problems.
+    fn write_entry(&self, entry_path: &Path, entry: &CacheEntry) {
+        if let Err(e) = self.try_write_entry(entry_path, entry) {
+            warn!(
+                "Unable to write cache entry for {}: {e}",
+                entry.path.display()
+            );
+        }
+    }
+    /// Write a cache entry to a temporary file and move it into place, so that a partially written
+    /// entry is never read.
+    fn try_write_entry(&self, entry_path: &Path, entry: &CacheEntry) -> Result<(), ParserError> {
+        fs::create_dir_all(&self.directory)?;
+        let temp_path = entry_path.with_extension(format!(
+            "{}.tmp",
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+        ));
+
+        let mut writer = BufWriter::new(fs::File::create(&temp_path)?);
+        serde_json::to_writer(&mut writer, entry).map_err(std::io::Error::from)?;
+        writer.flush()?;
+        drop(writer);
+
+        fs::rename(&temp_path, entry_path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp_path);
+        })?;
+        Ok(())
+    }
+}
+
+/// Hash everything apart from the raw file itself which affects its parse result: the options
+/// which change what is parsed, and the module's `info.txt` (used for the metadata).
+fn context_hash(raw_file_path: &Path, options: &ParserOptions) -> u64 {
+    // The info.txt is found the same way as `InfoFile::from_raw_file_path` does.
+    let info_file_contents = raw_file_path
+        .parent()
+        .and_then(Path::parent)
+        .and_then(|module_path| fs::read(module_path.join("info.txt")).ok())
+        .unwrap_or_default();
+
+    let mut context = serde_json::to_vec(&(
+        options.attach_metadata_to_raws,
+        &options.object_types_to_parse,
+    ))
+    .unwrap_or_default();
+    context.extend_from_slice(&info_file_contents);
+
+    xxh3_64(&context)
+}
@@ -28,4 +28,19 @@ pub enum Parser {
     /// Cannot parse the target from the given string
     #[error("Target for parsing cannot be opened: {0}")]
     NothingToParse(String),
+    /// A graphic could not be rendered
+    #[error("Unable to render graphic: {0}")]
+    Render(String),
+    /// A legends export could not be read
+    #[error("Invalid legends export: {0}")]
+    InvalidLegendsExport(String),
+    /// The raws could not be exported
+    #[error("Unable to export raws: {0}")]
+    Export(String),
+    /// A raw query could not be parsed
+    #[error("Invalid query: {0}")]
+    InvalidQuery(String),
+    /// A page of raws could not be found from a cursor
+    #[error("Invalid page cursor: {0}")]
+    InvalidCursor(String),
 }
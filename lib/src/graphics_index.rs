@@ -0,0 +1,293 @@
+//! An index linking `Graphic`s to the raws they draw and the tile pages they use.
+//!
+//! Graphics only refer to their target by identifier (`[CREATURE_GRAPHICS:DWARF]`,
+//! `[CREATURE_CASTE_GRAPHICS:DWARF:FEMALE]`, `[PLANT_GRAPHICS:MUSHROOM_HELMET_PLUMP]` or
+//! `[TILE_GRAPHICS:MARBLE]`) and to tile pages by their identifier, which can be in any module.
+//! A `GraphicsIndex` joins them up once, so it's easy to ask which graphics apply to a raw.
+//!
+//! Raws are indexed in the order they were parsed, and mods override in the same way the game
+//! does: a later module's raw (or tile page) with the same identifier replaces an earlier one, and
+//! a later module's graphics of one kind for a target replace an earlier module's graphics of that
+//! kind for the target. So a mod which only adds `[STATUE_CREATURE_GRAPHICS:DWARF]` keeps vanilla's
+//! `[CREATURE_GRAPHICS:DWARF]`, while a mod with its own `[CREATURE_GRAPHICS:DWARF]` replaces it
+//! (with all of its layer sets).
+//!
+//! The graphics for a caste are a separate target from the graphics for its creature, so a later
+//! module's `CREATURE_GRAPHICS` don't hide an earlier module's `CREATURE_CASTE_GRAPHICS`; the caste
+//! graphics are only replaced by later caste graphics for the same caste.
+//!
+//! ```rust,no_run
+//! use dfraw_parser::{
+//!     graphics_index::GraphicsIndex,
+//!     metadata::{ParserOptions, RawModuleLocation},
+//!     traits::RawObject,
+//! };
+//!
+//! let mut options = ParserOptions::new("path/to/dwarf_fortress");
+//! options.add_location_to_parse(RawModuleLocation::Vanilla);
+//! options.include_graphics();
+//! let result = dfraw_parser::parse(&options).unwrap();
+//!
+//! let index = GraphicsIndex::from_parse_result(&result);
+//! for graphic in index.graphics_for_caste("DWARF", "FEMALE") {
+//!     println!("{} uses {:?}", graphic.get_object_id(), graphic.get_tile_pages());
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use tracing::warn;
+
+use crate::{
+    graphic::Graphic, metadata::ObjectType, tags::GraphicTypeTag, tile_page::TilePage,
+    traits::RawObject, ParseResult,
+};
+
+/// The key of a graphic's target: the target's object type, identifier and caste (if any).
+type TargetKey = (ObjectType, String, Option<String>);
+
+/// The graphics of one kind (e.g. `CREATURE_GRAPHICS`) for a target, with the module they are
+/// from.
+type GraphicsOfKind<'a> = (GraphicTypeTag, String, Vec<&'a Graphic>);
+
+/// An index of which graphics apply to which raws, and which tile pages they use.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Default)]
+pub struct GraphicsIndex<'a> {
+    /// The raws that graphics can target, by object type and identifier.
+    targets: HashMap<(ObjectType, String), &'a dyn RawObject>,
+    /// The tile pages, by identifier.
+    tile_pages: HashMap<String, &'a TilePage>,
+    /// The graphics for each target, by kind (in the order each kind was first added).
+    graphics: HashMap<TargetKey, Vec<GraphicsOfKind<'a>>>,
+}
+
+impl<'a> GraphicsIndex<'a> {
+    /// Build an index over some parsed raws.
+    ///
+    /// # Arguments
+    ///
+    /// * `raws` - The parsed raws (in the order they were parsed, so later mods win).
+    ///
+    /// # Returns
+    ///
+    /// * `GraphicsIndex` - The index.
+    #[must_use]
+    pub fn new(raws: &'a [Box<dyn RawObject>]) -> Self {
+        let mut index = Self::default();
+        for raw in raws {
+            match raw.get_type() {
+                ObjectType::Graphics => {
+                    if let Some(graphic) = raw.as_any().downcast_ref::<Graphic>() {
+                        index.add_graphic(graphic);
+                    }
+                }
+                ObjectType::TilePage => {
+                    if let Some(tile_page) = raw.as_any().downcast_ref::<TilePage>() {
+                        index
+                            .tile_pages
+                            .insert(tile_page.get_identifier().to_string(), tile_page);
+                    }
+                }
+                object_type => {
+                    index.targets.insert(
+                        (object_type.clone(), raw.get_identifier().to_string()),
+                        raw.as_ref(),
+                    );
+                }
+            }
+        }
+        index
+    }
+    /// Build an index over the raws of a parse result.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The result of parsing (which should include graphics and tile pages).
+    ///
+    /// # Returns
+    ///
+    /// * `GraphicsIndex` - The index.
+    #[must_use]
+    pub fn from_parse_result(result: &'a ParseResult) -> Self {
+        Self::new(&result.raws)
+    }
+    /// Add a graphic for its target. Graphics of the same kind from a different module than the
+    /// ones already there replace them (the module parsed later wins).
+    fn add_graphic(&mut self, graphic: &'a Graphic) {
+        let Some(object_type) = graphic.get_target_object_type() else {
+            return;
+        };
+        let kind = graphic.get_graphic_type();
+        let module = graphic.get_metadata().get_module_object_id().to_string();
+        let key = (
+            object_type,
+            graphic.get_identifier().to_string(),
+            graphic.get_caste_identifier().map(String::from),
+        );
+        let kinds = self.graphics.entry(key).or_default();
+        if let Some((_, graphics_module, graphics)) = kinds
+            .iter_mut()
+            .find(|(graphics_kind, _, _)| *graphics_kind == kind)
+        {
+            if *graphics_module != module {
+                graphics.clear();
+                graphics_module.clone_from(&module);
+            }
+            graphics.push(graphic);
+        } else {
+            kinds.push((kind, module, vec![graphic]));
+        }
+    }
+    /// Get the graphics for a target, of every kind.
+    fn graphics_of(&self, key: &TargetKey) -> Vec<&'a Graphic> {
+        self.graphics
+            .get(key)
+            .map(|kinds| {
+                kinds
+                    .iter()
+                    .flat_map(|(_, _, graphics)| graphics.iter().copied())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+    /// Get the graphics for an object (not including the graphics for only some of its castes).
+    ///
+    /// # Arguments
+    ///
+    /// * `object_type` - The type of the object (e.g. `ObjectType::Plant`).
+    /// * `identifier` - The identifier of the object (e.g. `MUSHROOM_HELMET_PLUMP`).
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&Graphic>` - The graphics for the object.
+    #[must_use]
+    pub fn graphics_for(&self, object_type: &ObjectType, identifier: &str) -> Vec<&'a Graphic> {
+        self.graphics_of(&(object_type.clone(), identifier.to_string(), None))
+    }
+    /// Get the graphics which apply to one caste of a creature: the creature's graphics, then the
+    /// graphics for that caste (`CREATURE_CASTE_GRAPHICS`, which a later module's graphics for the
+    /// whole creature don't replace).
+    ///
+    /// # Arguments
+    ///
+    /// * `creature` - The identifier of the creature (e.g. `DWARF`).
+    /// * `caste` - The identifier of the caste (e.g. `FEMALE`).
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&Graphic>` - The graphics for the caste.
+    #[must_use]
+    pub fn graphics_for_caste(&self, creature: &str, caste: &str) -> Vec<&'a Graphic> {
+        let mut graphics = self.graphics_for(&ObjectType::Creature, creature);
+        graphics.extend(self.graphics_of(&(
+            ObjectType::Creature,
+            creature.to_string(),
+            Some(caste.to_string()),
+        )));
+        graphics
+    }
+    /// Get every graphic for a creature, including the graphics for each of its castes.
+    ///
+    /// # Arguments
+    ///
+    /// * `creature` - The identifier of the creature (e.g. `DWARF`).
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&Graphic>` - The graphics for the creature and its castes.
+    #[must_use]
+    pub fn graphics_for_creature(&self, creature: &str) -> Vec<&'a Graphic> {
+        let mut graphics = self.graphics_for(&ObjectType::Creature, creature);
+        let mut caste_graphics: Vec<_> = self
+            .graphics
+            .iter()
+            .filter(|((object_type, identifier, caste), _)| {
+                *object_type == ObjectType::Creature && identifier == creature && caste.is_some()
+            })
+            .collect();
+        // Keep the castes in a stable order
+        caste_graphics.sort_by(|(a, _), (b, _)| a.2.cmp(&b.2));
+        graphics.extend(
+            caste_graphics
+                .into_iter()
+                .flat_map(|(_, kinds)| kinds)
+                .flat_map(|(_, _, graphics)| graphics),
+        );
+        graphics
+    }
+    /// Get the raw a graphic is for.
+    ///
+    /// # Arguments
+    ///
+    /// * `graphic` - The graphic.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&dyn RawObject>` - The target raw, or `None` if it wasn't parsed (or the graphic
+    ///   doesn't target a raw).
+    #[must_use]
+    pub fn target_of(&self, graphic: &Graphic) -> Option<&'a dyn RawObject> {
+        let object_type = graphic.get_target_object_type()?;
+        self.targets
+            .get(&(object_type, graphic.get_identifier().to_string()))
+            .copied()
+    }
+    /// Get a tile page by its identifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `identifier` - The identifier of the tile page.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&TilePage>` - The tile page (from the last module which defines it).
+    #[must_use]
+    pub fn tile_page(&self, identifier: &str) -> Option<&'a TilePage> {
+        self.tile_pages.get(identifier).copied()
+    }
+    /// Get the tile pages a graphic uses. Tile pages which weren't parsed are logged and skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `graphic` - The graphic.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&TilePage>` - The tile pages (each only once).
+    #[must_use]
+    pub fn tile_pages_of(&self, graphic: &Graphic) -> Vec<&'a TilePage> {
+        let mut tile_pages: Vec<&'a TilePage> = Vec::new();
+        for identifier in graphic.get_tile_pages() {
+            let Some(tile_page) = self.tile_page(&identifier) else {
+                warn!(
+                    "tile_pages_of: {} uses missing tile page {identifier}",
+                    graphic.get_object_id()
+                );
+                continue;
+            };
+            if !tile_pages.iter().any(|t| std::ptr::eq(*t, tile_page)) {
+                tile_pages.push(tile_page);
+            }
+        }
+        tile_pages
+    }
+    /// Get the graphics whose target raw wasn't parsed.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&Graphic>` - The graphics without a target (sorted by object id).
+    #[must_use]
+    pub fn unlinked_graphics(&self) -> Vec<&'a Graphic> {
+        let mut graphics: Vec<&'a Graphic> = self
+            .graphics
+            .values()
+            .flatten()
+            .flat_map(|(_, _, graphics)| graphics)
+            .filter(|graphic| self.target_of(graphic).is_none())
+            .copied()
+            .collect();
+        graphics.sort_by(|a, b| a.get_object_id().cmp(b.get_object_id()));
+        graphics
+    }
+}
@@ -0,0 +1,15 @@
+/// How grown up a creature is, for the `CONDITION_BABY`, `CONDITION_CHILD` and
+/// `CONDITION_NOT_CHILD` layer conditions.
+#[derive(
+    serde::Serialize, serde::Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum AgeStage {
+    /// A baby
+    Baby,
+    /// A child
+    Child,
+    /// An adult (the default)
+    #[default]
+    Adult,
+}
@@ -0,0 +1,59 @@
+/// Identifies a body part of the creature being rendered.
+///
+/// Layer conditions select body parts `BY_TOKEN`, `BY_CATEGORY` or `BY_TYPE`, so a body part is
+/// described by all three.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct BodyPartRef {
+    /// The body part's token (e.g. `UB`)
+    pub token: String,
+    /// The body part's category (e.g. `BODY_UPPER`)
+    pub category: String,
+    /// The body part's types (e.g. `UPPERBODY`, `GRASP`)
+    pub types: Vec<String>,
+}
+
+impl BodyPartRef {
+    /// Create a body part reference.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The body part's token.
+    /// * `category` - The body part's category.
+    #[must_use]
+    pub fn new(token: &str, category: &str) -> Self {
+        Self {
+            token: String::from(token),
+            category: String::from(category),
+            types: Vec::new(),
+        }
+    }
+    /// Add a body part type (e.g. `GRASP`) to the body part.
+    #[must_use]
+    pub fn with_type(mut self, body_part_type: &str) -> Self {
+        self.types.push(String::from(body_part_type));
+        self
+    }
+    /// Whether this body part is selected by a condition's selector.
+    ///
+    /// # Arguments
+    ///
+    /// * `selector` - How the body part is selected (`BY_TOKEN`, `BY_CATEGORY` or `BY_TYPE`).
+    /// * `name` - The token, category or type to select (`ALL` selects every body part).
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `true` if the body part is selected.
+    #[must_use]
+    pub fn is_selected_by(&self, selector: &str, name: &str) -> bool {
+        if name == "ALL" {
+            return true;
+        }
+        match selector {
+            "BY_TOKEN" => self.token == name,
+            "BY_CATEGORY" => self.category == name,
+            "BY_TYPE" => self.types.iter().any(|t| t == name),
+            _ => false,
+        }
+    }
+}
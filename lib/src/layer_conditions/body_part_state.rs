@@ -0,0 +1,41 @@
+use super::BodyPartRef;
+
+/// The state of a body part of the creature being rendered, for the `CONDITION_BP` layer
+/// conditions.
+///
+/// Body parts without a state are treated as present, unscarred and with default appearance
+/// modifiers.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct BodyPartState {
+    /// The body part
+    pub body_part: BodyPartRef,
+    /// Whether the body part is missing
+    pub missing: bool,
+    /// Whether the body part is scarred
+    pub scarred: bool,
+    /// The body part's appearance modifiers (e.g. `("LENGTH", 90)`)
+    pub appearance_modifiers: Vec<(String, i32)>,
+}
+
+impl BodyPartState {
+    /// The value used for appearance modifiers which aren't given.
+    pub const DEFAULT_APPEARANCE_MODIFIER: i32 = 100;
+
+    /// Get the value of an appearance modifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `modifier` - The appearance modifier (e.g. `LENGTH`).
+    ///
+    /// # Returns
+    ///
+    /// * `i32` - The value of the modifier, or `DEFAULT_APPEARANCE_MODIFIER` if it isn't given.
+    #[must_use]
+    pub fn get_appearance_modifier(&self, modifier: &str) -> i32 {
+        self.appearance_modifiers
+            .iter()
+            .find(|(name, _)| name == modifier)
+            .map_or(Self::DEFAULT_APPEARANCE_MODIFIER, |(_, value)| *value)
+    }
+}
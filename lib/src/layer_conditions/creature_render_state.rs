@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use crate::{graphic::Graphic, sprite_layer::SpriteLayer, sprite_layer_group::SpriteLayerGroup};
+
+use super::{evaluator::LayerEvaluator, AgeStage, BodyPartState, TissueState, WornItem};
+
+/// The state of a creature to render, used to decide which of a graphic's layers are visible.
+///
+/// Everything defaults to an adult with no caste, items, profession, syndromes or hair; fill in
+/// as much as is known about the creature.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatureRenderState {
+    /// The creature's caste (e.g. `FEMALE`), for `CONDITION_CASTE`
+    pub caste: Option<String>,
+    /// How grown up the creature is
+    pub age: AgeStage,
+    /// The creature's classes, for `CONDITION_CLASS`
+    pub creature_classes: Vec<String>,
+    /// The items the creature is wearing or holding
+    pub worn_items: Vec<WornItem>,
+    /// The creature's profession and profession categories (e.g. `MINER`, `MILITARY`), for
+    /// `CONDITION_PROFESSION_CATEGORY`
+    pub professions: Vec<String>,
+    /// The state of the creature's body parts, for `CONDITION_BP`
+    pub body_parts: Vec<BodyPartState>,
+    /// The creature's tissue layers (e.g. hair), for `CONDITION_TISSUE_LAYER`
+    pub tissues: Vec<TissueState>,
+    /// The classes of the syndromes affecting the creature, for `CONDITION_SYN_CLASS`
+    pub syndrome_classes: Vec<String>,
+    /// Whether the creature is a ghost
+    pub ghost: bool,
+    /// How many items the creature is hauling, for `CONDITION_HAUL_COUNT_MIN`/`MAX`
+    pub haul_count: u32,
+    /// The chosen variant for each randomized part (e.g. `HEAD` -> 3), for
+    /// `CONDITION_RANDOM_PART_INDEX`. Parts which aren't given use the first variant (1).
+    pub random_part_indices: HashMap<String, u32>,
+}
+
+impl CreatureRenderState {
+    /// Create a render state for an adult with nothing else known about them.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Whether a layer is visible for this creature.
+    ///
+    /// Every condition of the layer must be met. Conditions that narrow down a previous condition
+    /// (like `CONDITION_MATERIAL_FLAG` after `CONDITION_ITEM_WORN`, or `TISSUE_MIN_LENGTH` after
+    /// `CONDITION_TISSUE_LAYER`) must be met by the same item, body part or tissue layer.
+    /// Conditions which don't depend on the creature's state are treated as met.
+    ///
+    /// # Arguments
+    ///
+    /// * `layer` - The layer to check.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `true` if the layer is visible.
+    #[must_use]
+    pub fn is_layer_visible(&self, layer: &SpriteLayer) -> bool {
+        LayerEvaluator::new(self).evaluate(layer.get_conditions())
+    }
+    /// Get the layers which are visible for this creature, in the order they are drawn.
+    ///
+    /// # Arguments
+    ///
+    /// * `layers` - The layers to check (e.g. one layer set of a graphic).
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&SpriteLayer>` - The visible layers, in the same order as `layers`.
+    #[must_use]
+    pub fn visible_layers<'a>(&self, layers: &'a [SpriteLayer]) -> Vec<&'a SpriteLayer> {
+        layers
+            .iter()
+            .filter(|layer| self.is_layer_visible(layer))
+            .collect()
+    }
+    /// Whether a layer group's shared conditions are met for this creature.
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The layer group to check.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `true` if the group's layers can be visible.
+    #[must_use]
+    pub fn is_layer_group_visible(&self, group: &SpriteLayerGroup) -> bool {
+        LayerEvaluator::new(self).evaluate(group.get_conditions())
+    }
+    /// Get the layers of some layer groups which are visible for this creature, in the order they
+    /// are drawn.
+    ///
+    /// A layer is visible if its group's conditions and its own conditions are met.
+    ///
+    /// # Arguments
+    ///
+    /// * `groups` - The layer groups to check (e.g. one layer set of a graphic).
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&SpriteLayer>` - The visible layers, in the same order as in `groups`.
+    #[must_use]
+    pub fn visible_group_layers<'a>(&self, groups: &'a [SpriteLayerGroup]) -> Vec<&'a SpriteLayer> {
+        groups
+            .iter()
+            .filter(|group| self.is_layer_group_visible(group))
+            .flat_map(|group| self.visible_layers(group.get_layers()))
+            .collect()
+    }
+    /// Get the layers of one of a graphic's layer sets which are visible for this creature.
+    ///
+    /// # Arguments
+    ///
+    /// * `graphic` - The graphic.
+    /// * `layer_set` - The name of the layer set (e.g. `DEFAULT` or `PORTRAIT`).
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&SpriteLayer>` - The visible layers in the order they are drawn, or an empty list if
+    ///   the graphic has no such layer set.
+    #[must_use]
+    pub fn visible_graphic_layers<'a>(
+        &self,
+        graphic: &'a Graphic,
+        layer_set: &str,
+    ) -> Vec<&'a SpriteLayer> {
+        graphic
+            .get_layer_set(layer_set)
+            .map(|groups| self.visible_group_layers(groups))
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,262 @@
+use tracing::debug;
+
+use crate::tags::ConditionTag;
+
+use super::{AgeStage, BodyPartState, CreatureRenderState, TissueState, WornItem};
+
+/// What the narrowing conditions (material, dye, `BP_`, `TISSUE_`) apply to: the candidates
+/// selected by the last `CONDITION_ITEM_WORN`, `CONDITION_BP` or `CONDITION_TISSUE_LAYER`.
+enum Subject<'a> {
+    /// No selecting condition yet
+    None,
+    /// Items selected by `CONDITION_ITEM_WORN`
+    Items(Vec<&'a WornItem>),
+    /// Body parts selected by `CONDITION_BP` (`None` is a body part with no given state)
+    BodyParts(Vec<Option<&'a BodyPartState>>),
+    /// Tissue layers selected by `CONDITION_TISSUE_LAYER`
+    Tissues(Vec<&'a TissueState>),
+}
+
+/// Evaluates the conditions of one layer against a creature's state.
+pub struct LayerEvaluator<'a> {
+    state: &'a CreatureRenderState,
+    subject: Subject<'a>,
+}
+
+impl<'a> LayerEvaluator<'a> {
+    pub const fn new(state: &'a CreatureRenderState) -> Self {
+        Self {
+            state,
+            subject: Subject::None,
+        }
+    }
+    /// Whether all of the conditions are met, in order.
+    pub fn evaluate(mut self, conditions: &[(ConditionTag, String)]) -> bool {
+        conditions
+            .iter()
+            .all(|(condition, value)| self.condition_is_met(*condition, value))
+    }
+    fn condition_is_met(&mut self, condition: ConditionTag, value: &str) -> bool {
+        let state = self.state;
+        match condition {
+            ConditionTag::Caste => list_contains(value, state.caste.as_deref()),
+            ConditionTag::Male => state.caste.as_deref() == Some("MALE"),
+            ConditionTag::Female => state.caste.as_deref() == Some("FEMALE"),
+            ConditionTag::Baby => state.age == AgeStage::Baby,
+            ConditionTag::Child => state.age == AgeStage::Child,
+            ConditionTag::NotChild => state.age == AgeStage::Adult,
+            ConditionTag::Class => any_listed(value, &state.creature_classes),
+            ConditionTag::ProfessionCategory => any_listed(value, &state.professions),
+            ConditionTag::SyndromeClass => any_listed(value, &state.syndrome_classes),
+            ConditionTag::Ghost => state.ghost,
+            ConditionTag::HaulCountMin => {
+                parse_number(condition, value).is_none_or(|min| state.haul_count >= min)
+            }
+            ConditionTag::HaulCountMax => {
+                parse_number(condition, value).is_none_or(|max| state.haul_count <= max)
+            }
+            ConditionTag::RandomPartIndex => random_part_index_is_met(state, value),
+            ConditionTag::ShutOffIfItemPresent => !state
+                .worn_items
+                .iter()
+                .any(|item| item.matches_condition(value)),
+            ConditionTag::ItemWorn
+            | ConditionTag::MaterialFlag
+            | ConditionTag::MaterialType
+            | ConditionTag::Dye
+            | ConditionTag::NotDyed => self.item_condition_is_met(condition, value),
+            ConditionTag::BodyPart
+            | ConditionTag::BodyPartPresent
+            | ConditionTag::BodyPartScarred
+            | ConditionTag::BodyPartAppearanceModifierRange => {
+                self.body_part_condition_is_met(condition, value)
+            }
+            ConditionTag::TissueLayer
+            | ConditionTag::TissueMinLength
+            | ConditionTag::TissueMaxLength
+            | ConditionTag::TissueMinCurly
+            | ConditionTag::TissueMaxCurly
+            | ConditionTag::TissueMayHaveShaping
+            | ConditionTag::TissueNotShaped
+            | ConditionTag::TissueMayHaveColor => self.tissue_condition_is_met(condition, value),
+            // Everything else (like `TISSUE_SWAP`) changes how the layer is drawn, or doesn't
+            // depend on the creature's state.
+            _ => true,
+        }
+    }
+    /// Evaluate `CONDITION_ITEM_WORN` and the material and dye conditions narrowing it down.
+    fn item_condition_is_met(&mut self, condition: ConditionTag, value: &str) -> bool {
+        let state = self.state;
+        match condition {
+            ConditionTag::ItemWorn => {
+                let items: Vec<&WornItem> = state
+                    .worn_items
+                    .iter()
+                    .filter(|item| item.matches_condition(value))
+                    .collect();
+                let met = !items.is_empty();
+                self.subject = Subject::Items(items);
+                met
+            }
+            ConditionTag::MaterialFlag => self.retain_items(|item| {
+                value
+                    .split(':')
+                    .all(|flag| item.material_flags.iter().any(|f| f == flag))
+            }),
+            ConditionTag::MaterialType => {
+                self.retain_items(|item| item.material.as_deref() == Some(value))
+            }
+            ConditionTag::Dye => self.retain_items(|item| {
+                item.dye
+                    .as_deref()
+                    .is_some_and(|dye| value.is_empty() || list_contains(value, Some(dye)))
+            }),
+            ConditionTag::NotDyed => self.retain_items(|item| item.dye.is_none()),
+            _ => true,
+        }
+    }
+    /// Evaluate `CONDITION_BP` and the `BP_` conditions narrowing it down.
+    fn body_part_condition_is_met(&mut self, condition: ConditionTag, value: &str) -> bool {
+        let state = self.state;
+        match condition {
+            ConditionTag::BodyPart => {
+                let mut parts = value.split(':');
+                let (Some(selector), Some(name)) = (parts.next(), parts.next()) else {
+                    return false;
+                };
+                let selected: Vec<&BodyPartState> = state
+                    .body_parts
+                    .iter()
+                    .filter(|part| part.body_part.is_selected_by(selector, name))
+                    .collect();
+                // A body part with no given state is a normal, present body part.
+                self.subject = Subject::BodyParts(if selected.is_empty() {
+                    vec![None]
+                } else {
+                    selected.into_iter().map(Some).collect()
+                });
+                true
+            }
+            ConditionTag::BodyPartPresent => {
+                self.retain_body_parts(|part| part.is_none_or(|part| !part.missing))
+            }
+            ConditionTag::BodyPartScarred => {
+                self.retain_body_parts(|part| part.is_some_and(|part| part.scarred))
+            }
+            ConditionTag::BodyPartAppearanceModifierRange => {
+                let mut parts = value.split(':');
+                let (Some(modifier), Some(Ok(min)), Some(Ok(max))) = (
+                    parts.next(),
+                    parts.next().map(str::parse::<i32>),
+                    parts.next().map(str::parse::<i32>),
+                ) else {
+                    debug!("Ignoring unreadable appearance modifier range {value}");
+                    return true;
+                };
+                self.retain_body_parts(|part| {
+                    let modifier_value = part
+                        .map_or(BodyPartState::DEFAULT_APPEARANCE_MODIFIER, |part| {
+                            part.get_appearance_modifier(modifier)
+                        });
+                    (min..=max).contains(&modifier_value)
+                })
+            }
+            _ => true,
+        }
+    }
+    /// Evaluate `CONDITION_TISSUE_LAYER` and the `TISSUE_` conditions narrowing it down.
+    fn tissue_condition_is_met(&mut self, condition: ConditionTag, value: &str) -> bool {
+        let state = self.state;
+        match condition {
+            ConditionTag::TissueLayer => {
+                let tissues: Vec<&TissueState> = state
+                    .tissues
+                    .iter()
+                    .filter(|tissue| tissue.matches_condition(value))
+                    .collect();
+                let met = !tissues.is_empty();
+                self.subject = Subject::Tissues(tissues);
+                met
+            }
+            ConditionTag::TissueMinLength => parse_number(condition, value)
+                .is_none_or(|min| self.retain_tissues(|tissue| tissue.length >= min)),
+            ConditionTag::TissueMaxLength => parse_number(condition, value)
+                .is_none_or(|max| self.retain_tissues(|tissue| tissue.length <= max)),
+            ConditionTag::TissueMinCurly => parse_number(condition, value)
+                .is_none_or(|min| self.retain_tissues(|tissue| tissue.curly >= min)),
+            ConditionTag::TissueMaxCurly => parse_number(condition, value)
+                .is_none_or(|max| self.retain_tissues(|tissue| tissue.curly <= max)),
+            ConditionTag::TissueMayHaveShaping => self.retain_tissues(|tissue| {
+                tissue
+                    .shaping
+                    .as_deref()
+                    .is_some_and(|shaping| list_contains(value, Some(shaping)))
+            }),
+            ConditionTag::TissueNotShaped => self.retain_tissues(|tissue| tissue.shaping.is_none()),
+            ConditionTag::TissueMayHaveColor => self.retain_tissues(|tissue| {
+                // An unknown color could be any of them.
+                tissue.color.is_none() || list_contains(value, tissue.color.as_deref())
+            }),
+            _ => true,
+        }
+    }
+    /// Narrow down the items selected by `CONDITION_ITEM_WORN`.
+    fn retain_items(&mut self, keep: impl Fn(&WornItem) -> bool) -> bool {
+        match &mut self.subject {
+            Subject::Items(items) => {
+                items.retain(|item| keep(item));
+                !items.is_empty()
+            }
+            _ => true,
+        }
+    }
+    /// Narrow down the body parts selected by `CONDITION_BP`.
+    fn retain_body_parts(&mut self, keep: impl Fn(Option<&BodyPartState>) -> bool) -> bool {
+        match &mut self.subject {
+            Subject::BodyParts(parts) => {
+                parts.retain(|part| keep(*part));
+                !parts.is_empty()
+            }
+            _ => true,
+        }
+    }
+    /// Narrow down the tissue layers selected by `CONDITION_TISSUE_LAYER`.
+    fn retain_tissues(&mut self, keep: impl Fn(&TissueState) -> bool) -> bool {
+        match &mut self.subject {
+            Subject::Tissues(tissues) => {
+                tissues.retain(|tissue| keep(tissue));
+                !tissues.is_empty()
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Whether `item` is one of the colon-separated values in `list`.
+fn list_contains(list: &str, item: Option<&str>) -> bool {
+    item.is_some_and(|item| list.split(':').any(|listed| listed == item))
+}
+
+/// Whether any of the colon-separated values in `list` is in `values`.
+fn any_listed(list: &str, values: &[String]) -> bool {
+    list.split(':')
+        .any(|listed| values.iter().any(|v| v == listed))
+}
+
+/// Parse a condition's numeric value, logging it if it can't be read (so it's ignored).
+fn parse_number(condition: ConditionTag, value: &str) -> Option<u32> {
+    value
+        .parse()
+        .map_err(|_| debug!("Ignoring {condition:?} with unreadable value {value}"))
+        .ok()
+}
+
+/// Whether the chosen variant of a random part is the one in `value` (e.g. `HEAD:3:4`).
+fn random_part_index_is_met(state: &CreatureRenderState, value: &str) -> bool {
+    let mut parts = value.split(':');
+    let (Some(part), Some(Ok(index))) = (parts.next(), parts.next().map(str::parse::<u32>)) else {
+        debug!("Ignoring unreadable random part index {value}");
+        return true;
+    };
+    state.random_part_indices.get(part).copied().unwrap_or(1) == index
+}
@@ -0,0 +1,59 @@
+//! Evaluate the conditions on layered graphics to decide which layers are drawn for a creature.
+//!
+//! Layered graphics (like the dwarf portraits) are made of many `SpriteLayer`s, each with
+//! conditions such as `CONDITION_ITEM_WORN`, `CONDITION_BP`, `CONDITION_TISSUE_LAYER`,
+//! `CONDITION_PROFESSION_CATEGORY`, `CONDITION_SYN_CLASS` or `SHUT_OFF_IF_ITEM_PRESENT`. A
+//! `CreatureRenderState` describes the creature being drawn (caste, age, worn items, profession,
+//! hair, syndromes, ...) and picks out the layers that would be visible, in drawing order. Layers
+//! in a `SpriteLayerGroup` are only visible if the group's shared conditions are also met.
+//!
+//! ```rust
+//! use dfraw_parser::{
+//!     layer_conditions::{BodyPartRef, CreatureRenderState, TissueState, WornItem},
+//!     sprite_layer::SpriteLayer,
+//! };
+//!
+//! let mut breastplate = SpriteLayer::parse_layer_from_value("BREASTPLATE:PORTRAIT:0:1").unwrap();
+//! breastplate.parse_condition_token(
+//!     "CONDITION_ITEM_WORN",
+//!     "BY_CATEGORY:BODY_UPPER:ARMOR:ITEM_ARMOR_BREASTPLATE",
+//! );
+//! let mut braids = SpriteLayer::parse_layer_from_value("HAIR_BRAIDED:PORTRAIT:1:1").unwrap();
+//! braids.parse_condition_token("CONDITION_TISSUE_LAYER", "BY_CATEGORY:HEAD:HAIR");
+//! braids.parse_condition_token("TISSUE_MAY_HAVE_SHAPING", "BRAIDED");
+//! let layers = vec![breastplate, braids];
+//!
+//! let mut state = CreatureRenderState::new();
+//! state.caste = Some(String::from("FEMALE"));
+//! state.worn_items.push(WornItem::new(
+//!     BodyPartRef::new("UB", "BODY_UPPER"),
+//!     "ARMOR",
+//!     "ITEM_ARMOR_BREASTPLATE",
+//! ));
+//! let mut hair = TissueState::new(BodyPartRef::new("HD", "HEAD"), "HAIR");
+//! hair.shaping = Some(String::from("BRAIDED"));
+//! state.tissues.push(hair);
+//!
+//! let visible = state.visible_layers(&layers);
+//! assert_eq!(visible.len(), 2);
+//!
+//! state.tissues.clear();
+//! let visible = state.visible_layers(&layers);
+//! assert_eq!(visible[0].get_layer_name(), "BREASTPLATE");
+//! assert_eq!(visible.len(), 1);
+//! ```
+
+mod age_stage;
+mod body_part_ref;
+mod body_part_state;
+mod creature_render_state;
+mod evaluator;
+mod tissue_state;
+mod worn_item;
+
+pub use age_stage::AgeStage;
+pub use body_part_ref::BodyPartRef;
+pub use body_part_state::BodyPartState;
+pub use creature_render_state::CreatureRenderState;
+pub use tissue_state::TissueState;
+pub use worn_item::WornItem;
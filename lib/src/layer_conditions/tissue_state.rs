@@ -0,0 +1,60 @@
+use super::BodyPartRef;
+
+/// The state of a tissue layer of the creature being rendered (e.g. the hair on its head), for
+/// the `CONDITION_TISSUE_LAYER` and `TISSUE_*` layer conditions.
+///
+/// This is how hair styles are rendered: the style is the tissue's shaping, along with its
+/// length and curliness.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct TissueState {
+    /// The body part the tissue layer is on
+    pub body_part: BodyPartRef,
+    /// The tissue (e.g. `HAIR`)
+    pub tissue: String,
+    /// The tissue's length
+    pub length: u32,
+    /// How curly the tissue is
+    pub curly: u32,
+    /// How the tissue is shaped (e.g. `NEATLY_COMBED`, `BRAIDED`), or `None` if it isn't
+    pub shaping: Option<String>,
+    /// The tissue's color (e.g. `BLACK`), or `None` if it's unknown
+    pub color: Option<String>,
+}
+
+impl TissueState {
+    /// Create a tissue layer state.
+    ///
+    /// # Arguments
+    ///
+    /// * `body_part` - The body part the tissue layer is on.
+    /// * `tissue` - The tissue (e.g. `HAIR`).
+    #[must_use]
+    pub fn new(body_part: BodyPartRef, tissue: &str) -> Self {
+        Self {
+            body_part,
+            tissue: String::from(tissue),
+            ..Self::default()
+        }
+    }
+    /// Whether this tissue layer matches a `CONDITION_TISSUE_LAYER` condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The condition's value (e.g. `BY_CATEGORY:HEAD:HAIR`).
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `true` if the tissue layer matches.
+    #[must_use]
+    pub fn matches_condition(&self, value: &str) -> bool {
+        let mut parts = value.split(':');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(selector), Some(name), Some(tissue)) => {
+                self.body_part.is_selected_by(selector, name)
+                    && (tissue == "ALL" || tissue == self.tissue)
+            }
+            _ => false,
+        }
+    }
+}
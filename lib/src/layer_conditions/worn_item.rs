@@ -0,0 +1,78 @@
+use super::BodyPartRef;
+
+/// An item worn (or held) by the creature being rendered.
+///
+/// Used for the `CONDITION_ITEM_WORN` and `SHUT_OFF_IF_ITEM_PRESENT` layer conditions, and the
+/// material and dye conditions which follow them.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct WornItem {
+    /// The body part the item is worn on (or held in)
+    pub body_part: BodyPartRef,
+    /// The item type (e.g. `ARMOR`)
+    pub item_type: String,
+    /// The item subtype (e.g. `ITEM_ARMOR_BREASTPLATE`)
+    pub item_subtype: String,
+    /// Whether the item is held (for `ANY_HELD`)
+    pub held: bool,
+    /// Whether the item is wielded (for `WIELD`)
+    pub wielded: bool,
+    /// The item's material (e.g. `INORGANIC:IRON`), for `CONDITION_MATERIAL_TYPE`
+    pub material: Option<String>,
+    /// The item material's flags (e.g. `METAL`), for `CONDITION_MATERIAL_FLAG`
+    pub material_flags: Vec<String>,
+    /// The color the item is dyed, for `CONDITION_DYE` and `CONDITION_NOT_DYED`
+    pub dye: Option<String>,
+}
+
+impl WornItem {
+    /// Create an item worn on a body part.
+    ///
+    /// # Arguments
+    ///
+    /// * `body_part` - The body part the item is worn on.
+    /// * `item_type` - The item type (e.g. `ARMOR`).
+    /// * `item_subtype` - The item subtype (e.g. `ITEM_ARMOR_BREASTPLATE`).
+    #[must_use]
+    pub fn new(body_part: BodyPartRef, item_type: &str, item_subtype: &str) -> Self {
+        Self {
+            body_part,
+            item_type: String::from(item_type),
+            item_subtype: String::from(item_subtype),
+            ..Self::default()
+        }
+    }
+    /// Whether this item matches the selector of a `CONDITION_ITEM_WORN` or
+    /// `SHUT_OFF_IF_ITEM_PRESENT` condition.
+    ///
+    /// The condition's value is a body part selector (`BY_CATEGORY:BODY_UPPER`, `BY_TOKEN:RH`,
+    /// `BY_TYPE:GRASP`, `ANY_HELD` or `WIELD`) followed by the item type and any number of item
+    /// subtypes. With no subtypes (or `ANY`), any item of that type matches.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The condition's value (e.g. `BY_CATEGORY:BODY_UPPER:ARMOR:ITEM_ARMOR_BREASTPLATE`).
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `true` if the item matches.
+    #[must_use]
+    pub fn matches_condition(&self, value: &str) -> bool {
+        let mut parts = value.split(':');
+        let position_matches = match parts.next() {
+            Some("ANY_HELD") => self.held,
+            Some("WIELD") => self.wielded,
+            Some(selector) => parts
+                .next()
+                .is_some_and(|name| self.body_part.is_selected_by(selector, name)),
+            None => false,
+        };
+        if !position_matches || parts.next() != Some(self.item_type.as_str()) {
+            return false;
+        }
+
+        let mut subtypes = parts.peekable();
+        subtypes.peek().is_none()
+            || subtypes.any(|subtype| subtype == "ANY" || subtype == self.item_subtype)
+    }
+}
@@ -0,0 +1,193 @@
+//! The `Artifact` struct, a named item in a world's history.
+
+use crate::legends_export::record::XmlRecord;
+
+/// An artifact from a legends export (`<artifact>`).
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Artifact {
+    id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// The name of the item itself (e.g. `the Sapphire of Legends`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    item_name: Option<String>,
+    /// The type of item (e.g. `weapon`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    item_type: Option<String>,
+    /// The subtype of item (e.g. `battle axe`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    item_subtype: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    item_description: Option<String>,
+    /// The material of the item (e.g. `adamantine`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    material: Option<String>,
+    /// The number of pages, for books
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page_count: Option<i32>,
+    /// The id of the written content, for books and scrolls
+    #[serde(skip_serializing_if = "Option::is_none")]
+    written_content_id: Option<i32>,
+    /// The id of the site the artifact is in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    site_id: Option<i32>,
+    /// The local id of the structure the artifact is in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    structure_id: Option<i32>,
+    /// The id of the historical figure holding the artifact
+    #[serde(skip_serializing_if = "Option::is_none")]
+    holder_figure_id: Option<i32>,
+}
+
+impl Artifact {
+    /// Update the artifact with the fields of a record (fields missing from the record are left as
+    /// they were).
+    pub(crate) fn update_from_record(&mut self, record: &XmlRecord) {
+        let item = record.children_named("item").next();
+        let item_field = |name: &str| item.and_then(|item| item.get_string(name));
+        let positive = |id: i32| (id >= 0).then_some(id);
+
+        self.id = record.get_i32("id").unwrap_or(self.id);
+        self.name = record.get_string("name").or_else(|| self.name.take());
+        self.item_name = item_field("name_string")
+            .or_else(|| record.get_string("name_string"))
+            .or_else(|| self.item_name.take());
+        self.item_type = record
+            .get_string("item_type")
+            .or_else(|| self.item_type.take());
+        self.item_subtype = record
+            .get_string("item_subtype")
+            .or_else(|| self.item_subtype.take());
+        self.item_description = record
+            .get_string("item_description")
+            .or_else(|| self.item_description.take());
+        self.material = record.get_string("mat").or_else(|| self.material.take());
+        self.page_count = record.get_i32("page_count").or(self.page_count);
+        self.written_content_id = record
+            .get_i32("writing")
+            .or_else(|| item_field("writing_written_content_id")?.parse().ok())
+            .and_then(positive)
+            .or(self.written_content_id);
+        self.site_id = record
+            .get_i32("site_id")
+            .and_then(positive)
+            .or(self.site_id);
+        self.structure_id = record
+            .get_i32("structure_local_id")
+            .and_then(positive)
+            .or(self.structure_id);
+        self.holder_figure_id = record
+            .get_i32("holder_hfid")
+            .and_then(positive)
+            .or(self.holder_figure_id);
+    }
+    /// Get the id of the artifact.
+    ///
+    /// # Returns
+    ///
+    /// * `i32` - The id.
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// Get the name of the artifact.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The name, if it has one.
+    #[must_use]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// Get the name of the item the artifact is.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The item name, if known.
+    #[must_use]
+    pub fn get_item_name(&self) -> Option<&str> {
+        self.item_name.as_deref()
+    }
+    /// Get the type of item the artifact is.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The item type (e.g. `weapon`), if known.
+    #[must_use]
+    pub fn get_item_type(&self) -> Option<&str> {
+        self.item_type.as_deref()
+    }
+    /// Get the subtype of item the artifact is.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The item subtype (e.g. `battle axe`), if known.
+    #[must_use]
+    pub fn get_item_subtype(&self) -> Option<&str> {
+        self.item_subtype.as_deref()
+    }
+    /// Get the description of the item.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The description, if known.
+    #[must_use]
+    pub fn get_item_description(&self) -> Option<&str> {
+        self.item_description.as_deref()
+    }
+    /// Get the material the artifact is made of.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The material (e.g. `adamantine`), if known.
+    #[must_use]
+    pub fn get_material(&self) -> Option<&str> {
+        self.material.as_deref()
+    }
+    /// Get the number of pages of the artifact (for books).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The page count, if known.
+    #[must_use]
+    pub const fn get_page_count(&self) -> Option<i32> {
+        self.page_count
+    }
+    /// Get the written content of the artifact (for books and scrolls).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The id of the written content, if any.
+    #[must_use]
+    pub const fn get_written_content_id(&self) -> Option<i32> {
+        self.written_content_id
+    }
+    /// Get the site the artifact is in.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The id of the site, if known.
+    #[must_use]
+    pub const fn get_site_id(&self) -> Option<i32> {
+        self.site_id
+    }
+    /// Get the structure the artifact is in.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The local id of the structure in its site, if known.
+    #[must_use]
+    pub const fn get_structure_id(&self) -> Option<i32> {
+        self.structure_id
+    }
+    /// Get the historical figure holding the artifact.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The id of the historical figure, if any.
+    #[must_use]
+    pub const fn get_holder_figure_id(&self) -> Option<i32> {
+        self.holder_figure_id
+    }
+}
@@ -2,16 +2,57 @@
 //!
 //! The `ExportedCreature` struct is used to store information about a creature that has been exported
 //! from the Legends Viewer.
+//!
+//! Creatures the world generated (forgotten beasts, titans, night creatures, ...) only exist in the
+//! legends export, so everything the export has for a creature is kept: its flags, description,
+//! castes, interactions and any other fields (like its body or materials).
+//!
+//! A `Creature` has no body parts or materials of its own (they come from body and material
+//! templates), so those stay on the `ExportedCreature` (see `get_body_parts` and
+//! `get_fields_of`) rather than being converted.
 
-use crate::{creature::Creature, metadata::RawMetadata, name::Name};
+use crate::{
+    creature::Creature,
+    legends_export::{
+        record::XmlRecord,
+        util::{extend_unique, is_generated_creature_id},
+    },
+    metadata::RawMetadata,
+    name::Name,
+    traits::RawObject,
+};
 
 /// The `ExportedCreature` struct is used to store information about a creature that has been exported
-#[derive(Debug, Default)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 pub struct ExportedCreature {
     creature_id: String,
     name_singular: String,
     name_plural: String,
     tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    castes: Vec<ExportedCaste>,
+    /// The interactions the creature can use (e.g. a forgotten beast's poison)
+    interactions: Vec<String>,
+    /// The other fields of the creature, in order (nested fields are named `parent/child`)
+    fields: Vec<(String, String)>,
+}
+
+/// A caste of an exported creature (`<caste>`).
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedCaste {
+    caste_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name_singular: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name_plural: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    tags: Vec<String>,
+    /// The other fields of the caste, in order
+    fields: Vec<(String, String)>,
 }
 
 impl ExportedCreature {
@@ -56,8 +97,194 @@ impl ExportedCreature {
     pub fn add_tag(&mut self, tag: &str) {
         self.tags.push(tag.to_string());
     }
+    /// Add an interaction the creature can use
+    ///
+    /// # Arguments
+    ///
+    /// * `interaction` - A `&str` that represents the interaction (e.g. `DEITY_CURSE_WEREBEAST_1`)
+    pub fn add_interaction(&mut self, interaction: &str) {
+        if !self.interactions.iter().any(|known| known == interaction) {
+            self.interactions.push(interaction.to_string());
+        }
+    }
+    /// Update the creature with the fields of a record (fields missing from the record are left
+    /// as they were, and flags like `<has_male/>` are added as tags).
+    pub(crate) fn update_from_record(&mut self, record: &XmlRecord) {
+        for (key, value) in &record.fields {
+            match key.as_str() {
+                "creature_id" => self.set_creature_id(value),
+                "name_singular" => self.set_name_singular(value),
+                "name_plural" => self.set_name_plural(value),
+                "description" => self.description = Some(value.clone()),
+                "interaction" | "can_do_interaction" | "active_interaction" => {
+                    self.add_interaction(value);
+                }
+                _ if value.is_empty() => {
+                    if !self.tags.contains(key) {
+                        self.add_tag(key);
+                    }
+                }
+                _ => extend_unique(&mut self.fields, [(key.clone(), value.clone())]),
+            }
+        }
+        for child in &record.children {
+            if child.name == "caste" {
+                let caste_id = child
+                    .get("caste_id")
+                    .or_else(|| child.get("id"))
+                    .unwrap_or_default()
+                    .to_uppercase();
+                if caste_id.is_empty() {
+                    continue;
+                }
+                if let Some(caste) = self
+                    .castes
+                    .iter_mut()
+                    .find(|caste| caste.caste_id == caste_id)
+                {
+                    caste.update_from_record(child);
+                } else {
+                    let mut caste = ExportedCaste {
+                        caste_id,
+                        ..ExportedCaste::default()
+                    };
+                    caste.update_from_record(child);
+                    self.castes.push(caste);
+                }
+            } else {
+                extend_unique(
+                    &mut self.fields,
+                    child
+                        .flattened_fields()
+                        .into_iter()
+                        .map(|(key, value)| (format!("{}/{key}", child.name), value)),
+                );
+            }
+        }
+    }
+    /// Get the creature ID
+    ///
+    /// # Returns
+    ///
+    /// The creature ID (e.g. `DWARF`)
+    #[must_use]
+    pub fn get_creature_id(&self) -> &str {
+        &self.creature_id
+    }
+    /// Get the singular name of the creature
+    ///
+    /// # Returns
+    ///
+    /// The singular name of the creature
+    #[must_use]
+    pub fn get_name_singular(&self) -> &str {
+        &self.name_singular
+    }
+    /// Get the plural name of the creature
+    ///
+    /// # Returns
+    ///
+    /// The plural name of the creature
+    #[must_use]
+    pub fn get_name_plural(&self) -> &str {
+        &self.name_plural
+    }
+    /// Get the tags of the creature
+    ///
+    /// # Returns
+    ///
+    /// The tags of the creature (e.g. `has_male`)
+    #[must_use]
+    pub fn get_tags(&self) -> &[String] {
+        &self.tags
+    }
+    /// Get the description of the creature
+    ///
+    /// # Returns
+    ///
+    /// The description of the creature, if the export has one
+    #[must_use]
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    /// Get the castes of the creature
+    ///
+    /// # Returns
+    ///
+    /// The castes the export describes (castes only implied by flags like `has_male` aren't
+    /// included)
+    #[must_use]
+    pub fn get_castes(&self) -> &[ExportedCaste] {
+        &self.castes
+    }
+    /// Get the interactions the creature can use
+    ///
+    /// # Returns
+    ///
+    /// The interaction identifiers
+    #[must_use]
+    pub fn get_interactions(&self) -> &[String] {
+        &self.interactions
+    }
+    /// Get the other fields of the creature
+    ///
+    /// # Returns
+    ///
+    /// The names and values of the fields, in order
+    #[must_use]
+    pub fn get_fields(&self) -> &[(String, String)] {
+        &self.fields
+    }
+    /// Get the fields of a nested element of the creature (e.g. its `<material>`s)
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - The name of the nested element (e.g. `material`)
+    ///
+    /// # Returns
+    ///
+    /// The names (without the `element/` prefix) and values of the fields, in order
+    pub fn get_fields_of<'a>(
+        &'a self,
+        element: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.fields.iter().filter_map(move |(key, value)| {
+            key.strip_prefix(element)
+                .and_then(|key| key.strip_prefix('/'))
+                .map(|key| (key, value.as_str()))
+        })
+    }
+    /// Get the body parts of the creature (the `<body_part>`s of its `<body>`)
+    ///
+    /// # Returns
+    ///
+    /// The body parts, in order
+    #[must_use]
+    pub fn get_body_parts(&self) -> Vec<&str> {
+        self.fields
+            .iter()
+            .filter(|(key, _)| key == "body/body_part" || key == "body_part")
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+    /// Whether the creature was generated by the game (e.g. a forgotten beast)
+    ///
+    /// # Returns
+    ///
+    /// `true` if the export flags the creature as generated or it has a generated identifier
+    #[must_use]
+    pub fn is_generated(&self) -> bool {
+        self.tags.iter().any(|tag| tag == "generated")
+            || is_generated_creature_id(&self.creature_id)
+    }
     /// Convert the `ExportedCreature` struct into a `Creature` struct
     ///
+    /// Fields named like creature or caste tokens (e.g. `<body_size>` or `<can_learn/>`) are parsed
+    /// as those tokens. The description, interactions and fields apply to every caste, and the
+    /// castes from the export are added with their own names, descriptions and tokens. Nested
+    /// elements (like the body and materials) aren't tokens, so they are only kept on the
+    /// `ExportedCreature`.
+    ///
     /// # Arguments
     ///
     /// * `metadata` - A reference to a `RawMetadata` struct
@@ -71,6 +298,116 @@ impl ExportedCreature {
         creature.set_name(Name::new(&self.name_singular, &self.name_plural, ""));
         creature.parse_tags_from_xml(self.tags.as_slice());
 
+        creature.select_caste("ALL");
+        if let Some(description) = &self.description {
+            creature.parse_tag("DESCRIPTION", description);
+        }
+        for interaction in &self.interactions {
+            creature.parse_tag("CAN_DO_INTERACTION", interaction);
+        }
+        for (key, value) in self.fields.iter().filter(|(key, _)| is_token_field(key)) {
+            creature.parse_tag(&key.to_uppercase(), value);
+        }
+        for caste in &self.castes {
+            caste.apply_to(&mut creature);
+        }
+
         creature
     }
 }
+
+impl ExportedCaste {
+    fn update_from_record(&mut self, record: &XmlRecord) {
+        for (key, value) in record.flattened_fields() {
+            match key.as_str() {
+                "caste_id" | "id" => (),
+                "name_singular" => self.name_singular = Some(value),
+                "name_plural" => self.name_plural = Some(value),
+                "description" => self.description = Some(value),
+                _ if value.is_empty() => {
+                    if !self.tags.contains(&key) {
+                        self.tags.push(key);
+                    }
+                }
+                _ => extend_unique(&mut self.fields, [(key, value)]),
+            }
+        }
+    }
+    /// Add the caste, with its names, description and tokens, to a creature.
+    fn apply_to(&self, creature: &mut Creature) {
+        creature.select_caste(&self.caste_id);
+        if let Some(singular) = &self.name_singular {
+            let plural = self.name_plural.as_deref().unwrap_or(singular);
+            creature.parse_tag("CASTE_NAME", &format!("{singular}:{plural}:{singular}"));
+        }
+        if let Some(description) = &self.description {
+            creature.parse_tag("DESCRIPTION", description);
+        }
+        for tag in &self.tags {
+            creature.parse_tag(&tag.to_uppercase(), "");
+        }
+        for (key, value) in self.fields.iter().filter(|(key, _)| is_token_field(key)) {
+            creature.parse_tag(&key.to_uppercase(), value);
+        }
+    }
+    /// Get the caste ID
+    ///
+    /// # Returns
+    ///
+    /// The caste ID (e.g. `FEMALE`)
+    #[must_use]
+    pub fn get_caste_id(&self) -> &str {
+        &self.caste_id
+    }
+    /// Get the singular name of the caste
+    ///
+    /// # Returns
+    ///
+    /// The singular name of the caste, if the export has one
+    #[must_use]
+    pub fn get_name_singular(&self) -> Option<&str> {
+        self.name_singular.as_deref()
+    }
+    /// Get the plural name of the caste
+    ///
+    /// # Returns
+    ///
+    /// The plural name of the caste, if the export has one
+    #[must_use]
+    pub fn get_name_plural(&self) -> Option<&str> {
+        self.name_plural.as_deref()
+    }
+    /// Get the description of the caste
+    ///
+    /// # Returns
+    ///
+    /// The description of the caste, if the export has one
+    #[must_use]
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    /// Get the tags of the caste
+    ///
+    /// # Returns
+    ///
+    /// The tags of the caste (e.g. `can_learn`)
+    #[must_use]
+    pub fn get_tags(&self) -> &[String] {
+        &self.tags
+    }
+    /// Get the other fields of the caste
+    ///
+    /// # Returns
+    ///
+    /// The names and values of the fields, in order
+    #[must_use]
+    pub fn get_fields(&self) -> &[(String, String)] {
+        &self.fields
+    }
+}
+
+/// Whether a field could be a creature or caste token: fields of nested elements (named
+/// `parent/child`, like `body/body_part`) and the `<body_part>`s of a body never are.
+fn is_token_field(key: &str) -> bool {
+    !key.contains('/') && key != "body_part"
+}
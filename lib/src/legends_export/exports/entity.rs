@@ -1,30 +1,77 @@
 //! The `ExportedEntity` struct is used to store information about an entity that has been exported
 //! from the Legends Viewer.
 
-use crate::{entity::Entity, metadata::RawMetadata};
+use crate::{
+    entity::Entity,
+    legends_export::{record::XmlRecord, util::extend_unique},
+    metadata::RawMetadata,
+};
 
 /// The `ExportedEntity` struct is used to store information about an entity that has been exported
-#[derive(Debug, Default)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 pub struct ExportedEntity {
     entity_id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
     race: String,
     entity_type: String,
-    child_id: u32,
-    // population: u32,
-    // civ_id: u32,
-    // positions: Vec<ExportedEntityPosition>,
+    child_ids: Vec<u32>,
+    positions: Vec<ExportedEntityPosition>,
+    position_assignments: Vec<ExportedEntityPositionAssignment>,
+    /// The historical figures which are members of the entity
+    figure_ids: Vec<i32>,
 }
 
-// #[derive(Debug, Default)]
-// pub(crate) struct ExportedEntityPosition {
-//     id: u32,
-//     name: String,
-//     male_name: String,
-//     female_name: String,
-//     spouse_name: String,
-//     male_spouse_name: String,
-//     female_spouse_name: String,
-// }
+/// A position in an entity (`<entity_position>`), like a monarch or a general.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedEntityPosition {
+    /// The id of the position within the entity
+    pub id: u32,
+    /// The name of the position
+    pub name: String,
+    /// The name of the position when held by a male
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub male_name: Option<String>,
+    /// The name of the position when held by a female
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub female_name: Option<String>,
+    /// The name of the spouse of the position holder
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spouse_name: Option<String>,
+    /// The name of the male spouse of the position holder
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub male_spouse_name: Option<String>,
+    /// The name of the female spouse of the position holder
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub female_spouse_name: Option<String>,
+}
+
+/// Who holds a position in an entity (`<entity_position_assignment>`).
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedEntityPositionAssignment {
+    /// The id of the assignment within the entity
+    pub id: u32,
+    /// The id of the position (an `ExportedEntityPosition`)
+    pub position_id: u32,
+    /// The id of the historical figure holding the position, if anyone does
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub figure_id: Option<i32>,
+}
+
+/// The population of an entity (`<entity_population>`).
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedEntityPopulation {
+    id: i32,
+    /// The id of the civilization the population belongs to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    civ_id: Option<i32>,
+    /// The races in the population, with how many there are of each
+    races: Vec<(String, u32)>,
+}
 
 impl ExportedEntity {
     /// Set the entity ID
@@ -51,13 +98,147 @@ impl ExportedEntity {
     pub fn set_entity_type(&mut self, entity_type: &str) {
         self.entity_type = entity_type.into();
     }
-    /// Set the child ID
+    /// Set the child ID (replacing any other child IDs)
     ///
     /// # Arguments
     ///
     /// * `child_id` - A `u32` that represents the child ID
     pub fn set_child_id(&mut self, child_id: u32) {
-        self.child_id = child_id;
+        self.child_ids = vec![child_id];
+    }
+    /// Add a child ID
+    ///
+    /// # Arguments
+    ///
+    /// * `child_id` - A `u32` that represents the child ID
+    pub fn add_child_id(&mut self, child_id: u32) {
+        self.child_ids.push(child_id);
+    }
+    /// Update the entity with the fields of a record (fields missing from the record are left as
+    /// they were, and lists are added to).
+    pub(crate) fn update_from_record(&mut self, record: &XmlRecord) {
+        let number = |value: &str| value.trim().parse().ok();
+
+        if let Some(id) = record.get("id").and_then(number) {
+            self.entity_id = id;
+        }
+        self.name = record.get_string("name").or_else(|| self.name.take());
+        if let Some(race) = record.get("race") {
+            self.set_race(race);
+        }
+        if let Some(entity_type) = record.get("type") {
+            self.set_entity_type(entity_type);
+        }
+        extend_unique(
+            &mut self.child_ids,
+            record.get_all("child").filter_map(number),
+        );
+        extend_unique(
+            &mut self.figure_ids,
+            record
+                .get_all("histfig_id")
+                .filter_map(|id| id.parse().ok()),
+        );
+        extend_unique(
+            &mut self.positions,
+            record
+                .children_named("entity_position")
+                .filter_map(|position| {
+                    Some(ExportedEntityPosition {
+                        id: position.get("id").and_then(number)?,
+                        name: position.get_string("name").unwrap_or_default(),
+                        male_name: position.get_string("name_male"),
+                        female_name: position.get_string("name_female"),
+                        spouse_name: position.get_string("spouse"),
+                        male_spouse_name: position.get_string("spouse_male"),
+                        female_spouse_name: position.get_string("spouse_female"),
+                    })
+                }),
+        );
+        extend_unique(
+            &mut self.position_assignments,
+            record
+                .children_named("entity_position_assignment")
+                .filter_map(|assignment| {
+                    Some(ExportedEntityPositionAssignment {
+                        id: assignment.get("id").and_then(number)?,
+                        position_id: assignment.get("position_id").and_then(number)?,
+                        figure_id: assignment.get_i32("histfig").filter(|id| *id >= 0),
+                    })
+                }),
+        );
+    }
+    /// Get the entity ID
+    ///
+    /// # Returns
+    ///
+    /// The ID of the entity
+    #[must_use]
+    pub const fn get_id(&self) -> u32 {
+        self.entity_id
+    }
+    /// Get the entity name
+    ///
+    /// # Returns
+    ///
+    /// The name of the entity, if it has one
+    #[must_use]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// Get the entity race
+    ///
+    /// # Returns
+    ///
+    /// The race of the entity (e.g. `dwarf`)
+    #[must_use]
+    pub fn get_race(&self) -> &str {
+        &self.race
+    }
+    /// Get the entity type
+    ///
+    /// # Returns
+    ///
+    /// The type of the entity (e.g. `civilization` or `sitegovernment`)
+    #[must_use]
+    pub fn get_entity_type(&self) -> &str {
+        &self.entity_type
+    }
+    /// Get the child IDs
+    ///
+    /// # Returns
+    ///
+    /// The IDs of the entities which are children of this one
+    #[must_use]
+    pub fn get_child_ids(&self) -> &[u32] {
+        &self.child_ids
+    }
+    /// Get the positions in the entity
+    ///
+    /// # Returns
+    ///
+    /// The positions (e.g. the monarch)
+    #[must_use]
+    pub fn get_positions(&self) -> &[ExportedEntityPosition] {
+        &self.positions
+    }
+    /// Get who holds the positions in the entity
+    ///
+    /// # Returns
+    ///
+    /// The position assignments
+    #[must_use]
+    pub fn get_position_assignments(&self) -> &[ExportedEntityPositionAssignment] {
+        &self.position_assignments
+    }
+    /// Get the historical figures which are members of the entity
+    ///
+    /// # Returns
+    ///
+    /// The IDs of the historical figures
+    #[must_use]
+    pub fn get_figure_ids(&self) -> &[i32] {
+        &self.figure_ids
     }
     /// Convert the `ExportedEntity` struct into an `Entity` struct
     ///
@@ -76,10 +257,48 @@ impl ExportedEntity {
             legend_metadata,
         )
     }
-    // pub fn set_population(&mut self, population: u32) {
-    //     self.population = population;
-    // }
-    // pub fn set_civ_id(&mut self, civ_id: u32) {
-    //     self.civ_id = civ_id;
-    // }
+}
+
+impl ExportedEntityPopulation {
+    /// Update the population with the fields of a record (fields missing from the record are left
+    /// as they were).
+    pub(crate) fn update_from_record(&mut self, record: &XmlRecord) {
+        self.id = record.get_i32("id").unwrap_or(self.id);
+        self.civ_id = record.get_i32("civ_id").or(self.civ_id);
+        // Races are written as `race:count`
+        extend_unique(
+            &mut self.races,
+            record.get_all("race").filter_map(|race| {
+                let (race, count) = race.rsplit_once(':')?;
+                Some((String::from(race), count.trim().parse().ok()?))
+            }),
+        );
+    }
+    /// Get the population ID
+    ///
+    /// # Returns
+    ///
+    /// The ID of the population
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// Get the civilization the population belongs to
+    ///
+    /// # Returns
+    ///
+    /// The ID of the civilization, if known
+    #[must_use]
+    pub const fn get_civ_id(&self) -> Option<i32> {
+        self.civ_id
+    }
+    /// Get the races in the population
+    ///
+    /// # Returns
+    ///
+    /// The races, with how many there are of each
+    #[must_use]
+    pub fn get_races(&self) -> &[(String, u32)] {
+        &self.races
+    }
 }
@@ -0,0 +1,134 @@
+//! The `HistoricalEvent` struct, something that happened in a world's history.
+
+use crate::legends_export::{record::XmlRecord, util::extend_unique};
+
+/// A historical event from a legends export (`<historical_event>`).
+///
+/// There are over a hundred types of event, each with their own fields, so apart from the id,
+/// time and type the fields are kept as they are in the export (e.g. `hfid` or `site_id`).
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoricalEvent {
+    id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    year: Option<i32>,
+    /// The time in the year the event happened (there are 72 per day)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seconds72: Option<i32>,
+    /// The type of event (e.g. `hf died` or `change hf state`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event_type: Option<String>,
+    /// The other fields of the event, in order (nested fields are named `parent/child`)
+    fields: Vec<(String, String)>,
+}
+
+impl HistoricalEvent {
+    /// Update the event with the fields of a record (fields missing from the record are left as
+    /// they were, and other fields are added to the existing ones).
+    pub(crate) fn update_from_record(&mut self, record: &XmlRecord) {
+        self.id = record.get_i32("id").unwrap_or(self.id);
+        self.year = record.get_i32("year").or(self.year);
+        self.seconds72 = record.get_i32("seconds72").or(self.seconds72);
+        self.event_type = record.get_string("type").or_else(|| self.event_type.take());
+
+        extend_unique(
+            &mut self.fields,
+            record
+                .flattened_fields()
+                .into_iter()
+                .filter(|(key, _)| !matches!(key.as_str(), "id" | "year" | "seconds72" | "type")),
+        );
+    }
+    /// Get the id of the event.
+    ///
+    /// # Returns
+    ///
+    /// * `i32` - The id.
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// Get the year the event happened.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The year, if known.
+    #[must_use]
+    pub const fn get_year(&self) -> Option<i32> {
+        self.year
+    }
+    /// Get the time in the year the event happened.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The time (there are 72 per day, and 403200 per year), if known.
+    #[must_use]
+    pub const fn get_seconds72(&self) -> Option<i32> {
+        self.seconds72
+    }
+    /// Get the type of the event.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The type (e.g. `hf died`), if known.
+    #[must_use]
+    pub fn get_event_type(&self) -> Option<&str> {
+        self.event_type.as_deref()
+    }
+    /// Get all the other fields of the event.
+    ///
+    /// # Returns
+    ///
+    /// * `&[(String, String)]` - The names and values of the fields, in order.
+    #[must_use]
+    pub fn get_fields(&self) -> &[(String, String)] {
+        &self.fields
+    }
+    /// Get the first value of a field.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the field (e.g. `slayer_hfid`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The value, if the event has the field.
+    #[must_use]
+    pub fn get_field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+    /// Get the first value of a field, as a number.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the field (e.g. `site_id`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The value, if the event has the field and it is a number.
+    #[must_use]
+    pub fn get_field_i32(&self, name: &str) -> Option<i32> {
+        self.get_field(name)
+            .and_then(|value| value.trim().parse().ok())
+    }
+    /// Get every value of a field (some fields, like `competitor_hfid`, are repeated).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the field.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&str>` - The values.
+    #[must_use]
+    pub fn get_field_values(&self, name: &str) -> Vec<&str> {
+        self.fields
+            .iter()
+            .filter(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+}
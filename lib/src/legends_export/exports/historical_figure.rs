@@ -0,0 +1,361 @@
+//! The `HistoricalFigure` struct, a person, creature, deity or force in a world's history.
+
+use crate::legends_export::{
+    record::XmlRecord,
+    util::{extend_unique, extend_unique_by},
+};
+
+/// A historical figure from a legends export (`<historical_figure>`).
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoricalFigure {
+    id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// The creature identifier of the figure's race (e.g. `DWARF`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    race: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    caste: Option<String>,
+    /// The sex of the figure (0 is female, 1 is male, -1 is none)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sex: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    appeared: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    birth_year: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    birth_seconds72: Option<i32>,
+    /// The year the figure died (-1 if still alive)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    death_year: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    death_seconds72: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    associated_type: Option<String>,
+    deity: bool,
+    force: bool,
+    spheres: Vec<String>,
+    goals: Vec<String>,
+    active_interactions: Vec<String>,
+    held_artifacts: Vec<i32>,
+    entity_links: Vec<FigureEntityLink>,
+    figure_links: Vec<FigureLink>,
+    site_links: Vec<FigureSiteLink>,
+    skills: Vec<FigureSkill>,
+}
+
+/// A link from a historical figure to an entity (`<entity_link>`).
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FigureEntityLink {
+    /// The kind of link (e.g. `member` or `enemy`)
+    pub link_type: String,
+    /// The id of the entity
+    pub entity_id: i32,
+    /// How strong the link is
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_strength: Option<i32>,
+}
+
+/// A link from a historical figure to another (`<hf_link>`).
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FigureLink {
+    /// The kind of link (e.g. `mother` or `spouse`)
+    pub link_type: String,
+    /// The id of the other historical figure
+    pub figure_id: i32,
+    /// How strong the link is
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_strength: Option<i32>,
+}
+
+/// A link from a historical figure to a site (`<site_link>`).
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FigureSiteLink {
+    /// The kind of link (e.g. `lair` or `home site building`)
+    pub link_type: String,
+    /// The id of the site
+    pub site_id: i32,
+    /// The local id of the structure in the site, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub structure_id: Option<i32>,
+    /// The id of the entity, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity_id: Option<i32>,
+}
+
+/// A skill of a historical figure (`<hf_skill>`).
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FigureSkill {
+    /// The skill (e.g. `MINING`)
+    pub skill: String,
+    /// The experience in the skill
+    pub total_ip: i32,
+}
+
+impl HistoricalFigure {
+    /// Update the historical figure with the fields of a record (fields missing from the record
+    /// are left as they were, and links are added to the existing ones unless they link to the
+    /// same thing).
+    pub(crate) fn update_from_record(&mut self, record: &XmlRecord) {
+        self.id = record.get_i32("id").unwrap_or(self.id);
+        self.name = record.get_string("name").or_else(|| self.name.take());
+        self.race = record.get_string("race").or_else(|| self.race.take());
+        self.caste = record.get_string("caste").or_else(|| self.caste.take());
+        self.sex = record.get_i32("sex").or(self.sex);
+        self.appeared = record.get_i32("appeared").or(self.appeared);
+        self.birth_year = record.get_i32("birth_year").or(self.birth_year);
+        self.birth_seconds72 = record.get_i32("birth_seconds72").or(self.birth_seconds72);
+        self.death_year = record.get_i32("death_year").or(self.death_year);
+        self.death_seconds72 = record.get_i32("death_seconds72").or(self.death_seconds72);
+        self.associated_type = record
+            .get_string("associated_type")
+            .or_else(|| self.associated_type.take());
+        self.deity |= record.has("deity");
+        self.force |= record.has("force");
+
+        extend_unique(
+            &mut self.spheres,
+            record.get_all("sphere").map(String::from),
+        );
+        extend_unique(&mut self.goals, record.get_all("goal").map(String::from));
+        extend_unique(
+            &mut self.active_interactions,
+            record.get_all("active_interaction").map(String::from),
+        );
+        extend_unique(
+            &mut self.held_artifacts,
+            record
+                .get_all("holds_artifact")
+                .filter_map(|id| id.parse().ok()),
+        );
+        extend_unique_by(
+            &mut self.entity_links,
+            record.children_named("entity_link").filter_map(|link| {
+                Some(FigureEntityLink {
+                    link_type: link.get_string("link_type")?,
+                    entity_id: link.get_i32("entity_id")?,
+                    link_strength: link.get_i32("link_strength"),
+                })
+            }),
+            |a, b| a.link_type == b.link_type && a.entity_id == b.entity_id,
+        );
+        extend_unique_by(
+            &mut self.figure_links,
+            record.children_named("hf_link").filter_map(|link| {
+                Some(FigureLink {
+                    link_type: link.get_string("link_type")?,
+                    figure_id: link.get_i32("hfid")?,
+                    link_strength: link.get_i32("link_strength"),
+                })
+            }),
+            |a, b| a.link_type == b.link_type && a.figure_id == b.figure_id,
+        );
+        extend_unique_by(
+            &mut self.site_links,
+            record.children_named("site_link").filter_map(|link| {
+                Some(FigureSiteLink {
+                    link_type: link.get_string("link_type")?,
+                    site_id: link.get_i32("site_id")?,
+                    structure_id: link.get_i32("sub_id").filter(|id| *id >= 0),
+                    entity_id: link.get_i32("entity_id").filter(|id| *id >= 0),
+                })
+            }),
+            |a, b| a.link_type == b.link_type && a.site_id == b.site_id,
+        );
+        extend_unique_by(
+            &mut self.skills,
+            record.children_named("hf_skill").filter_map(|skill| {
+                Some(FigureSkill {
+                    skill: skill.get_string("skill")?,
+                    total_ip: skill.get_i32("total_ip").unwrap_or_default(),
+                })
+            }),
+            |a, b| a.skill == b.skill,
+        );
+    }
+    /// Get the id of the historical figure.
+    ///
+    /// # Returns
+    ///
+    /// * `i32` - The id.
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// Get the name of the historical figure.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The name, if it has one.
+    #[must_use]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// Get the race of the historical figure.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The creature identifier of the race (e.g. `DWARF`), if known.
+    #[must_use]
+    pub fn get_race(&self) -> Option<&str> {
+        self.race.as_deref()
+    }
+    /// Get the caste of the historical figure.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The caste identifier (e.g. `FEMALE`), if known.
+    #[must_use]
+    pub fn get_caste(&self) -> Option<&str> {
+        self.caste.as_deref()
+    }
+    /// Get the sex of the historical figure.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The sex (0 is female, 1 is male, -1 is none), if known.
+    #[must_use]
+    pub const fn get_sex(&self) -> Option<i32> {
+        self.sex
+    }
+    /// Get the year the historical figure appeared in the world.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The year, if known.
+    #[must_use]
+    pub const fn get_appeared(&self) -> Option<i32> {
+        self.appeared
+    }
+    /// Get the year the historical figure was born.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The birth year, if known.
+    #[must_use]
+    pub const fn get_birth_year(&self) -> Option<i32> {
+        self.birth_year
+    }
+    /// Get the year the historical figure died.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The death year, or `None` if the figure is alive (or it's unknown).
+    #[must_use]
+    pub fn get_death_year(&self) -> Option<i32> {
+        self.death_year.filter(|year| *year >= 0)
+    }
+    /// Check whether the historical figure is alive (at the time of the export).
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the figure is alive.
+    #[must_use]
+    pub fn is_alive(&self) -> bool {
+        self.get_death_year().is_none()
+    }
+    /// Get the type of the historical figure (e.g. `standard`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The associated type, if known.
+    #[must_use]
+    pub fn get_associated_type(&self) -> Option<&str> {
+        self.associated_type.as_deref()
+    }
+    /// Check whether the historical figure is a deity.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the figure is a deity.
+    #[must_use]
+    pub const fn is_deity(&self) -> bool {
+        self.deity
+    }
+    /// Check whether the historical figure is a force (of nature).
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the figure is a force.
+    #[must_use]
+    pub const fn is_force(&self) -> bool {
+        self.force
+    }
+    /// Get the spheres of the historical figure (for deities, e.g. `MOUNTAINS`).
+    ///
+    /// # Returns
+    ///
+    /// * `&[String]` - The spheres.
+    #[must_use]
+    pub fn get_spheres(&self) -> &[String] {
+        &self.spheres
+    }
+    /// Get the goals of the historical figure.
+    ///
+    /// # Returns
+    ///
+    /// * `&[String]` - The goals.
+    #[must_use]
+    pub fn get_goals(&self) -> &[String] {
+        &self.goals
+    }
+    /// Get the interactions the historical figure can use (e.g. `DEITY_CURSE_WEREBEAST_...`).
+    ///
+    /// # Returns
+    ///
+    /// * `&[String]` - The interactions.
+    #[must_use]
+    pub fn get_active_interactions(&self) -> &[String] {
+        &self.active_interactions
+    }
+    /// Get the artifacts the historical figure holds.
+    ///
+    /// # Returns
+    ///
+    /// * `&[i32]` - The artifact ids.
+    #[must_use]
+    pub fn get_held_artifacts(&self) -> &[i32] {
+        &self.held_artifacts
+    }
+    /// Get the entities the historical figure is linked to.
+    ///
+    /// # Returns
+    ///
+    /// * `&[FigureEntityLink]` - The entity links.
+    #[must_use]
+    pub fn get_entity_links(&self) -> &[FigureEntityLink] {
+        &self.entity_links
+    }
+    /// Get the historical figures this one is linked to (family, spouses, deities, ...).
+    ///
+    /// # Returns
+    ///
+    /// * `&[FigureLink]` - The links to other historical figures.
+    #[must_use]
+    pub fn get_figure_links(&self) -> &[FigureLink] {
+        &self.figure_links
+    }
+    /// Get the sites the historical figure is linked to.
+    ///
+    /// # Returns
+    ///
+    /// * `&[FigureSiteLink]` - The site links.
+    #[must_use]
+    pub fn get_site_links(&self) -> &[FigureSiteLink] {
+        &self.site_links
+    }
+    /// Get the skills of the historical figure.
+    ///
+    /// # Returns
+    ///
+    /// * `&[FigureSkill]` - The skills.
+    #[must_use]
+    pub fn get_skills(&self) -> &[FigureSkill] {
+        &self.skills
+    }
+}
@@ -1,7 +1,24 @@
 //! Representations of the various exports from the Legends Viewer.
 
+pub mod artifact;
 pub mod creature;
 pub mod entity;
+pub mod historical_event;
+pub mod historical_figure;
+pub mod region;
+pub mod site;
+pub mod world_construction;
 
-pub use creature::ExportedCreature;
-pub use entity::ExportedEntity;
+pub use artifact::Artifact;
+pub use creature::{ExportedCaste, ExportedCreature};
+pub use entity::{
+    ExportedEntity, ExportedEntityPopulation, ExportedEntityPosition,
+    ExportedEntityPositionAssignment,
+};
+pub use historical_event::HistoricalEvent;
+pub use historical_figure::{
+    FigureEntityLink, FigureLink, FigureSiteLink, FigureSkill, HistoricalFigure,
+};
+pub use region::Region;
+pub use site::{Site, Structure};
+pub use world_construction::WorldConstruction;
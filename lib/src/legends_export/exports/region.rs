@@ -0,0 +1,96 @@
+//! The `Region` struct, an area of a world's surface or underground.
+
+use crate::legends_export::{record::XmlRecord, util::parse_coords_list};
+
+/// A region from a legends export (`<region>` or `<underground_region>`).
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+    id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// The type of region (e.g. `Mountains`, or `cavern` for underground regions)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region_type: Option<String>,
+    /// How evil the region is (e.g. `evil` or `good`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    evilness: Option<String>,
+    /// The depth of an underground region (1 to 3 for the caverns, 4 for the magma sea)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    depth: Option<i32>,
+    /// The world tiles the region covers
+    coords: Vec<[i32; 2]>,
+}
+
+impl Region {
+    /// Update the region with the fields of a record (fields missing from the record are left as
+    /// they were).
+    pub(crate) fn update_from_record(&mut self, record: &XmlRecord) {
+        self.id = record.get_i32("id").unwrap_or(self.id);
+        self.name = record.get_string("name").or_else(|| self.name.take());
+        self.region_type = record
+            .get_string("type")
+            .or_else(|| self.region_type.take());
+        self.evilness = record
+            .get_string("evilness")
+            .or_else(|| self.evilness.take());
+        self.depth = record.get_i32("depth").or(self.depth);
+        if let Some(coords) = record.get("coords") {
+            self.coords = parse_coords_list(coords);
+        }
+    }
+    /// Get the id of the region.
+    ///
+    /// # Returns
+    ///
+    /// * `i32` - The id.
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// Get the name of the region.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The name, if it has one.
+    #[must_use]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// Get the type of the region.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The type (e.g. `Mountains`), if known.
+    #[must_use]
+    pub fn get_region_type(&self) -> Option<&str> {
+        self.region_type.as_deref()
+    }
+    /// Get how evil the region is.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The evilness (e.g. `evil`), if known.
+    #[must_use]
+    pub fn get_evilness(&self) -> Option<&str> {
+        self.evilness.as_deref()
+    }
+    /// Get the depth of an underground region.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The depth, for underground regions.
+    #[must_use]
+    pub const fn get_depth(&self) -> Option<i32> {
+        self.depth
+    }
+    /// Get the world tiles the region covers.
+    ///
+    /// # Returns
+    ///
+    /// * `&[[i32; 2]]` - The x and y coordinates of each tile.
+    #[must_use]
+    pub fn get_coords(&self) -> &[[i32; 2]] {
+        &self.coords
+    }
+}
@@ -0,0 +1,268 @@
+//! The `Site` and `Structure` structs, the places in a world and the buildings in them.
+
+use crate::legends_export::{
+    record::XmlRecord,
+    util::{extend_unique, parse_coords, parse_rectangle},
+};
+
+/// A site from a legends export (`<site>`), like a fortress, town or cave.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Site {
+    id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// The type of site (e.g. `fortress` or `cave`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    site_type: Option<String>,
+    /// The position of the site on the world map
+    #[serde(skip_serializing_if = "Option::is_none")]
+    coords: Option<[i32; 2]>,
+    /// The corners of the site, in world tiles
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rectangle: Option<[[i32; 2]; 2]>,
+    /// The id of the civilization the site belongs to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    civ_id: Option<i32>,
+    /// The id of the entity which currently owns the site
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current_owner_id: Option<i32>,
+    structures: Vec<Structure>,
+}
+
+/// A structure in a site (`<structure>`), like a temple, tavern or tomb.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Structure {
+    /// The id of the structure within its site
+    local_id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// A second name of the structure (e.g. for temples)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name2: Option<String>,
+    /// The type of structure (e.g. `temple` or `mead hall`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    structure_type: Option<String>,
+    /// The id of the entity which owns the structure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entity_id: Option<i32>,
+    /// The id of the historical figure worshipped in the structure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    worship_figure_id: Option<i32>,
+    /// The ids of the historical figures living in the structure
+    inhabitants: Vec<i32>,
+}
+
+impl Site {
+    /// Update the site with the fields of a record (fields missing from the record are left as
+    /// they were, and structures are merged by their local id).
+    pub(crate) fn update_from_record(&mut self, record: &XmlRecord) {
+        self.id = record.get_i32("id").unwrap_or(self.id);
+        self.name = record.get_string("name").or_else(|| self.name.take());
+        self.site_type = record.get_string("type").or_else(|| self.site_type.take());
+        self.coords = record.get("coords").and_then(parse_coords).or(self.coords);
+        self.rectangle = record
+            .get("rectangle")
+            .and_then(parse_rectangle)
+            .or(self.rectangle);
+        self.civ_id = record.get_i32("civ_id").or(self.civ_id);
+        self.current_owner_id = record.get_i32("cur_owner_id").or(self.current_owner_id);
+
+        for structures in record.children_named("structures") {
+            for structure_record in structures.children_named("structure") {
+                let Some(local_id) = structure_record
+                    .get_i32("local_id")
+                    .or_else(|| structure_record.get_i32("id"))
+                else {
+                    continue;
+                };
+                if let Some(structure) = self
+                    .structures
+                    .iter_mut()
+                    .find(|structure| structure.local_id == local_id)
+                {
+                    structure.update_from_record(structure_record);
+                } else {
+                    let mut structure = Structure {
+                        local_id,
+                        ..Structure::default()
+                    };
+                    structure.update_from_record(structure_record);
+                    self.structures.push(structure);
+                }
+            }
+        }
+    }
+    /// Get the id of the site.
+    ///
+    /// # Returns
+    ///
+    /// * `i32` - The id.
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// Get the name of the site.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The name, if it has one.
+    #[must_use]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// Get the type of the site.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The type (e.g. `fortress`), if known.
+    #[must_use]
+    pub fn get_site_type(&self) -> Option<&str> {
+        self.site_type.as_deref()
+    }
+    /// Get the position of the site on the world map.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<[i32; 2]>` - The x and y coordinates, if known.
+    #[must_use]
+    pub const fn get_coords(&self) -> Option<[i32; 2]> {
+        self.coords
+    }
+    /// Get the area of the site.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<[[i32; 2]; 2]>` - The two corners of the site, if known.
+    #[must_use]
+    pub const fn get_rectangle(&self) -> Option<[[i32; 2]; 2]> {
+        self.rectangle
+    }
+    /// Get the civilization the site belongs to.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The id of the civilization, if known.
+    #[must_use]
+    pub const fn get_civ_id(&self) -> Option<i32> {
+        self.civ_id
+    }
+    /// Get the entity which currently owns the site.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The id of the entity, if known.
+    #[must_use]
+    pub const fn get_current_owner_id(&self) -> Option<i32> {
+        self.current_owner_id
+    }
+    /// Get the structures in the site.
+    ///
+    /// # Returns
+    ///
+    /// * `&[Structure]` - The structures.
+    #[must_use]
+    pub fn get_structures(&self) -> &[Structure] {
+        &self.structures
+    }
+    /// Get a structure in the site.
+    ///
+    /// # Arguments
+    ///
+    /// * `local_id` - The id of the structure within the site.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&Structure>` - The structure, if it exists.
+    #[must_use]
+    pub fn get_structure(&self, local_id: i32) -> Option<&Structure> {
+        self.structures
+            .iter()
+            .find(|structure| structure.local_id == local_id)
+    }
+}
+
+impl Structure {
+    fn update_from_record(&mut self, record: &XmlRecord) {
+        self.name = record.get_string("name").or_else(|| self.name.take());
+        self.name2 = record.get_string("name2").or_else(|| self.name2.take());
+        self.structure_type = record
+            .get_string("type")
+            .or_else(|| self.structure_type.take());
+        self.entity_id = record.get_i32("entity_id").or(self.entity_id);
+        self.worship_figure_id = record
+            .get_i32("worship_hfid")
+            .or_else(|| record.get_i32("deity"))
+            .or(self.worship_figure_id);
+        extend_unique(
+            &mut self.inhabitants,
+            record
+                .get_all("inhabitant")
+                .filter_map(|id| id.parse().ok()),
+        );
+    }
+    /// Get the id of the structure within its site.
+    ///
+    /// # Returns
+    ///
+    /// * `i32` - The local id.
+    #[must_use]
+    pub const fn get_local_id(&self) -> i32 {
+        self.local_id
+    }
+    /// Get the name of the structure.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The name, if it has one.
+    #[must_use]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// Get the second name of the structure.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The second name, if it has one.
+    #[must_use]
+    pub fn get_name2(&self) -> Option<&str> {
+        self.name2.as_deref()
+    }
+    /// Get the type of the structure.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The type (e.g. `temple`), if known.
+    #[must_use]
+    pub fn get_structure_type(&self) -> Option<&str> {
+        self.structure_type.as_deref()
+    }
+    /// Get the entity which owns the structure.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The id of the entity, if known.
+    #[must_use]
+    pub const fn get_entity_id(&self) -> Option<i32> {
+        self.entity_id
+    }
+    /// Get the historical figure worshipped in the structure.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The id of the historical figure, if any.
+    #[must_use]
+    pub const fn get_worship_figure_id(&self) -> Option<i32> {
+        self.worship_figure_id
+    }
+    /// Get the historical figures living in the structure.
+    ///
+    /// # Returns
+    ///
+    /// * `&[i32]` - The ids of the historical figures.
+    #[must_use]
+    pub fn get_inhabitants(&self) -> &[i32] {
+        &self.inhabitants
+    }
+}
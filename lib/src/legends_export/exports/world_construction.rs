@@ -0,0 +1,68 @@
+//! The `WorldConstruction` struct, a road, bridge or tunnel between sites.
+
+use crate::legends_export::{record::XmlRecord, util::parse_coords_list};
+
+/// A world construction from a legends export (`<world_construction>`).
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldConstruction {
+    id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// The type of construction (`road`, `bridge` or `tunnel`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    construction_type: Option<String>,
+    /// The world tiles the construction covers
+    coords: Vec<[i32; 2]>,
+}
+
+impl WorldConstruction {
+    /// Update the world construction with the fields of a record (fields missing from the record
+    /// are left as they were).
+    pub(crate) fn update_from_record(&mut self, record: &XmlRecord) {
+        self.id = record.get_i32("id").unwrap_or(self.id);
+        self.name = record.get_string("name").or_else(|| self.name.take());
+        self.construction_type = record
+            .get_string("type")
+            .or_else(|| self.construction_type.take());
+        if let Some(coords) = record.get("coords") {
+            self.coords = parse_coords_list(coords);
+        }
+    }
+    /// Get the id of the world construction.
+    ///
+    /// # Returns
+    ///
+    /// * `i32` - The id.
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// Get the name of the world construction.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The name, if it has one.
+    #[must_use]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// Get the type of the world construction.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The type (`road`, `bridge` or `tunnel`), if known.
+    #[must_use]
+    pub fn get_construction_type(&self) -> Option<&str> {
+        self.construction_type.as_deref()
+    }
+    /// Get the world tiles the construction covers.
+    ///
+    /// # Returns
+    ///
+    /// * `&[[i32; 2]]` - The x and y coordinates of each tile.
+    #[must_use]
+    pub fn get_coords(&self) -> &[[i32; 2]] {
+        &self.coords
+    }
+}
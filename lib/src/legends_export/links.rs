@@ -0,0 +1,309 @@
+//! Links from the creatures and entities of a legends export back to the raws they came from.
+//!
+//! A legends export only names the creatures of its world (`creature_id`) and the races of its
+//! entities, so the `Creature`s and `Entity`s made from it are stripped down. `LegendsLinks`
+//! matches them to the full raws parsed alongside the export, which shows which raws actually
+//! exist in the world. Creatures the world generated (forgotten beasts, titans, night creatures,
+//! ...) have no raws, and are told apart from creatures whose raws weren't parsed (e.g. from a mod
+//! which isn't installed).
+//!
+//! ```rust,no_run
+//! use dfraw_parser::{
+//!     legends_export::LegendsLinks,
+//!     metadata::{ParserOptions, RawModuleLocation},
+//! };
+//!
+//! let mut options = ParserOptions::new("path/to/dwarf_fortress");
+//! options.add_location_to_parse(RawModuleLocation::Vanilla);
+//! options.add_legends_export_to_parse(&"path/to/region1-legends_plus.xml");
+//! let result = dfraw_parser::parse(&options).unwrap();
+//!
+//! let links = LegendsLinks::from_parse_result(&result);
+//! for creature_id in links.get_missing_creatures() {
+//!     println!("{creature_id} is in the world but its raws weren't parsed");
+//! }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    creature::Creature,
+    entity::Entity,
+    metadata::{ObjectType, RawModuleLocation},
+    tags::CreatureTag,
+    traits::RawObject,
+    ParseResult,
+};
+
+use super::util::is_generated_creature_id;
+
+/// How an exported creature or entity relates to the parsed raws.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkStatus {
+    /// The raws it comes from were parsed
+    Linked,
+    /// It was generated by the game, so there are no raws for it
+    Generated,
+    /// It should have raws, but they weren't parsed
+    Missing,
+}
+
+/// An exported creature and the creature raw it comes from.
+#[derive(Debug, Clone)]
+pub struct CreatureLink<'a> {
+    /// The creature made from the legends export
+    pub exported: &'a Creature,
+    /// The creature raw with the same identifier, if one was parsed
+    pub source: Option<&'a Creature>,
+    /// How the exported creature relates to the raws
+    pub status: LinkStatus,
+}
+
+/// An exported entity and the raws for its race.
+#[derive(Debug, Clone)]
+pub struct EntityLink<'a> {
+    /// The entity made from the legends export
+    pub exported: &'a Entity,
+    /// The race of the entity (the creature identifier, e.g. `DWARF`)
+    pub race: String,
+    /// The creature raw for the race, if one was parsed
+    pub creature: Option<&'a Creature>,
+    /// The entity raws for the race (e.g. `MOUNTAIN` for dwarves)
+    pub entities: Vec<&'a Entity>,
+    /// How the exported entity relates to the raws
+    pub status: LinkStatus,
+}
+
+/// Links from the creatures and entities of legends exports to the raws they come from.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Default)]
+pub struct LegendsLinks<'a> {
+    creatures: Vec<CreatureLink<'a>>,
+    entities: Vec<EntityLink<'a>>,
+    /// The object ids of the raws which are in the world
+    in_world: HashSet<&'a str>,
+}
+
+impl<'a> LegendsLinks<'a> {
+    /// Link the legends export raws in a list of parsed raws to the other raws in it.
+    ///
+    /// Raws are matched by identifier (ignoring case). If more than one module has a raw with the
+    /// same identifier, the one parsed last is used (the way the game does).
+    ///
+    /// # Arguments
+    ///
+    /// * `raws` - The parsed raws, including those from legends exports.
+    ///
+    /// # Returns
+    ///
+    /// * `LegendsLinks` - The links.
+    #[must_use]
+    pub fn new(raws: &'a [Box<dyn RawObject>]) -> Self {
+        let mut exported_creatures: Vec<&Creature> = Vec::new();
+        let mut exported_entities: Vec<&Entity> = Vec::new();
+        let mut source_creatures: HashMap<String, &Creature> = HashMap::new();
+        let mut source_entities: Vec<&Entity> = Vec::new();
+
+        for raw in raws {
+            let from_legends =
+                *raw.get_metadata().get_location() == RawModuleLocation::LegendsExport;
+            match raw.get_type() {
+                ObjectType::Creature => {
+                    let Some(creature) = raw.as_any().downcast_ref::<Creature>() else {
+                        continue;
+                    };
+                    if from_legends {
+                        exported_creatures.push(creature);
+                    } else {
+                        source_creatures.insert(creature.get_identifier().to_uppercase(), creature);
+                    }
+                }
+                ObjectType::Entity => {
+                    let Some(entity) = raw.as_any().downcast_ref::<Entity>() else {
+                        continue;
+                    };
+                    if from_legends {
+                        exported_entities.push(entity);
+                    } else {
+                        source_entities.push(entity);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        let mut links = Self::default();
+        let mut generated: HashSet<String> = HashSet::new();
+
+        for exported in exported_creatures {
+            let identifier = exported.get_identifier().to_uppercase();
+            let source = source_creatures.get(&identifier).copied();
+            let status = if source.is_some() {
+                LinkStatus::Linked
+            } else if exported.has_tag(&CreatureTag::Generated)
+                || is_generated_creature_id(&identifier)
+            {
+                generated.insert(identifier);
+                LinkStatus::Generated
+            } else {
+                LinkStatus::Missing
+            };
+            if let Some(source) = source {
+                links.in_world.insert(source.get_object_id());
+            }
+            links.creatures.push(CreatureLink {
+                exported,
+                source,
+                status,
+            });
+        }
+
+        for exported in exported_entities {
+            // Exported entities are named `race-typeID` (e.g. `dwarf-civilization2`)
+            let race = exported
+                .get_identifier()
+                .split_once('-')
+                .map_or("", |(race, _)| race)
+                .to_uppercase();
+            if race.is_empty() {
+                continue;
+            }
+            let creature = source_creatures.get(&race).copied();
+            let entities: Vec<&Entity> = source_entities
+                .iter()
+                .filter(|entity| {
+                    entity
+                        .get_creature()
+                        .is_some_and(|creature| creature.eq_ignore_ascii_case(&race))
+                })
+                .copied()
+                .collect();
+            let status = if creature.is_some() {
+                LinkStatus::Linked
+            } else if generated.contains(&race) || is_generated_creature_id(&race) {
+                LinkStatus::Generated
+            } else {
+                LinkStatus::Missing
+            };
+            if let Some(creature) = creature {
+                links.in_world.insert(creature.get_object_id());
+            }
+            for entity in &entities {
+                links.in_world.insert(entity.get_object_id());
+            }
+            links.entities.push(EntityLink {
+                exported,
+                race,
+                creature,
+                entities,
+                status,
+            });
+        }
+
+        links
+    }
+    /// Link the legends export raws in a parse result to the other raws in it.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The result of parsing (which should include the raws and a legends export).
+    ///
+    /// # Returns
+    ///
+    /// * `LegendsLinks` - The links.
+    #[must_use]
+    pub fn from_parse_result(result: &'a ParseResult) -> Self {
+        Self::new(&result.raws)
+    }
+    /// Get the links for the exported creatures.
+    ///
+    /// # Returns
+    ///
+    /// * `&[CreatureLink]` - The links, in the order the creatures were parsed.
+    #[must_use]
+    pub fn get_creature_links(&self) -> &[CreatureLink<'a>] {
+        &self.creatures
+    }
+    /// Get the links for the exported entities (entities without a race are left out).
+    ///
+    /// # Returns
+    ///
+    /// * `&[EntityLink]` - The links, in the order the entities were parsed.
+    #[must_use]
+    pub fn get_entity_links(&self) -> &[EntityLink<'a>] {
+        &self.entities
+    }
+    /// Get the creature raw an exported creature comes from.
+    ///
+    /// # Arguments
+    ///
+    /// * `creature_id` - The identifier of the creature (e.g. `DWARF`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&Creature>` - The creature raw, if the creature is in the world and its raws were
+    ///   parsed.
+    #[must_use]
+    pub fn get_source_creature(&self, creature_id: &str) -> Option<&'a Creature> {
+        self.creatures
+            .iter()
+            .find(|link| {
+                link.exported
+                    .get_identifier()
+                    .eq_ignore_ascii_case(creature_id)
+            })
+            .and_then(|link| link.source)
+    }
+    /// Check whether a raw is in the world (i.e. an exported creature or entity links to it).
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw to check.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the raw is in the world.
+    #[must_use]
+    pub fn is_in_world(&self, raw: &dyn RawObject) -> bool {
+        self.in_world.contains(raw.get_object_id())
+    }
+    /// Get the creatures which were generated by the game.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&Creature>` - The exported creatures with no raws because they were generated.
+    #[must_use]
+    pub fn get_generated_creatures(&self) -> Vec<&'a Creature> {
+        self.creatures
+            .iter()
+            .filter(|link| link.status == LinkStatus::Generated)
+            .map(|link| link.exported)
+            .collect()
+    }
+    /// Get the identifiers of the exported creatures whose raws weren't parsed.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&str>` - The creature identifiers.
+    #[must_use]
+    pub fn get_missing_creatures(&self) -> Vec<&'a str> {
+        self.creatures
+            .iter()
+            .filter(|link| link.status == LinkStatus::Missing)
+            .map(|link| link.exported.get_identifier())
+            .collect()
+    }
+    /// Get the identifiers of the exported entities whose race's raws weren't parsed.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&str>` - The identifiers of the exported entities (e.g. `elf-civilization3`).
+    #[must_use]
+    pub fn get_missing_entities(&self) -> Vec<&'a str> {
+        self.entities
+            .iter()
+            .filter(|link| link.status == LinkStatus::Missing)
+            .map(|link| link.exported.get_identifier())
+            .collect()
+    }
+}
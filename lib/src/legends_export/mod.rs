@@ -1,12 +1,44 @@
 //! This module supports parsing Dwarf Fortress legends export files.
 //!
-//! So far this only supports parsing the creature and entity sections of the legends export.
+//! `parse` reads the creature and entity sections of a legends export into raw objects, and
+//! `parse_world` reads the history of the world (historical figures, sites, artifacts, regions,
+//! world constructions and historical events) into a `LegendsWorld`. Dwarf Fortress writes
+//! `<world>-legends.xml` and `DFHack` adds `<world>-legends_plus.xml` with more details;
+//! `parse_world_files` merges both into one `LegendsWorld`.
+//!
+//! `LegendsLinks` links the creatures and entities of a legends export back to the raws they come
+//! from.
+//!
+//! The exports are streamed rather than read into memory, since the exports of old worlds can be
+//! several gigabytes. `parse_with_progress`, `parse_world_with_progress` and
+//! `parse_world_files_with_progress` report how much of the exports has been read.
+//!
+//! ```no_run
+//! use dfraw_parser::legends_export::{find_world_exports, parse_world_files};
+//!
+//! let exports = find_world_exports(&"region1-00250-01-01-legends.xml");
+//! let world = parse_world_files(&exports).unwrap();
+//! println!("{:?} has {} historical figures", world.get_name(), world.get_historical_figures().len());
+//! ```
 
+mod links;
+mod progress;
 mod reader;
+mod record;
 mod util;
+mod world;
 
 pub mod exports;
 
 pub use exports::ExportedCreature;
 pub use exports::ExportedEntity;
+pub use links::{CreatureLink, EntityLink, LegendsLinks, LinkStatus};
+pub use progress::LegendsProgress;
 pub use reader::parse_legends_export as parse;
+pub use reader::parse_legends_export_with_progress as parse_with_progress;
+pub use reader::parse_legends_world as parse_world;
+pub use reader::parse_legends_world_files as parse_world_files;
+pub use reader::parse_legends_world_files_with_progress as parse_world_files_with_progress;
+pub use reader::parse_legends_world_with_progress as parse_world_with_progress;
+pub use util::find_world_exports;
+pub use world::LegendsWorld;
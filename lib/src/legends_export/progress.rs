@@ -0,0 +1,86 @@
+//! Progress reporting for reading legends exports, which can be several gigabytes.
+
+use std::io::{BufRead, Read};
+
+/// How often progress is reported while reading a legends export.
+const PROGRESS_INTERVAL: u64 = 4 * 1024 * 1024;
+
+/// How far through a legends export the reader is.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LegendsProgress {
+    /// The number of bytes of the export which have been read
+    pub bytes_read: u64,
+    /// The size of the export in bytes (0 if it couldn't be determined)
+    pub total_bytes: u64,
+}
+
+impl LegendsProgress {
+    /// Get how much of the export has been read.
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The fraction of the export which has been read (from 0.0 to 1.0), or 0.0 if the
+    ///   size of the export isn't known.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn get_fraction(&self) -> f64 {
+        if self.total_bytes == 0 {
+            0.0
+        } else {
+            (self.bytes_read as f64 / self.total_bytes as f64).min(1.0)
+        }
+    }
+}
+
+/// A reader which counts the bytes consumed from it, and reports the count every
+/// `PROGRESS_INTERVAL` bytes.
+pub struct ProgressReader<R, F> {
+    inner: R,
+    progress: LegendsProgress,
+    next_report: u64,
+    on_progress: F,
+}
+
+impl<R: BufRead, F: FnMut(LegendsProgress)> ProgressReader<R, F> {
+    pub const fn new(inner: R, total_bytes: u64, on_progress: F) -> Self {
+        Self {
+            inner,
+            progress: LegendsProgress {
+                bytes_read: 0,
+                total_bytes,
+            },
+            next_report: PROGRESS_INTERVAL,
+            on_progress,
+        }
+    }
+    /// Report the final count (when the reader is done with).
+    pub fn finish(mut self) {
+        (self.on_progress)(self.progress);
+    }
+    fn advance(&mut self, amount: usize) {
+        self.progress.bytes_read += amount as u64;
+        if self.progress.bytes_read >= self.next_report {
+            self.next_report = self.progress.bytes_read + PROGRESS_INTERVAL;
+            (self.on_progress)(self.progress);
+        }
+    }
+}
+
+impl<R: BufRead, F: FnMut(LegendsProgress)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let amount = self.inner.read(buf)?;
+        self.advance(amount);
+        Ok(amount)
+    }
+}
+
+impl<R: BufRead, F: FnMut(LegendsProgress)> BufRead for ProgressReader<R, F> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+    fn consume(&mut self, amount: usize) {
+        self.inner.consume(amount);
+        self.advance(amount);
+    }
+}
@@ -1,39 +1,24 @@
-use std::io::Read;
+use std::collections::HashMap;
+use std::io::BufReader;
 use std::path::Path;
 
-use quick_xml::{events::Event, Reader};
+use quick_xml::Reader;
 use tracing::{error, info};
 
 use crate::{
-    legends_export::{ExportedCreature, ExportedEntity},
+    legends_export::{ExportedCreature, ExportedEntity, LegendsWorld},
     metadata::{ObjectType, ParserOptions},
     traits::RawObject,
     utilities::try_get_file,
     ParserError,
 };
 
-use super::util::legends_metadata;
-
-#[derive(Eq, PartialEq)]
-enum Current {
-    None,
-    NameSingular,
-    NamePlural,
-    Identifier,
-    CivId,
-    Child,
-    Type,
-    Race,
-}
-
-#[derive(Eq, PartialEq)]
-enum Parent {
-    None,
-    Creature,
-    Entity,
-}
+use super::{
+    progress::{LegendsProgress, ProgressReader},
+    record::{read_items, Encoding, LegendsItem},
+    util::{extend_unique, is_legends_plus, legends_metadata},
+};
 
-#[allow(clippy::too_many_lines)]
 /// Parses the legends export file at the specified input path and returns a vector of raw objects.
 ///
 /// # Arguments
@@ -51,13 +36,38 @@ pub fn parse_legends_export<P: AsRef<Path>>(
     input_path: &P,
     options: &ParserOptions,
 ) -> Result<Vec<Box<dyn RawObject>>, ParserError> {
-    let mut results: Vec<Box<dyn RawObject>> = Vec::new();
-    let mut file = try_get_file(&input_path.as_ref())?;
+    parse_legends_export_with_progress(input_path, options, |_| ())
+}
 
-    // Read the file into a str for parsing
-    let mut file_str = String::new();
-    file.read_to_string(&mut file_str)
-        .map_err(|e| ParserError::Io { source: e })?;
+/// Parses the legends export file at the specified input path and returns a vector of raw objects,
+/// reporting progress as the file is read.
+///
+/// The file is streamed, so only one record of the export is held in memory at a time (apart from
+/// the creatures and entities which are returned). Progress is reported every few megabytes, and
+/// once more when the file has been read.
+///
+/// # Arguments
+///
+/// * `input_path` - The path to the legends export file.
+/// * `options` - The parser options (for the metadata of the raws).
+/// * `on_progress` - Called with the number of bytes read so far (and the size of the file).
+///
+/// # Returns
+///
+/// A vector of boxed dynamic `RawObject` trait objects.
+///
+/// # Errors
+///
+/// Returns a `ParserError` if there is an issue reading the file. Malformed XML is logged, and the
+/// raw objects read before it are returned.
+pub fn parse_legends_export_with_progress<P: AsRef<Path>>(
+    input_path: &P,
+    options: &ParserOptions,
+    on_progress: impl FnMut(LegendsProgress),
+) -> Result<Vec<Box<dyn RawObject>>, ParserError> {
+    let mut results: Vec<Box<dyn RawObject>> = Vec::new();
+    let file = try_get_file(&input_path.as_ref())?;
+    let total_bytes = file.metadata().map_or(0, |metadata| metadata.len());
 
     info!(
         "parse_legends_export: Parsing file {}",
@@ -68,106 +78,65 @@ pub fn parse_legends_export<P: AsRef<Path>>(
             .to_string_lossy()
     );
 
-    let mut reader = Reader::from_str(&file_str);
+    let mut reader = Reader::from_reader(ProgressReader::new(
+        BufReader::new(file),
+        total_bytes,
+        on_progress,
+    ));
     reader.config_mut().trim_text(true);
 
-    let mut current_tag = Current::None;
-    let mut parent_tag = Parent::None;
     let mut creatures: Vec<ExportedCreature> = Vec::new();
-    let mut temp_creature = ExportedCreature::default();
     let mut entities: Vec<ExportedEntity> = Vec::new();
-    let mut temp_entity = ExportedEntity::default();
-
-    let mut tag_txt = String::new();
-
-    let mut buf = Vec::new();
-
-    // The `Reader` does not implement `Iterator` because it outputs borrowed data (`Cow`s)
-    loop {
-        // NOTE: this is the generic case when we don't know about the input BufRead.
-        // when the input is a &str or a &[u8], we don't actually need to use another
-        // buffer, we could directly call `reader.read_event()`
-        match reader.read_event_into(&mut buf) {
-            Err(e) => {
-                error!(
-                    "parse_legends_export: Error at position {}: {:?}",
-                    reader.buffer_position(),
-                    e
-                );
-                break;
-            }
-            // exits the loop when reaching end of file
-            Ok(Event::Eof) => break,
-
-            Ok(Event::Start(e)) => match e.name().as_ref() {
-                b"creature" => parent_tag = Parent::Creature,
-                b"name_singular" => current_tag = Current::NameSingular,
-                b"name_plural" => current_tag = Current::NamePlural,
-                b"creature_id" | b"id" => current_tag = Current::Identifier,
-                b"race" => current_tag = Current::Race,
-                b"civ_id" => current_tag = Current::CivId,
-                b"type" => current_tag = Current::Type,
-                b"child" => current_tag = Current::Child,
-                b"entity" => parent_tag = Parent::Entity,
-                _ => (),
-            },
-            Ok(Event::Text(e)) => {
-                if parent_tag != Parent::None {
-                    tag_txt = e
-                        .unescape()
-                        .map_or_else(|_| String::new(), std::borrow::Cow::into_owned);
-                }
-                match parent_tag {
-                    Parent::Creature => match current_tag {
-                        Current::NameSingular => temp_creature.set_name_singular(&tag_txt),
-                        Current::NamePlural => temp_creature.set_name_plural(&tag_txt),
-                        Current::Identifier => temp_creature.set_creature_id(&tag_txt),
-                        _ => (),
-                    },
-                    Parent::Entity => match current_tag {
-                        Current::Identifier => {
-                            temp_entity.set_id(tag_txt.parse().unwrap_or_default());
-                        }
-                        Current::Child => {
-                            temp_entity.set_child_id(tag_txt.parse().unwrap_or_default());
-                        }
-                        Current::Race => temp_entity.set_race(&tag_txt),
-                        Current::Type => temp_entity.set_entity_type(&tag_txt),
-                        _ => (),
-                    },
-                    Parent::None => (),
-                }
-            }
+    // The interactions of historical figures, by race (for the generated creatures)
+    let mut figure_interactions: HashMap<String, Vec<String>> = HashMap::new();
 
-            Ok(Event::Empty(e)) => {
-                let tag_name = reader
-                    .decoder()
-                    .decode(e.name().as_ref())
-                    .map_or_else(|_| String::new(), std::borrow::Cow::into_owned);
-                if parent_tag == Parent::Creature {
-                    temp_creature.add_tag(&tag_name);
+    // Only the creatures and entities are kept, every other record is dropped as soon as it's read
+    let read_result = read_items(&mut reader, Encoding::of_export(input_path), |item| {
+        let LegendsItem::Record { section, record } = item else {
+            return;
+        };
+        match (section.as_str(), record.name.as_str()) {
+            ("creature_raw", "creature") => {
+                let mut creature = ExportedCreature::default();
+                creature.update_from_record(&record);
+                if !creature.is_empty() {
+                    creatures.push(creature);
                 }
             }
-            Ok(Event::End(e)) => match e.name().as_ref() {
-                b"creature" => {
-                    parent_tag = Parent::None;
-                    if !temp_creature.is_empty() {
-                        creatures.push(temp_creature);
-                        temp_creature = ExportedCreature::default();
-                    }
-                }
-                b"entity" => {
-                    parent_tag = Parent::None;
-                    entities.push(temp_entity);
-                    temp_entity = ExportedEntity::default();
+            ("entities", "entity") => {
+                let mut entity = ExportedEntity::default();
+                entity.update_from_record(&record);
+                entities.push(entity);
+            }
+            ("historical_figures", "historical_figure") => {
+                if let Some(race) = record.get("race") {
+                    let interactions = figure_interactions.entry(race.to_uppercase()).or_default();
+                    extend_unique(
+                        interactions,
+                        record.get_all("active_interaction").map(String::from),
+                    );
                 }
-                _ => (),
-            },
-            // There are several other `Event`s we do not consider here
+            }
             _ => (),
         }
-        // if we don't keep a borrow elsewhere, we can clear the buffer to keep memory usage low
-        buf.clear();
+    });
+    if let Err(e) = read_result {
+        error!("parse_legends_export: {e}");
+    }
+    reader.into_inner().finish();
+
+    // Generated creatures carry their interactions through the historical figures of their race
+    for creature in creatures
+        .iter_mut()
+        .filter(|creature| creature.is_generated())
+    {
+        if let Some(interactions) =
+            figure_interactions.get(&creature.get_creature_id().to_uppercase())
+        {
+            for interaction in interactions {
+                creature.add_interaction(interaction);
+            }
+        }
     }
 
     let legend_metadata = legends_metadata(input_path.as_ref(), &ObjectType::Creature, options);
@@ -184,3 +153,154 @@ pub fn parse_legends_export<P: AsRef<Path>>(
 
     Ok(results)
 }
+
+/// Parses the legends export file at the specified input path into a `LegendsWorld`, with its
+/// historical figures, sites, artifacts, regions, world constructions and historical events.
+///
+/// Both `legends.xml` and the `legends_plus.xml` written by `DFHack` can be parsed; the latter has
+/// the creatures, entity positions and more details for most records. To merge both exports of a
+/// world, use `parse_legends_world_files`.
+///
+/// # Arguments
+///
+/// * `input_path` - The path to the legends export file.
+///
+/// # Returns
+///
+/// The `LegendsWorld` read from the file.
+///
+/// # Errors
+///
+/// Returns a `ParserError` if there is an issue reading the file, or if the XML is malformed.
+pub fn parse_legends_world<P: AsRef<Path>>(input_path: &P) -> Result<LegendsWorld, ParserError> {
+    parse_legends_world_with_progress(input_path, |_| ())
+}
+
+/// Parses the legends export file at the specified input path into a `LegendsWorld`, reporting
+/// progress as the file is read.
+///
+/// Progress is reported every few megabytes, and once more when the file has been read.
+///
+/// # Arguments
+///
+/// * `input_path` - The path to the legends export file.
+/// * `on_progress` - Called with the number of bytes read so far (and the size of the file).
+///
+/// # Returns
+///
+/// The `LegendsWorld` read from the file.
+///
+/// # Errors
+///
+/// Returns a `ParserError` if there is an issue reading the file, or if the XML is malformed.
+pub fn parse_legends_world_with_progress<P: AsRef<Path>>(
+    input_path: &P,
+    on_progress: impl FnMut(LegendsProgress),
+) -> Result<LegendsWorld, ParserError> {
+    let mut world = LegendsWorld::default();
+    read_world_file(input_path.as_ref(), &mut world, on_progress)?;
+
+    Ok(world)
+}
+
+/// Parses the legends exports of one world (usually `<world>-legends.xml` and
+/// `<world>-legends_plus.xml`) and merges their records by id into a single `LegendsWorld`.
+///
+/// `legends.xml` is read before any `legends_plus.xml`, so where both exports have a field the
+/// more detailed `legends_plus.xml` value is kept. Use `find_world_exports` to find both exports
+/// from the path of either.
+///
+/// # Arguments
+///
+/// * `input_paths` - The paths to the legends export files.
+///
+/// # Returns
+///
+/// The merged `LegendsWorld`.
+///
+/// # Errors
+///
+/// Returns a `ParserError` if there is an issue reading any of the files, or if the XML is
+/// malformed.
+pub fn parse_legends_world_files<P: AsRef<Path>>(
+    input_paths: &[P],
+) -> Result<LegendsWorld, ParserError> {
+    parse_legends_world_files_with_progress(input_paths, |_| ())
+}
+
+/// Parses the legends exports of one world and merges them into a single `LegendsWorld`, reporting
+/// progress as the files are read.
+///
+/// The progress covers all of the files: the bytes read count up through each file in turn, and
+/// the total is the size of all of them.
+///
+/// # Arguments
+///
+/// * `input_paths` - The paths to the legends export files.
+/// * `on_progress` - Called with the number of bytes read so far (and the size of all the files).
+///
+/// # Returns
+///
+/// The merged `LegendsWorld`.
+///
+/// # Errors
+///
+/// Returns a `ParserError` if there is an issue reading any of the files, or if the XML is
+/// malformed.
+pub fn parse_legends_world_files_with_progress<P: AsRef<Path>>(
+    input_paths: &[P],
+    mut on_progress: impl FnMut(LegendsProgress),
+) -> Result<LegendsWorld, ParserError> {
+    let mut input_paths: Vec<&Path> = input_paths.iter().map(AsRef::as_ref).collect();
+    input_paths.sort_by_key(is_legends_plus);
+
+    let file_sizes: Vec<u64> = input_paths
+        .iter()
+        .map(|input_path| std::fs::metadata(input_path).map_or(0, |metadata| metadata.len()))
+        .collect();
+    let total_bytes = file_sizes.iter().sum();
+
+    let mut world = LegendsWorld::default();
+    let mut bytes_before = 0;
+    for (input_path, file_size) in input_paths.into_iter().zip(file_sizes) {
+        read_world_file(input_path, &mut world, |progress: LegendsProgress| {
+            on_progress(LegendsProgress {
+                bytes_read: bytes_before + progress.bytes_read,
+                total_bytes,
+            });
+        })?;
+        bytes_before += file_size;
+    }
+
+    Ok(world)
+}
+
+/// Read the records of a legends export into a `LegendsWorld` (merging them with any records
+/// which are already in it).
+fn read_world_file(
+    input_path: &Path,
+    world: &mut LegendsWorld,
+    on_progress: impl FnMut(LegendsProgress),
+) -> Result<(), ParserError> {
+    let file = try_get_file(&input_path)?;
+    let total_bytes = file.metadata().map_or(0, |metadata| metadata.len());
+
+    info!(
+        "parse_legends_world: Parsing file {}",
+        input_path.file_name().unwrap_or_default().to_string_lossy()
+    );
+
+    let mut reader = Reader::from_reader(ProgressReader::new(
+        BufReader::new(file),
+        total_bytes,
+        on_progress,
+    ));
+    reader.config_mut().trim_text(true);
+
+    read_items(&mut reader, Encoding::of_export(&input_path), |item| {
+        world.add_item(item);
+    })?;
+    reader.into_inner().finish();
+
+    Ok(())
+}
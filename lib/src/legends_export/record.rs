@@ -0,0 +1,221 @@
+//! A generic reader for the records in a legends export.
+//!
+//! Legends exports are a `<df_world>` element holding a few world fields (like `<name>`) and a
+//! list of sections (like `<historical_figures>`), each a list of records (`<historical_figure>`).
+//! Records are mostly flat, with some nested elements (like `<entity_link>`), so each one is read
+//! into an `XmlRecord` which the typed structs are built from.
+//!
+//! Dwarf Fortress writes the text of `legends.xml` in code page 437 (even though its XML
+//! declaration says UTF-8), while `DFHack` writes `legends_plus.xml` in UTF-8, so the encoding is
+//! picked from the name of the export rather than its declaration.
+
+use std::{borrow::Cow, io::BufRead, path::Path};
+
+use quick_xml::{escape::unescape, events::Event, Reader};
+
+use crate::ParserError;
+
+use super::util::{decode_cp437, is_legends_plus};
+
+/// An element of a legends export, with its text fields and nested elements.
+#[derive(Debug, Default, Clone)]
+pub struct XmlRecord {
+    /// The element name (e.g. `historical_figure`)
+    pub name: String,
+    /// The child elements which only hold text (or nothing, for flags like `<deity/>`), in order
+    pub fields: Vec<(String, String)>,
+    /// The child elements which hold other elements, in order
+    pub children: Vec<Self>,
+}
+
+impl XmlRecord {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Self::default()
+        }
+    }
+    /// Get the first field with a name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+    /// Get the first field with a name, as a string.
+    pub fn get_string(&self, name: &str) -> Option<String> {
+        self.get(name).map(String::from)
+    }
+    /// Get the first field with a name, as a number.
+    pub fn get_i32(&self, name: &str) -> Option<i32> {
+        self.get(name).and_then(|value| value.trim().parse().ok())
+    }
+    /// Get every field with a name.
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.fields
+            .iter()
+            .filter(move |(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+    /// Check whether the record has a field (e.g. a flag like `<deity/>`).
+    pub fn has(&self, name: &str) -> bool {
+        self.fields.iter().any(|(key, _)| key == name)
+    }
+    /// Get every nested element with a name.
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Self> + 'a {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+    /// Get the fields of the record and its nested elements, with the fields of nested elements
+    /// named `parent/name`.
+    pub fn flattened_fields(&self) -> Vec<(String, String)> {
+        let mut fields = Vec::new();
+        self.flatten_into("", &mut fields);
+        fields
+    }
+    fn flatten_into(&self, prefix: &str, fields: &mut Vec<(String, String)>) {
+        for (key, value) in &self.fields {
+            fields.push((format!("{prefix}{key}"), value.clone()));
+        }
+        for child in &self.children {
+            child.flatten_into(&format!("{prefix}{}/", child.name), fields);
+        }
+    }
+}
+
+/// Something read from a legends export.
+#[derive(Debug)]
+pub enum LegendsItem {
+    /// A field of the world itself (e.g. `name` or `altname`)
+    WorldField(String, String),
+    /// A record in a section (e.g. a `historical_figure` in `historical_figures`)
+    Record {
+        /// The name of the section
+        section: String,
+        /// The record
+        record: XmlRecord,
+    },
+}
+
+/// The encodings legends exports are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// UTF-8, which `DFHack` writes `legends_plus.xml` in
+    Utf8,
+    /// Code page 437, which Dwarf Fortress writes `legends.xml` in
+    Cp437,
+}
+
+impl Encoding {
+    /// The encoding of a legends export, from its file name. The XML declaration isn't used, since
+    /// `legends.xml` declares UTF-8 but is written in code page 437.
+    pub fn of_export<P: AsRef<Path>>(path: &P) -> Self {
+        if is_legends_plus(path) {
+            Self::Utf8
+        } else {
+            Self::Cp437
+        }
+    }
+    /// Decode text. Text which isn't valid UTF-8 is decoded as code page 437 (so an export which
+    /// was renamed, or edited by another tool, still keeps its names).
+    fn decode(self, bytes: &[u8]) -> Cow<'_, str> {
+        match self {
+            Self::Utf8 => std::str::from_utf8(bytes)
+                .map_or_else(|_| Cow::Owned(decode_cp437(bytes)), Cow::Borrowed),
+            Self::Cp437 => Cow::Owned(decode_cp437(bytes)),
+        }
+    }
+}
+
+/// Read a legends export, calling `on_item` for each world field and record as it is read.
+///
+/// # Arguments
+///
+/// * `reader` - The XML reader for the export.
+/// * `encoding` - The encoding of the text in the export (see `Encoding::of_export`).
+/// * `on_item` - Called with each world field and record.
+///
+/// # Errors
+///
+/// * `ParserError::InvalidLegendsExport` - If the XML is malformed.
+pub fn read_items<R: BufRead>(
+    reader: &mut Reader<R>,
+    encoding: Encoding,
+    mut on_item: impl FnMut(LegendsItem),
+) -> Result<(), ParserError> {
+    // The open elements: `df_world`, then the section, then the record and its nested elements
+    let mut stack: Vec<XmlRecord> = Vec::new();
+    let mut text = String::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(e) => {
+                return Err(ParserError::InvalidLegendsExport(format!(
+                    "error at position {}: {e}",
+                    reader.buffer_position()
+                )));
+            }
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => {
+                stack.push(XmlRecord::new(decode(e.name().as_ref()).into_owned()));
+                text.clear();
+            }
+            Ok(Event::Text(e)) => {
+                let raw = encoding.decode(e.as_ref());
+                text = unescape(&raw).map_or_else(|_| raw.to_string(), Cow::into_owned);
+            }
+            Ok(Event::Empty(e)) => {
+                if let Some(parent) = stack.last_mut() {
+                    parent
+                        .fields
+                        .push((decode(e.name().as_ref()).into_owned(), String::new()));
+                }
+            }
+            Ok(Event::End(_)) => {
+                let Some(element) = stack.pop() else {
+                    continue;
+                };
+                let is_leaf = element.fields.is_empty() && element.children.is_empty();
+                match stack.len() {
+                    // The end of `df_world`
+                    0 => (),
+                    // A section, or a field of the world
+                    1 => {
+                        if is_leaf && !text.is_empty() {
+                            on_item(LegendsItem::WorldField(element.name, text.clone()));
+                        }
+                    }
+                    // A record in a section
+                    2 => {
+                        if let Some(section) = stack.last() {
+                            on_item(LegendsItem::Record {
+                                section: section.name.clone(),
+                                record: element,
+                            });
+                        }
+                    }
+                    // A field or nested element of a record
+                    _ => {
+                        if let Some(parent) = stack.last_mut() {
+                            if is_leaf {
+                                parent.fields.push((element.name, text.clone()));
+                            } else {
+                                parent.children.push(element);
+                            }
+                        }
+                    }
+                }
+                text.clear();
+            }
+            _ => (),
+        }
+        buf.clear();
+    }
+
+    Ok(())
+}
+
+/// Decode an element name (these are always ASCII).
+fn decode(bytes: &[u8]) -> Cow<'_, str> {
+    String::from_utf8_lossy(bytes)
+}
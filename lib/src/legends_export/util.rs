@@ -1,6 +1,7 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::{
+    constants::CP437_TO_UNICODE,
     metadata::{ObjectType, ParserOptions, RawMetadata, RawModuleLocation},
     InfoFile,
 };
@@ -26,3 +27,124 @@ pub fn legends_metadata(
         options.attach_metadata_to_raws,
     )
 }
+
+/// Parse a coordinate pair like `12,34`.
+pub fn parse_coords(value: &str) -> Option<[i32; 2]> {
+    let (x, y) = value.split_once(',')?;
+    Some([x.trim().parse().ok()?, y.trim().parse().ok()?])
+}
+
+/// Parse a list of coordinate pairs like `1,2|3,4|`.
+pub fn parse_coords_list(value: &str) -> Vec<[i32; 2]> {
+    value.split('|').filter_map(parse_coords).collect()
+}
+
+/// Parse a rectangle like `1,2:3,4` into its two corners.
+pub fn parse_rectangle(value: &str) -> Option<[[i32; 2]; 2]> {
+    let (from, to) = value.split_once(':')?;
+    Some([parse_coords(from)?, parse_coords(to)?])
+}
+
+/// Add the items which aren't already in a list (used when merging records from two exports).
+pub fn extend_unique<T: PartialEq>(list: &mut Vec<T>, items: impl IntoIterator<Item = T>) {
+    for item in items {
+        if !list.contains(&item) {
+            list.push(item);
+        }
+    }
+}
+
+/// Add the items which aren't already in a list, where `same` decides whether two items are the
+/// same record (e.g. a link to the same entity, with or without a link strength).
+pub fn extend_unique_by<T>(
+    list: &mut Vec<T>,
+    items: impl IntoIterator<Item = T>,
+    same: impl Fn(&T, &T) -> bool,
+) {
+    for item in items {
+        if !list.iter().any(|existing| same(existing, &item)) {
+            list.push(item);
+        }
+    }
+}
+
+/// The prefixes of the identifiers the game gives to the creatures it generates.
+///
+/// `legends_plus.xml` marks generated creatures with a `generated` flag, but `legends.xml` doesn't,
+/// so these are used as well.
+const GENERATED_CREATURE_PREFIXES: [&str; 5] = [
+    "FORGOTTEN_BEAST_",
+    "TITAN_",
+    "DEMON_",
+    "NIGHT_CREATURE_",
+    "DIVINE_",
+];
+
+/// Whether a creature identifier is one the game gives to the creatures it generates (ignoring
+/// case, since entity races are lower case).
+pub fn is_generated_creature_id(identifier: &str) -> bool {
+    GENERATED_CREATURE_PREFIXES.iter().any(|prefix| {
+        identifier
+            .get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+    })
+}
+
+/// Decode text in code page 437, the encoding Dwarf Fortress writes `legends.xml` in.
+///
+/// ASCII is kept as it is (so new lines aren't turned into tileset glyphs).
+pub fn decode_cp437(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&byte| {
+            if byte.is_ascii() {
+                char::from(byte)
+            } else {
+                CP437_TO_UNICODE[usize::from(byte)]
+            }
+        })
+        .collect()
+}
+
+/// Find the legends exports of the same world as a legends export: Dwarf Fortress writes
+/// `<world>-legends.xml`, and `DFHack` writes `<world>-legends_plus.xml` next to it.
+///
+/// # Arguments
+///
+/// * `path` - The path to either of the legends exports.
+///
+/// # Returns
+///
+/// The paths of the exports which exist, with `legends.xml` first. If `path` isn't named like a
+/// legends export, it is returned on its own.
+pub fn find_world_exports<P: AsRef<Path>>(path: &P) -> Vec<PathBuf> {
+    let path = path.as_ref();
+    let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+        return vec![path.to_path_buf()];
+    };
+    let Some(prefix) = file_name
+        .strip_suffix("-legends_plus.xml")
+        .or_else(|| file_name.strip_suffix("-legends.xml"))
+    else {
+        return vec![path.to_path_buf()];
+    };
+
+    let exports: Vec<PathBuf> = ["legends", "legends_plus"]
+        .iter()
+        .map(|suffix| path.with_file_name(format!("{prefix}-{suffix}.xml")))
+        .filter(|export| export.is_file())
+        .collect();
+    if exports.is_empty() {
+        vec![path.to_path_buf()]
+    } else {
+        exports
+    }
+}
+
+/// Whether a legends export was written by `DFHack` (`legends_plus.xml`).
+pub fn is_legends_plus<P: AsRef<Path>>(path: &P) -> bool {
+    path.as_ref()
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with("legends_plus.xml"))
+}
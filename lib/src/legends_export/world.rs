@@ -0,0 +1,407 @@
+//! The `LegendsWorld` struct, everything read from the legends exports of a world.
+
+use super::{
+    exports::{
+        Artifact, ExportedCreature, ExportedEntity, ExportedEntityPopulation, HistoricalEvent,
+        HistoricalFigure, Region, Site, WorldConstruction,
+    },
+    record::{LegendsItem, XmlRecord},
+};
+
+/// The history of a world, as read from its legends exports (`legends.xml` and, if it was
+/// exported with `DFHack`, `legends_plus.xml`).
+///
+/// Records are kept in the order of their ids, and a record which appears in more than one export
+/// is merged into one.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LegendsWorld {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// The name of the world in its own language
+    #[serde(skip_serializing_if = "Option::is_none")]
+    alt_name: Option<String>,
+    creatures: Vec<ExportedCreature>,
+    entities: Vec<ExportedEntity>,
+    entity_populations: Vec<ExportedEntityPopulation>,
+    historical_figures: Vec<HistoricalFigure>,
+    sites: Vec<Site>,
+    artifacts: Vec<Artifact>,
+    regions: Vec<Region>,
+    underground_regions: Vec<Region>,
+    world_constructions: Vec<WorldConstruction>,
+    historical_events: Vec<HistoricalEvent>,
+}
+
+impl LegendsWorld {
+    /// Add something read from a legends export to the world.
+    ///
+    /// Records in sections which aren't supported (like `historical_eras`) are ignored.
+    pub(crate) fn add_item(&mut self, item: LegendsItem) {
+        match item {
+            LegendsItem::WorldField(name, value) => match name.as_str() {
+                "name" => self.name = Some(value),
+                "altname" => self.alt_name = Some(value),
+                _ => (),
+            },
+            LegendsItem::Record { section, record } => self.add_record(&section, &record),
+        }
+    }
+    fn add_record(&mut self, section: &str, record: &XmlRecord) {
+        match section {
+            "creature_raw" => {
+                let Some(creature_id) = record.get("creature_id") else {
+                    return;
+                };
+                if let Some(creature) = self
+                    .creatures
+                    .iter_mut()
+                    .find(|creature| creature.get_creature_id() == creature_id)
+                {
+                    creature.update_from_record(record);
+                } else {
+                    let mut creature = ExportedCreature::default();
+                    creature.update_from_record(record);
+                    self.creatures.push(creature);
+                }
+            }
+            "entities" => {
+                let Some(id) = record.get("id").and_then(|id| id.trim().parse().ok()) else {
+                    return;
+                };
+                merge_record(
+                    &mut self.entities,
+                    &id,
+                    ExportedEntity::get_id,
+                    ExportedEntity::update_from_record,
+                    record,
+                );
+            }
+            "entity_populations" => merge_by_id(
+                &mut self.entity_populations,
+                ExportedEntityPopulation::get_id,
+                ExportedEntityPopulation::update_from_record,
+                record,
+            ),
+            "historical_figures" => merge_by_id(
+                &mut self.historical_figures,
+                HistoricalFigure::get_id,
+                HistoricalFigure::update_from_record,
+                record,
+            ),
+            "sites" => merge_by_id(
+                &mut self.sites,
+                Site::get_id,
+                Site::update_from_record,
+                record,
+            ),
+            "artifacts" => merge_by_id(
+                &mut self.artifacts,
+                Artifact::get_id,
+                Artifact::update_from_record,
+                record,
+            ),
+            "regions" => merge_by_id(
+                &mut self.regions,
+                Region::get_id,
+                Region::update_from_record,
+                record,
+            ),
+            "underground_regions" => merge_by_id(
+                &mut self.underground_regions,
+                Region::get_id,
+                Region::update_from_record,
+                record,
+            ),
+            "world_constructions" => merge_by_id(
+                &mut self.world_constructions,
+                WorldConstruction::get_id,
+                WorldConstruction::update_from_record,
+                record,
+            ),
+            "historical_events" => merge_by_id(
+                &mut self.historical_events,
+                HistoricalEvent::get_id,
+                HistoricalEvent::update_from_record,
+                record,
+            ),
+            _ => (),
+        }
+    }
+    /// Get the name of the world.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The name, if known.
+    #[must_use]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// Get the name of the world in its own language.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The name, if known.
+    #[must_use]
+    pub fn get_alt_name(&self) -> Option<&str> {
+        self.alt_name.as_deref()
+    }
+    /// Get the creatures in the world (only in `legends_plus.xml`).
+    ///
+    /// # Returns
+    ///
+    /// * `&[ExportedCreature]` - The creatures.
+    #[must_use]
+    pub fn get_creatures(&self) -> &[ExportedCreature] {
+        &self.creatures
+    }
+    /// Get the entities (civilizations, site governments, religions, ...) in the world.
+    ///
+    /// # Returns
+    ///
+    /// * `&[ExportedEntity]` - The entities.
+    #[must_use]
+    pub fn get_entities(&self) -> &[ExportedEntity] {
+        &self.entities
+    }
+    /// Get the entity populations in the world.
+    ///
+    /// # Returns
+    ///
+    /// * `&[ExportedEntityPopulation]` - The entity populations.
+    #[must_use]
+    pub fn get_entity_populations(&self) -> &[ExportedEntityPopulation] {
+        &self.entity_populations
+    }
+    /// Get the historical figures in the world.
+    ///
+    /// # Returns
+    ///
+    /// * `&[HistoricalFigure]` - The historical figures.
+    #[must_use]
+    pub fn get_historical_figures(&self) -> &[HistoricalFigure] {
+        &self.historical_figures
+    }
+    /// Get the sites in the world.
+    ///
+    /// # Returns
+    ///
+    /// * `&[Site]` - The sites.
+    #[must_use]
+    pub fn get_sites(&self) -> &[Site] {
+        &self.sites
+    }
+    /// Get the artifacts in the world.
+    ///
+    /// # Returns
+    ///
+    /// * `&[Artifact]` - The artifacts.
+    #[must_use]
+    pub fn get_artifacts(&self) -> &[Artifact] {
+        &self.artifacts
+    }
+    /// Get the surface regions of the world.
+    ///
+    /// # Returns
+    ///
+    /// * `&[Region]` - The regions.
+    #[must_use]
+    pub fn get_regions(&self) -> &[Region] {
+        &self.regions
+    }
+    /// Get the underground regions of the world.
+    ///
+    /// # Returns
+    ///
+    /// * `&[Region]` - The underground regions.
+    #[must_use]
+    pub fn get_underground_regions(&self) -> &[Region] {
+        &self.underground_regions
+    }
+    /// Get the world constructions (roads, bridges and tunnels) in the world.
+    ///
+    /// # Returns
+    ///
+    /// * `&[WorldConstruction]` - The world constructions.
+    #[must_use]
+    pub fn get_world_constructions(&self) -> &[WorldConstruction] {
+        &self.world_constructions
+    }
+    /// Get the historical events of the world.
+    ///
+    /// # Returns
+    ///
+    /// * `&[HistoricalEvent]` - The historical events, in order.
+    #[must_use]
+    pub fn get_historical_events(&self) -> &[HistoricalEvent] {
+        &self.historical_events
+    }
+    /// Get a creature by its identifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `creature_id` - The creature identifier (e.g. `DWARF`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&ExportedCreature>` - The creature, if it's in the world.
+    #[must_use]
+    pub fn get_creature(&self, creature_id: &str) -> Option<&ExportedCreature> {
+        self.creatures
+            .iter()
+            .find(|creature| creature.get_creature_id().eq_ignore_ascii_case(creature_id))
+    }
+    /// Get an entity by its id.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id of the entity.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&ExportedEntity>` - The entity, if it exists.
+    #[must_use]
+    pub fn get_entity(&self, id: u32) -> Option<&ExportedEntity> {
+        find_by_id(&self.entities, &id, ExportedEntity::get_id)
+    }
+    /// Get a historical figure by its id.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id of the historical figure.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&HistoricalFigure>` - The historical figure, if it exists.
+    #[must_use]
+    pub fn get_historical_figure(&self, id: i32) -> Option<&HistoricalFigure> {
+        find_by_id(&self.historical_figures, &id, HistoricalFigure::get_id)
+    }
+    /// Get a site by its id.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id of the site.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&Site>` - The site, if it exists.
+    #[must_use]
+    pub fn get_site(&self, id: i32) -> Option<&Site> {
+        find_by_id(&self.sites, &id, Site::get_id)
+    }
+    /// Get an artifact by its id.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id of the artifact.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&Artifact>` - The artifact, if it exists.
+    #[must_use]
+    pub fn get_artifact(&self, id: i32) -> Option<&Artifact> {
+        find_by_id(&self.artifacts, &id, Artifact::get_id)
+    }
+    /// Get a historical event by its id.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id of the historical event.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&HistoricalEvent>` - The historical event, if it exists.
+    #[must_use]
+    pub fn get_historical_event(&self, id: i32) -> Option<&HistoricalEvent> {
+        find_by_id(&self.historical_events, &id, HistoricalEvent::get_id)
+    }
+    /// Get the creatures the world generated (forgotten beasts, titans, night creatures, ...), with
+    /// the interactions of the historical figures of their race added to them.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<ExportedCreature>` - The generated creatures.
+    #[must_use]
+    pub fn get_generated_creatures(&self) -> Vec<ExportedCreature> {
+        self.creatures
+            .iter()
+            .filter(|creature| creature.is_generated())
+            .map(|creature| {
+                let mut creature = creature.clone();
+                for figure in &self.historical_figures {
+                    if figure
+                        .get_race()
+                        .is_some_and(|race| race.eq_ignore_ascii_case(creature.get_creature_id()))
+                    {
+                        for interaction in figure.get_active_interactions() {
+                            creature.add_interaction(interaction);
+                        }
+                    }
+                }
+                creature
+            })
+            .collect()
+    }
+    /// Get the historical events which mention a historical figure (in any of their `hfid`
+    /// fields, e.g. `slayer_hfid`).
+    ///
+    /// # Arguments
+    ///
+    /// * `figure_id` - The id of the historical figure.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&HistoricalEvent>` - The historical events, in order.
+    #[must_use]
+    pub fn get_events_for_figure(&self, figure_id: i32) -> Vec<&HistoricalEvent> {
+        let figure_id = figure_id.to_string();
+        self.historical_events
+            .iter()
+            .filter(|event| {
+                event.get_fields().iter().any(|(key, value)| {
+                    (key.ends_with("hfid") || key.ends_with("hf_id")) && *value == figure_id
+                })
+            })
+            .collect()
+    }
+}
+
+/// Find a record in a list which is sorted by id.
+fn find_by_id<'a, T, K: Ord>(list: &'a [T], id: &K, get_id: impl Fn(&T) -> K) -> Option<&'a T> {
+    list.binary_search_by(|item| get_id(item).cmp(id))
+        .ok()
+        .map(|index| &list[index])
+}
+
+/// Merge a record into the record with the same id, or add it to the list (keeping it sorted).
+///
+/// Records are in id order in the exports, so new records are almost always added at the end.
+fn merge_record<T: Default, K: Ord>(
+    list: &mut Vec<T>,
+    id: &K,
+    get_id: impl Fn(&T) -> K,
+    update: impl Fn(&mut T, &XmlRecord),
+    record: &XmlRecord,
+) {
+    match list.binary_search_by(|item| get_id(item).cmp(id)) {
+        Ok(index) => update(&mut list[index], record),
+        Err(index) => {
+            let mut item = T::default();
+            update(&mut item, record);
+            list.insert(index, item);
+        }
+    }
+}
+
+/// Merge a record with an `<id>` field into a list (records without an id are skipped).
+fn merge_by_id<T: Default>(
+    list: &mut Vec<T>,
+    get_id: impl Fn(&T) -> i32,
+    update: impl Fn(&mut T, &XmlRecord),
+    record: &XmlRecord,
+) {
+    if let Some(id) = record.get_i32("id") {
+        merge_record(list, &id, get_id, update, record);
+    }
+}
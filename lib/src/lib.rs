@@ -6,22 +6,35 @@ mod parsed_definitions;
 mod parser;
 mod reader;
 
+pub mod cache;
 pub mod constants;
+pub mod graphics_index;
+pub mod layer_conditions;
 pub mod legends_export;
 pub mod metadata;
+pub mod query;
+pub mod raw_collection;
 pub mod raw_definitions;
 pub mod regex;
+#[cfg(feature = "render")]
+pub mod render;
+pub mod search_index;
+pub mod syndrome_timeline;
+pub mod tokenizer;
 pub mod traits;
 pub mod utilities;
+pub mod validation;
 
 pub use error::Parser as ParserError;
 pub use parsed_definitions::*;
 pub use parser::parse::parse;
+pub use parser::parse_iter;
 pub use parser::parse_location;
 pub use parser::parse_module;
 pub use parser::parse_module_info_file_in_module;
 pub use parser::parse_module_info_files;
 pub use parser::parse_module_info_files_at_location;
+pub use parser::ParseIter;
 pub use parser::ParseResult;
 pub use reader::parse_raw_file;
 pub use reader::FileParseResult;
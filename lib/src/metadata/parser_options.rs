@@ -121,6 +121,14 @@ pub struct ParserOptions {
     ///
     /// Default: false
     pub log_summary: bool,
+    /// Optionally specify a directory to cache the results of parsing each raw file in.
+    ///
+    /// When set, the parse results for each raw file are saved in this directory, and re-used on
+    /// the next parse if the file hasn't changed (same size and modified time, or same contents).
+    /// Only changed files are read again. The creature resolution steps always run again.
+    ///
+    /// Default: None
+    pub cache_directory: Option<PathBuf>,
 }
 
 impl Default for ParserOptions {
@@ -146,6 +154,7 @@ impl Default for ParserOptions {
             raw_files_to_parse: Vec::new(),
             raw_modules_to_parse: Vec::new(),
             module_info_files_to_parse: Vec::new(),
+            cache_directory: None,
         }
     }
 }
@@ -359,6 +368,16 @@ impl ParserOptions {
         self.log_summary = true;
     }
 
+    /// Cache the results of parsing each raw file in the given directory, so that unchanged raw
+    /// files don't need to be parsed again the next time.
+    ///
+    /// The directory is created if it doesn't exist. See `ParseCache` for details.
+    ///
+    /// Default: None
+    pub fn set_cache_directory<P: AsRef<Path>>(&mut self, cache_directory: &P) {
+        self.cache_directory = Some(cache_directory.as_ref().to_path_buf());
+    }
+
     /// Add a location to parse raws from.
     ///
     /// * `RawModuleLocation::Vanilla` will parse the vanilla raws.
@@ -193,6 +193,14 @@ impl Metadata {
         self.hidden = hidden;
         self
     }
+    /// Set the `hidden` status of the metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `hidden` - The hidden status to set
+    pub const fn set_hidden(&mut self, hidden: bool) {
+        self.hidden = hidden;
+    }
     /// Set the `raw_identifier` of the metadata at creation.
     ///
     /// # Arguments
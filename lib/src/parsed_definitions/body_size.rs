@@ -13,6 +13,21 @@ pub struct BodySize {
 }
 
 impl BodySize {
+    /// The age (in years) the size is reached at.
+    #[must_use]
+    pub const fn get_years(&self) -> u32 {
+        self.years
+    }
+    /// The age (in days, after `years`) the size is reached at.
+    #[must_use]
+    pub const fn get_days(&self) -> u32 {
+        self.days
+    }
+    /// The size (in cubic centimeters).
+    #[must_use]
+    pub const fn get_size_cm3(&self) -> u32 {
+        self.size_cm3
+    }
     /// Creates a new `BodySize` struct with the given years, days, and `size_cm3`
     ///
     /// # Arguments
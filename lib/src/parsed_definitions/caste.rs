@@ -101,6 +101,15 @@ impl Caste {
     pub fn get_tags(&self) -> &[CasteTag] {
         self.tags.as_ref().map_or(&[], |tags| tags.as_slice())
     }
+    /// Function to get the tile (character and color) of the creature caste.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&Tile>` - The tile of the creature caste, if it has one.
+    #[must_use]
+    pub const fn get_tile(&self) -> Option<&Tile> {
+        self.tile.as_ref()
+    }
     /// Function to get the milkable of the creature caste.
     ///
     /// # Returns
@@ -254,6 +263,159 @@ impl Caste {
     pub fn get_identifier(&self) -> &str {
         &self.identifier
     }
+    /// Function to get the name of the caste.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&Name>` - The name of the caste, if it has one.
+    #[must_use]
+    pub const fn get_caste_name(&self) -> Option<&Name> {
+        self.caste_name.as_ref()
+    }
+    /// Function to get the description of the caste.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The description of the caste, if it has one.
+    #[must_use]
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    /// Function to get the body sizes of the caste, in the order they are reached as it grows.
+    ///
+    /// # Returns
+    ///
+    /// * `&[BodySize]` - The body sizes of the caste.
+    #[must_use]
+    pub fn get_body_sizes(&self) -> &[BodySize] {
+        self.body_size.as_deref().unwrap_or_default()
+    }
+    /// Function to get the creature classes of the caste (`CREATURE_CLASS`).
+    ///
+    /// # Returns
+    ///
+    /// * `&[String]` - The creature classes of the caste.
+    #[must_use]
+    pub fn get_creature_classes(&self) -> &[String] {
+        self.creature_class.as_deref().unwrap_or_default()
+    }
+    /// Function to get the value of the caste as a pet (`PET_VALUE`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The pet value, if the caste has one.
+    #[must_use]
+    pub const fn get_pet_value(&self) -> Option<u32> {
+        self.pet_value
+    }
+    /// Function to get the difficulty of the caste (`DIFFICULTY`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The difficulty, if the caste has one.
+    #[must_use]
+    pub const fn get_difficulty(&self) -> Option<u32> {
+        self.difficulty
+    }
+    /// Function to get the population ratio of the caste (`POP_RATIO`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The population ratio, if the caste has one.
+    #[must_use]
+    pub const fn get_pop_ratio(&self) -> Option<u32> {
+        self.pop_ratio
+    }
+    /// Function to get the range of ages the caste dies of old age at (`MAX_AGE`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<[u32; 2]>` - The minimum and maximum age, if the caste has them.
+    #[must_use]
+    pub const fn get_max_age(&self) -> Option<[u32; 2]> {
+        self.max_age
+    }
+    /// Function to get the number of eggs the caste lays at once (`CLUTCH_SIZE`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<[u32; 2]>` - The minimum and maximum clutch size, if the caste has them.
+    #[must_use]
+    pub const fn get_clutch_size(&self) -> Option<[u32; 2]> {
+        self.clutch_size
+    }
+    /// Function to get the number of young the caste gives birth to at once (`LITTER_SIZE`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<[u32; 2]>` - The minimum and maximum litter size, if the caste has them.
+    #[must_use]
+    pub const fn get_litter_size(&self) -> Option<[u32; 2]> {
+        self.litter_size
+    }
+    /// Function to get the age the caste stops being a baby at (`BABY`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The age, if the caste has babies.
+    #[must_use]
+    pub const fn get_baby(&self) -> Option<u32> {
+        self.baby
+    }
+    /// Function to get the age the caste stops being a child at (`CHILD`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The age, if the caste has children.
+    #[must_use]
+    pub const fn get_child(&self) -> Option<u32> {
+        self.child
+    }
+    /// Function to get the size of the eggs the caste lays (`EGG_SIZE`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The egg size, if the caste has one.
+    #[must_use]
+    pub const fn get_egg_size(&self) -> Option<u32> {
+        self.egg_size
+    }
+    /// Function to get how much the caste tramples grass (`GRASSTRAMPLE`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The amount, if the caste has one.
+    #[must_use]
+    pub const fn get_grass_trample(&self) -> Option<u32> {
+        self.grass_trample
+    }
+    /// Function to get how much the caste needs to graze (`GRAZER`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The amount, if the caste grazes.
+    #[must_use]
+    pub const fn get_grazer(&self) -> Option<u32> {
+        self.grazer
+    }
+    /// Function to get how well the caste sees in the dark (`LOW_LIGHT_VISION`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The vision, if the caste has one.
+    #[must_use]
+    pub const fn get_low_light_vision(&self) -> Option<u32> {
+        self.low_light_vision
+    }
+    /// Function to get the percentage the body size of the caste is changed by (`CHANGE_BODY_SIZE_PERC`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The percentage, if the caste has one.
+    #[must_use]
+    pub const fn get_change_body_size_percentage(&self) -> Option<u32> {
+        self.change_body_size_percentage
+    }
     /// Function to remove a tag from the creature.
     ///
     /// # Arguments
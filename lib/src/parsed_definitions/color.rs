@@ -1,16 +1,119 @@
 //! A module containing the `Color` struct and its implementations.
 
+use crate::color_palette::ColorPalette;
+
 /// A struct representing a color in the format "foreground:background:brightness".
+///
+/// The RGB colors are worked out whenever a color is made (including when it is deserialized), so
+/// they always match the color indices.
 #[allow(clippy::module_name_repetitions)]
-#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, specta::Type)]
-#[serde(rename_all = "camelCase")]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, specta::Type)]
+#[serde(rename_all = "camelCase", from = "ColorParts")]
 pub struct Color {
     foreground: u8,
     background: u8,
     brightness: u8,
+    /// The RGB foreground color with the game's default colors (see `get_foreground_rgb` for
+    /// other palettes)
+    foreground_rgb: [u8; 3],
+    /// The RGB background color with the game's default colors (see `get_background_rgb` for
+    /// other palettes)
+    background_rgb: [u8; 3],
+}
+
+/// The parts of a serialized `Color` which are read back (the RGB colors are worked out again).
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ColorParts {
+    foreground: u8,
+    background: u8,
+    brightness: u8,
+}
+
+impl From<ColorParts> for Color {
+    fn from(parts: ColorParts) -> Self {
+        Self::new(parts.foreground, parts.background, parts.brightness)
+    }
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Self::new(0, 0, 0)
+    }
 }
 
 impl Color {
+    /// Create a color from its parts.
+    ///
+    /// # Arguments
+    ///
+    /// * `foreground` - The foreground color index (0-7).
+    /// * `background` - The background color index (0-7).
+    /// * `brightness` - Whether the foreground is bright (0 or 1).
+    #[must_use]
+    pub const fn new(foreground: u8, background: u8, brightness: u8) -> Self {
+        let mut color = Self {
+            foreground,
+            background,
+            brightness,
+            foreground_rgb: [0; 3],
+            background_rgb: [0; 3],
+        };
+        color.foreground_rgb = color.get_foreground_rgb(&ColorPalette::DEFAULT);
+        color.background_rgb = color.get_background_rgb(&ColorPalette::DEFAULT);
+        color
+    }
+    /// The foreground color index (0-7).
+    #[must_use]
+    pub const fn get_foreground(&self) -> u8 {
+        self.foreground
+    }
+    /// The background color index (0-7).
+    #[must_use]
+    pub const fn get_background(&self) -> u8 {
+        self.background
+    }
+    /// Whether the foreground is bright (0 or 1).
+    #[must_use]
+    pub const fn get_brightness(&self) -> u8 {
+        self.brightness
+    }
+    /// The RGB foreground color, using a palette.
+    ///
+    /// # Arguments
+    ///
+    /// * `palette` - The colors to pick from (e.g. `ColorPalette::default()` or a loaded `colors.txt`).
+    ///
+    /// # Returns
+    ///
+    /// * `[u8; 3]` - The RGB foreground color.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dfraw_parser::{Color, ColorPalette};
+    ///
+    /// let color = Color::from_value("7:0:1");
+    /// assert_eq!(color.get_foreground_rgb(&ColorPalette::default()), [255, 255, 255]);
+    /// assert_eq!(color.get_background_rgb(&ColorPalette::default()), [0, 0, 0]);
+    /// ```
+    #[must_use]
+    pub const fn get_foreground_rgb(&self, palette: &ColorPalette) -> [u8; 3] {
+        palette.get(ColorPalette::foreground_index(self))
+    }
+    /// The RGB background color, using a palette.
+    ///
+    /// # Arguments
+    ///
+    /// * `palette` - The colors to pick from (e.g. `ColorPalette::default()` or a loaded `colors.txt`).
+    ///
+    /// # Returns
+    ///
+    /// * `[u8; 3]` - The RGB background color.
+    #[must_use]
+    pub const fn get_background_rgb(&self, palette: &ColorPalette) -> [u8; 3] {
+        palette.get(ColorPalette::background_index(self))
+    }
     /// The function `from_value` takes a string value and splits it into three parts to create a
     /// `Color` struct, or returns a default `Color` if the string does not have three parts.
     ///
@@ -25,11 +128,11 @@ impl Color {
     pub fn from_value(value: &str) -> Self {
         let split = value.split(':').collect::<Vec<&str>>();
         if split.len() == 3 {
-            return Self {
-                foreground: split.first().unwrap_or(&"").parse::<u8>().unwrap_or(0),
-                background: split.get(1).unwrap_or(&"").parse::<u8>().unwrap_or(0),
-                brightness: split.get(2).unwrap_or(&"").parse::<u8>().unwrap_or(0),
-            };
+            return Self::new(
+                split.first().unwrap_or(&"").parse::<u8>().unwrap_or(0),
+                split.get(1).unwrap_or(&"").parse::<u8>().unwrap_or(0),
+                split.get(2).unwrap_or(&"").parse::<u8>().unwrap_or(0),
+            );
         }
         Self::default()
     }
@@ -0,0 +1,172 @@
+//! The 16 colors Dwarf Fortress draws tiles with.
+
+use std::path::Path;
+
+use tracing::warn;
+
+use crate::{color::Color, constants::DF_ENCODING, tokenizer, ParserError};
+
+/// The 16 colors (as RGB) used to draw tiles, in the game's order: black, blue, green, cyan, red,
+/// magenta, brown, light gray, then the bright versions (dark gray, light blue, ... white).
+///
+/// A `Color`'s foreground index plus 8 if it is bright picks the foreground color, and its background
+/// index picks the background color. The default is the game's default `colors.txt`.
+#[allow(clippy::module_name_repetitions)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorPalette {
+    colors: [[u8; 3]; 16],
+}
+
+impl Default for ColorPalette {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl ColorPalette {
+    /// The game's default colors (from `colors.txt`).
+    pub const DEFAULT: Self = Self {
+        colors: [
+            [0, 0, 0],
+            [0, 0, 128],
+            [0, 128, 0],
+            [0, 128, 128],
+            [128, 0, 0],
+            [128, 0, 128],
+            [128, 128, 0],
+            [192, 192, 192],
+            [128, 128, 128],
+            [0, 0, 255],
+            [0, 255, 0],
+            [0, 255, 255],
+            [255, 0, 0],
+            [255, 0, 255],
+            [255, 255, 0],
+            [255, 255, 255],
+        ],
+    };
+
+    /// The names of the colors in `colors.txt`, in the game's order.
+    pub const COLOR_NAMES: [&'static str; 16] = [
+        "BLACK", "BLUE", "GREEN", "CYAN", "RED", "MAGENTA", "BROWN", "LGRAY", "DGRAY", "LBLUE",
+        "LGREEN", "LCYAN", "LRED", "LMAGENTA", "YELLOW", "WHITE",
+    ];
+
+    /// Create a palette from 16 RGB colors, in the game's order.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors` - The RGB colors.
+    #[must_use]
+    pub const fn new(colors: [[u8; 3]; 16]) -> Self {
+        Self { colors }
+    }
+    /// Get one of the 16 colors.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the color (0-15). Indices past the end wrap around.
+    ///
+    /// # Returns
+    ///
+    /// * `[u8; 3]` - The RGB color.
+    #[must_use]
+    pub const fn get(&self, index: u8) -> [u8; 3] {
+        self.colors[(index % 16) as usize]
+    }
+    /// The palette index of a color's foreground: its foreground index, plus 8 if it is bright.
+    #[must_use]
+    pub const fn foreground_index(color: &Color) -> u8 {
+        (color.get_foreground() % 8) + if color.get_brightness() > 0 { 8 } else { 0 }
+    }
+    /// The palette index of a color's background.
+    #[must_use]
+    pub const fn background_index(color: &Color) -> u8 {
+        color.get_background() % 8
+    }
+    /// Parse the text of a `colors.txt` file (tokens like `[BLACK_R:0]`).
+    ///
+    /// Colors missing from the text keep their default values, and unknown tokens are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The contents of a `colors.txt` file.
+    ///
+    /// # Returns
+    ///
+    /// * `ColorPalette` - The palette described by the text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dfraw_parser::ColorPalette;
+    ///
+    /// let palette = ColorPalette::parse_colors_txt("[WHITE_R:250][WHITE_G:245][WHITE_B:240]");
+    /// assert_eq!(palette.get(15), [250, 245, 240]);
+    /// assert_eq!(palette.get(0), [0, 0, 0]);
+    /// ```
+    #[must_use]
+    pub fn parse_colors_txt(text: &str) -> Self {
+        let mut palette = Self::DEFAULT;
+        for node in tokenizer::tokenize(text) {
+            let tokenizer::Node::Token(token) = node else {
+                continue;
+            };
+            let Some((name, channel)) = token.key.rsplit_once('_') else {
+                continue;
+            };
+            let Some(index) = Self::COLOR_NAMES.iter().position(|n| *n == name) else {
+                continue;
+            };
+            let channel = match channel {
+                "R" => 0,
+                "G" => 1,
+                "B" => 2,
+                _ => continue,
+            };
+            if let Some(Ok(value)) = token.args.first().map(|value| value.parse::<u8>()) {
+                palette.colors[index][channel] = value;
+            } else {
+                warn!("parse_colors_txt: invalid value for {}", token.key);
+            }
+        }
+        palette
+    }
+    /// Load a `colors.txt` file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the `colors.txt` file.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Io` - If the file can't be read.
+    pub fn from_colors_txt<P: AsRef<Path>>(path: &P) -> Result<Self, ParserError> {
+        let bytes = std::fs::read(path)?;
+        let (text, _, _) = DF_ENCODING.decode(&bytes);
+        Ok(Self::parse_colors_txt(&text))
+    }
+    /// Load the colors a game install uses: the player's `prefs/colors.txt` if there is one,
+    /// otherwise `data/init/colors.txt`, otherwise the defaults.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_path` - The path to the Dwarf Fortress directory.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Io` - If a `colors.txt` file exists but can't be read.
+    pub fn from_game_directory<P: AsRef<Path>>(game_path: &P) -> Result<Self, ParserError> {
+        let game_path = game_path.as_ref();
+        for colors_file in [
+            game_path.join("prefs").join("colors.txt"),
+            game_path.join("data").join("init").join("colors.txt"),
+        ] {
+            if colors_file.is_file() {
+                return Self::from_colors_txt(&colors_file);
+            }
+        }
+        Ok(Self::DEFAULT)
+    }
+}
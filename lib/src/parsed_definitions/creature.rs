@@ -471,6 +471,42 @@ impl Creature {
         }
         ret_biomes
     }
+    /// Get how often the creature appears in its environment (`FREQUENCY`).
+    ///
+    /// # Returns
+    ///
+    /// The frequency, if the creature has one (the game uses 50 otherwise).
+    #[must_use]
+    pub const fn get_frequency(&self) -> Option<u32> {
+        self.frequency
+    }
+    /// Get how many of the creature are in each world map tile (`POPULATION_NUMBER`).
+    ///
+    /// # Returns
+    ///
+    /// The minimum and maximum population, if the creature has them.
+    #[must_use]
+    pub const fn get_population_number(&self) -> Option<[u32; 2]> {
+        self.population_number
+    }
+    /// Get how many of the creature spawn in each cluster (`CLUSTER_NUMBER`).
+    ///
+    /// # Returns
+    ///
+    /// The minimum and maximum cluster size, if the creature has them.
+    #[must_use]
+    pub const fn get_cluster_number(&self) -> Option<[u32; 2]> {
+        self.cluster_number
+    }
+    /// Get the depths the creature appears at underground (`UNDERGROUND_DEPTH`).
+    ///
+    /// # Returns
+    ///
+    /// The minimum and maximum depth, if the creature has them.
+    #[must_use]
+    pub const fn get_underground_depth(&self) -> Option<[u32; 2]> {
+        self.underground_depth
+    }
     /// Set the name of the creature.
     ///
     /// # Parameters
@@ -870,6 +906,14 @@ impl RawObject for Creature {
     fn clean_self(&mut self) {
         *self = self.cleaned();
     }
+    fn set_metadata_hidden(&mut self, hidden: bool) {
+        if let Some(metadata) = self.metadata.as_mut() {
+            metadata.set_hidden(hidden);
+        }
+        for select_creature in self.select_creature_variation.iter_mut().flatten() {
+            select_creature.set_metadata_hidden(hidden);
+        }
+    }
 }
 
 #[typetag::serde]
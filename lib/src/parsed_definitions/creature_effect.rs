@@ -1,20 +1,36 @@
 //! A module containing the `CreatureEffect` struct and its implementations.
 
-use crate::{default_checks, tags::CreatureEffectPropertyTag};
+use tracing::warn;
+
+use crate::{
+    default_checks,
+    raw_definitions::CREATURE_EFFECT_PROPERTY_TOKENS,
+    tags::{CreatureEffectPropertyTag, CreatureEffectTag},
+};
 
 /// A creature effect.
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct CreatureEffect {
+    /// The effect (e.g. `CE_PAIN` is `Pain`)
+    effect: CreatureEffectTag,
+    /// The arguments specific to the effect, before its properties (e.g. `STRENGTH:150:0` for
+    /// `CE_PHYS_ATT_CHANGE`, or `FLIER` for `CE_ADD_TAG`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    arguments: Option<Vec<String>>,
+
     severity: u32,
     probability: u8,
 
+    /// The targeted body parts and tissue layers, from `BP:BY_CATEGORY:<category>:<tissue>`
     #[serde(skip_serializing_if = "Option::is_none")]
-    affected_body_parts_by_category: Option<Vec<String>>,
+    affected_body_parts_by_category: Option<Vec<(String, String)>>,
+    /// The targeted body parts and tissue layers, from `BP:BY_TYPE:<type>:<tissue>`
     #[serde(skip_serializing_if = "Option::is_none")]
-    affected_body_parts_by_type: Option<Vec<String>>,
+    affected_body_parts_by_type: Option<Vec<(String, String)>>,
+    /// The targeted body parts and tissue layers, from `BP:BY_TOKEN:<token>:<tissue>`
     #[serde(skip_serializing_if = "Option::is_none")]
-    affected_body_parts_by_token: Option<Vec<String>>,
+    affected_body_parts_by_token: Option<Vec<(String, String)>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tags: Option<Vec<CreatureEffectPropertyTag>>,
 
@@ -27,6 +43,223 @@ pub struct CreatureEffect {
 }
 
 impl CreatureEffect {
+    /// Parse a creature effect from its token and value.
+    ///
+    /// The value holds the effect's own arguments followed by its properties, e.g.
+    /// `[CE_PAIN:SEV:50:PROB:100:RESISTABLE:BP:BY_CATEGORY:ALL:SKIN:START:50:PEAK:500:END:1000]`.
+    /// Values which aren't properties are kept as the effect's arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `effect` - The effect (from the `CE_*` token)
+    /// * `value` - The value of the token
+    ///
+    /// # Returns
+    ///
+    /// * `CreatureEffect` - The parsed creature effect
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dfraw_parser::{tags::CreatureEffectTag, CreatureEffect};
+    ///
+    /// let effect = CreatureEffect::from_value(
+    ///     CreatureEffectTag::Pain,
+    ///     "SEV:50:PROB:25:BP:BY_CATEGORY:ALL:SKIN:START:50:PEAK:500:END:1000",
+    /// );
+    /// assert_eq!(effect.get_severity(), 50);
+    /// assert_eq!(effect.get_probability(), 25);
+    /// assert_eq!(effect.get_timing(), (50, 500, 1000));
+    /// ```
+    #[must_use]
+    pub fn from_value(effect: CreatureEffectTag, value: &str) -> Self {
+        let mut creature_effect = Self {
+            effect,
+            // Effects without a `PROB` always happen
+            probability: 100,
+            ..Self::default()
+        };
+
+        let mut values = value.split(':').map(str::trim);
+        while let Some(token) = values.next() {
+            let Some(property) = CREATURE_EFFECT_PROPERTY_TOKENS.get(token) else {
+                creature_effect
+                    .arguments
+                    .get_or_insert_with(Vec::new)
+                    .push(String::from(token));
+                continue;
+            };
+            let mut number = || values.next().unwrap_or_default().parse::<u32>();
+            match property {
+                CreatureEffectPropertyTag::Severity => {
+                    creature_effect.severity = number().unwrap_or_default();
+                }
+                CreatureEffectPropertyTag::Probability => {
+                    creature_effect.probability = number().map_or(100, |probability| {
+                        u8::try_from(probability.min(100)).unwrap_or(100)
+                    });
+                }
+                CreatureEffectPropertyTag::Start => {
+                    creature_effect.start = number().unwrap_or_default();
+                }
+                CreatureEffectPropertyTag::Peak => {
+                    creature_effect.peak = number().unwrap_or_default();
+                }
+                CreatureEffectPropertyTag::End => {
+                    creature_effect.end = number().unwrap_or_default();
+                }
+                CreatureEffectPropertyTag::DwfStretch => {
+                    creature_effect.dwf_stretch = number()
+                        .ok()
+                        .map(|stretch| u8::try_from(stretch).unwrap_or(u8::MAX));
+                }
+                CreatureEffectPropertyTag::BodyPart => {
+                    let selector = values.next().unwrap_or_default();
+                    let body_part = String::from(values.next().unwrap_or_default());
+                    let tissue = String::from(values.next().unwrap_or_default());
+                    let targets = match CREATURE_EFFECT_PROPERTY_TOKENS.get(selector) {
+                        Some(CreatureEffectPropertyTag::ByCategory) => {
+                            &mut creature_effect.affected_body_parts_by_category
+                        }
+                        Some(CreatureEffectPropertyTag::ByType) => {
+                            &mut creature_effect.affected_body_parts_by_type
+                        }
+                        Some(CreatureEffectPropertyTag::ByToken) => {
+                            &mut creature_effect.affected_body_parts_by_token
+                        }
+                        _ => {
+                            warn!(
+                                "CreatureEffect::from_value: Unknown body part selector {} in {}",
+                                selector, value
+                            );
+                            continue;
+                        }
+                    };
+                    targets
+                        .get_or_insert_with(Vec::new)
+                        .push((body_part, tissue));
+                }
+                flag => {
+                    creature_effect
+                        .tags
+                        .get_or_insert_with(Vec::new)
+                        .push(flag.clone());
+                }
+            }
+        }
+
+        creature_effect
+    }
+    /// Get the effect.
+    ///
+    /// # Returns
+    ///
+    /// * `&CreatureEffectTag` - The effect
+    #[must_use]
+    pub const fn get_effect(&self) -> &CreatureEffectTag {
+        &self.effect
+    }
+    /// Get the arguments specific to the effect (e.g. the attribute and amounts of a
+    /// `CE_PHYS_ATT_CHANGE`).
+    ///
+    /// # Returns
+    ///
+    /// * `&[String]` - The arguments (empty if there are none)
+    #[must_use]
+    pub fn get_arguments(&self) -> &[String] {
+        self.arguments.as_deref().unwrap_or_default()
+    }
+    /// Get the severity of the effect.
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The severity
+    #[must_use]
+    pub const fn get_severity(&self) -> u32 {
+        self.severity
+    }
+    /// Get the chance of the effect happening, as a percentage.
+    ///
+    /// # Returns
+    ///
+    /// * `u8` - The probability (100 if the effect always happens)
+    #[must_use]
+    pub const fn get_probability(&self) -> u8 {
+        self.probability
+    }
+    /// Get the timing of the effect, in ticks after exposure.
+    ///
+    /// # Returns
+    ///
+    /// * `(u32, u32, u32)` - The start, peak and end of the effect (0 if not set)
+    #[must_use]
+    pub const fn get_timing(&self) -> (u32, u32, u32) {
+        (self.start, self.peak, self.end)
+    }
+    /// Get the multiplier of the effect's timing in fortress mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u8>` - The `DWF_STRETCH` multiplier, if set
+    #[must_use]
+    pub const fn get_dwf_stretch(&self) -> Option<u8> {
+        self.dwf_stretch
+    }
+    /// Get the targeted body parts and tissue layers by category (`BP:BY_CATEGORY`).
+    ///
+    /// # Returns
+    ///
+    /// * `&[(String, String)]` - The body part categories and tissue layers
+    #[must_use]
+    pub fn get_affected_body_parts_by_category(&self) -> &[(String, String)] {
+        self.affected_body_parts_by_category
+            .as_deref()
+            .unwrap_or_default()
+    }
+    /// Get the targeted body parts and tissue layers by type (`BP:BY_TYPE`).
+    ///
+    /// # Returns
+    ///
+    /// * `&[(String, String)]` - The body part types and tissue layers
+    #[must_use]
+    pub fn get_affected_body_parts_by_type(&self) -> &[(String, String)] {
+        self.affected_body_parts_by_type
+            .as_deref()
+            .unwrap_or_default()
+    }
+    /// Get the targeted body parts and tissue layers by token (`BP:BY_TOKEN`).
+    ///
+    /// # Returns
+    ///
+    /// * `&[(String, String)]` - The body part tokens and tissue layers
+    #[must_use]
+    pub fn get_affected_body_parts_by_token(&self) -> &[(String, String)] {
+        self.affected_body_parts_by_token
+            .as_deref()
+            .unwrap_or_default()
+    }
+    /// Get the flags of the effect (e.g. `Resistible` or `SizeDilutes`).
+    ///
+    /// # Returns
+    ///
+    /// * `&[CreatureEffectPropertyTag]` - The flags
+    #[must_use]
+    pub fn get_tags(&self) -> &[CreatureEffectPropertyTag] {
+        self.tags.as_deref().unwrap_or_default()
+    }
+    /// Returns whether the effect has a flag.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The flag to check for
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the effect has the flag
+    #[must_use]
+    pub fn has_tag(&self, tag: &CreatureEffectPropertyTag) -> bool {
+        self.get_tags().contains(tag)
+    }
     /// Function to "clean" the creature. This is used to remove any empty list or strings,
     /// and to remove any default values. By "removing" it means setting the value to None.
     ///
@@ -41,6 +274,13 @@ impl CreatureEffect {
     pub fn cleaned(&self) -> Self {
         let mut cleaned = self.clone();
 
+        // Set any empty string to None.
+        if let Some(arguments) = cleaned.arguments.clone() {
+            if arguments.is_empty() {
+                cleaned.arguments = None;
+            }
+        }
+
         // Set any empty string to None.
         if let Some(affected_body_parts_by_category) =
             cleaned.affected_body_parts_by_category.clone()
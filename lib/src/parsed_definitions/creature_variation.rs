@@ -164,6 +164,11 @@ impl RawObject for CreatureVariation {
     fn clean_self(&mut self) {
         *self = self.cleaned();
     }
+    fn set_metadata_hidden(&mut self, hidden: bool) {
+        if let Some(metadata) = self.metadata.as_mut() {
+            metadata.set_hidden(hidden);
+        }
+    }
 
     #[allow(clippy::too_many_lines)]
     fn parse_tag(&mut self, key: &str, value: &str) {
@@ -80,6 +80,24 @@ impl Dimensions {
 
         Self { x, y }
     }
+    /// Get the x value.
+    ///
+    /// # Returns
+    ///
+    /// * `i32` - The x value.
+    #[must_use]
+    pub const fn get_x(&self) -> i32 {
+        self.x
+    }
+    /// Get the y value.
+    ///
+    /// # Returns
+    ///
+    /// * `i32` - The y value.
+    #[must_use]
+    pub const fn get_y(&self) -> i32 {
+        self.y
+    }
     /// Returns an empty Dimensions object.
     ///
     /// # Returns
@@ -159,6 +159,48 @@ pub struct Entity {
 }
 
 impl Entity {
+    /// Get the creature the entity is made of
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The identifier of the creature, if the entity has one
+    #[must_use]
+    pub fn get_creature(&self) -> Option<&str> {
+        self.creature.as_deref()
+    }
+    /// Get the positions of the entity
+    ///
+    /// # Returns
+    ///
+    /// * `&[Position]` - The positions of the entity
+    #[must_use]
+    pub fn get_positions(&self) -> &[Position] {
+        self.positions.as_deref().unwrap_or_default()
+    }
+    /// Get the tags of the entity
+    ///
+    /// # Returns
+    ///
+    /// * `&[EntityTag]` - The tags of the entity
+    #[must_use]
+    pub fn get_tags(&self) -> &[EntityTag] {
+        &self.tags
+    }
+    /// Check whether the entity has a tag (any values of the tag are ignored)
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag to check for
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the entity has the tag
+    #[must_use]
+    pub fn has_tag(&self, tag: &EntityTag) -> bool {
+        self.tags
+            .iter()
+            .any(|t| std::mem::discriminant(t) == std::mem::discriminant(tag))
+    }
     /// Function to create a new empty Entity.
     ///
     /// # Returns
@@ -456,6 +498,11 @@ impl RawObject for Entity {
     fn clean_self(&mut self) {
         *self = self.cleaned();
     }
+    fn set_metadata_hidden(&mut self, hidden: bool) {
+        if let Some(metadata) = self.metadata.as_mut() {
+            metadata.set_hidden(hidden);
+        }
+    }
     #[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
     fn parse_tag(&mut self, key: &str, value: &str) {
         if let Some(position_token) = POSITION_TOKENS.get(key) {
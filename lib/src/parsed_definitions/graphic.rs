@@ -5,9 +5,12 @@ use tracing::warn;
 use crate::{
     custom_graphic_extension::CustomGraphicExtension,
     metadata::{ObjectType, RawMetadata},
-    raw_definitions::{CUSTOM_GRAPHIC_TOKENS, GROWTH_TOKENS, PLANT_GRAPHIC_TEMPLATE_TOKENS},
+    raw_definitions::{
+        CONDITION_TOKENS, CUSTOM_GRAPHIC_TOKENS, GROWTH_TOKENS, PLANT_GRAPHIC_TEMPLATE_TOKENS,
+    },
     sprite_graphic::SpriteGraphic,
     sprite_layer::SpriteLayer,
+    sprite_layer_group::SpriteLayerGroup,
     tags::GraphicTypeTag,
     traits::{searchable::clean_search_vec, RawObject, Searchable},
     utilities::build_object_id_from_pieces,
@@ -30,7 +33,7 @@ pub struct Graphic {
     #[serde(skip_serializing_if = "Option::is_none")]
     sprites: Option<Vec<SpriteGraphic>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    layers: Option<Vec<(String, Vec<SpriteLayer>)>>,
+    layers: Option<Vec<(String, Vec<SpriteLayerGroup>)>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     growths: Option<Vec<(String, Vec<SpriteGraphic>)>>,
 
@@ -41,6 +44,23 @@ pub struct Graphic {
 
     #[serde(skip)]
     layer_mode: bool,
+    #[serde(skip)]
+    #[specta(skip)]
+    layer_group_state: LayerGroupState,
+}
+
+/// Where layers and conditions are being added while parsing a layer set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum LayerGroupState {
+    /// No layer group is open
+    #[default]
+    Closed,
+    /// The last group holds layers outside of a `LAYER_GROUP`
+    Loose,
+    /// In a `LAYER_GROUP`, adding conditions to the group itself
+    GroupConditions,
+    /// In a `LAYER_GROUP`, adding conditions to its last layer
+    GroupLayers,
 }
 
 impl Graphic {
@@ -73,8 +93,21 @@ impl Graphic {
     /// * `Graphic` - The new Graphic.
     #[must_use]
     pub fn new(identifier: &str, metadata: &RawMetadata, graphic_type: GraphicTypeTag) -> Self {
+        // [CREATURE_CASTE_GRAPHICS:DWARF:FEMALE] targets one caste of the creature
+        let (target_identifier, caste_identifier) = match identifier.split_once(':') {
+            Some((creature, caste))
+                if matches!(
+                    graphic_type,
+                    GraphicTypeTag::CreatureCaste | GraphicTypeTag::StatueCreatureCaste
+                ) =>
+            {
+                (creature, Some(String::from(caste)))
+            }
+            _ => (identifier, None),
+        };
         Self {
-            identifier: String::from(identifier),
+            identifier: String::from(target_identifier),
+            caste_identifier,
             metadata: Some(metadata.clone()),
             object_id: build_object_id_from_pieces(metadata, identifier, &ObjectType::Graphics),
             kind: graphic_type,
@@ -92,35 +125,73 @@ impl Graphic {
     }
     fn parse_layer_set_from_value(&mut self, value: &str) {
         self.add_layer_if_not_exists(String::from(value));
+        self.layer_group_state = LayerGroupState::Closed;
+    }
+    /// The groups of the current layer set, starting a `default` layer set if there isn't one.
+    fn current_layer_set_mut(&mut self) -> &mut Vec<SpriteLayerGroup> {
+        if self.layers.is_none() {
+            self.add_layer_if_not_exists(String::from("default"));
+        }
+        #[allow(clippy::unwrap_used)]
+        &mut self.layers.as_mut().unwrap().last_mut().unwrap().1
+    }
+    fn start_layer_group(&mut self) {
+        self.current_layer_set_mut()
+            .push(SpriteLayerGroup::default());
+        self.layer_group_state = LayerGroupState::GroupConditions;
     }
     fn parse_layer_from_value(&mut self, value: &str) {
         if let Some(layer) = SpriteLayer::parse_layer_from_value(value) {
-            if self.layers.is_none() {
-                self.add_layer_if_not_exists(String::from("default"));
-            }
-            if let Some(layers) = self.layers.as_mut() {
-                #[allow(clippy::unwrap_used)]
-                layers.last_mut().unwrap().1.push(layer);
+            // Layers outside of a LAYER_GROUP share a group without conditions
+            let in_layer_group = matches!(
+                self.layer_group_state,
+                LayerGroupState::GroupConditions | LayerGroupState::GroupLayers
+            );
+            let start_loose_group = self.layer_group_state == LayerGroupState::Closed;
+            let layer_set = self.current_layer_set_mut();
+            if start_loose_group || layer_set.is_empty() {
+                layer_set.push(SpriteLayerGroup::default());
             }
+            #[allow(clippy::unwrap_used)]
+            layer_set.last_mut().unwrap().add_layer(layer);
+
+            self.layer_group_state = if in_layer_group {
+                LayerGroupState::GroupLayers
+            } else {
+                LayerGroupState::Loose
+            };
         }
     }
     fn parse_layer_condition_token(&mut self, key: &str, value: &str) {
-        if let Some(layers) = self.layers.as_mut() {
-            // Conditions get attached to the last layer in the last layer group
-            #[allow(clippy::unwrap_used)]
-            if let Some(layer) = layers.last_mut().unwrap().1.last_mut() {
-                layer.parse_condition_token(key, value);
-            } else {
-                warn!(
-                    "Graphic::parse_condition_token: [{}] Failed to parse {}:{} as LayerCondition",
-                    self.identifier, key, value
-                );
-            }
-        } else {
+        // LG_CONDITION_BP (and the BP_ conditions after it) apply to the whole layer group
+        if key == "LG_CONDITION_BP" && self.layer_group_state == LayerGroupState::GroupLayers {
+            self.layer_group_state = LayerGroupState::GroupConditions;
+        }
+        let group_conditions_mode = self.layer_group_state == LayerGroupState::GroupConditions;
+
+        let Some(group) = self
+            .layers
+            .as_mut()
+            .and_then(|layers| layers.last_mut())
+            .and_then(|(_, groups)| groups.last_mut())
+        else {
             warn!(
                 "Graphic::parse_condition_token: [{}] Failed to parse {}:{} as LayerCondition (No existing layers)",
                 self.identifier, key, value
             );
+            return;
+        };
+
+        if group_conditions_mode {
+            group.parse_condition_token(key, value);
+        } else if let Some(layer) = group.last_layer_mut() {
+            // Conditions get attached to the last layer in the current group
+            layer.parse_condition_token(key, value);
+        } else {
+            warn!(
+                "Graphic::parse_condition_token: [{}] Failed to parse {}:{} as LayerCondition",
+                self.identifier, key, value
+            );
         }
     }
     /// Parse a token from a tag into a `SpriteGraphic` and add it to the current sprite.
@@ -148,18 +219,31 @@ impl Graphic {
             return;
         }
 
-        // Layers can be defined in groups.. for now we just ignore it
+        // Layers can be defined in groups, which share conditions
         if key == "LAYER_GROUP" {
+            self.start_layer_group();
             self.layer_mode = true;
             return;
         }
         if key == "END_LAYER_GROUP" {
-            self.layer_mode = false;
+            self.layer_group_state = LayerGroupState::Closed;
             return;
         }
 
-        // Right now we don't handle TREE_TILE
+        // Tree tiles are sprites for the parts of a tree
         if key == "TREE_TILE" {
+            if let Some(sprite_graphic) = SpriteGraphic::from_tree_tile_value(value) {
+                self.sprites
+                    .get_or_insert_with(Vec::new)
+                    .push(sprite_graphic);
+            } else {
+                warn!(
+                    "Graphic::parse_sprite_from_tag:_tree_tile [{}] Failed to parse {},{} as SpriteGraphic",
+                    self.identifier,
+                    key,
+                    value
+                );
+            }
             return;
         }
 
@@ -173,6 +257,12 @@ impl Graphic {
             return;
         }
 
+        // Conditions without a value (like BP_PRESENT or CONDITION_NOT_CHILD) still belong to the layers
+        if self.layer_mode && value.is_empty() && CONDITION_TOKENS.contains_key(key) {
+            self.parse_layer_condition_token(key, value);
+            return;
+        }
+
         // Check if the value is empty, which means we have a tag
         if value.is_empty() {
             if let Some(tags) = self.tags.as_mut() {
@@ -275,6 +365,102 @@ impl Graphic {
     pub const fn get_graphic_type(&self) -> GraphicTypeTag {
         self.kind
     }
+    /// Get the caste the Graphic is for (for `CREATURE_CASTE_GRAPHICS` and
+    /// `STATUE_CREATURE_CASTE_GRAPHICS`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The caste identifier, or `None` if the Graphic isn't for one caste.
+    #[must_use]
+    pub fn get_caste_identifier(&self) -> Option<&str> {
+        self.caste_identifier.as_deref()
+    }
+    /// Get the type of object the Graphic is for: creatures for `CREATURE_GRAPHICS`,
+    /// `CREATURE_CASTE_GRAPHICS` and their `STATUE_` versions, plants for `PLANT_GRAPHICS` and
+    /// inorganics for `TILE_GRAPHICS`.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<ObjectType>` - The type of the target object, or `None` if the Graphic doesn't
+    ///   target an object.
+    #[must_use]
+    pub const fn get_target_object_type(&self) -> Option<ObjectType> {
+        match self.kind {
+            GraphicTypeTag::Creature
+            | GraphicTypeTag::CreatureCaste
+            | GraphicTypeTag::StatueCreature
+            | GraphicTypeTag::StatueCreatureCaste => Some(ObjectType::Creature),
+            GraphicTypeTag::Plant => Some(ObjectType::Plant),
+            GraphicTypeTag::Tile => Some(ObjectType::Inorganic),
+            _ => None,
+        }
+    }
+    /// Get the sprites of the Graphic.
+    ///
+    /// # Returns
+    ///
+    /// * `&[SpriteGraphic]` - The sprites of the Graphic.
+    #[must_use]
+    pub fn get_sprites(&self) -> &[SpriteGraphic] {
+        self.sprites.as_deref().unwrap_or_default()
+    }
+    /// Get the layer sets of the Graphic, as pairs of the layer set name and its layers.
+    ///
+    /// # Returns
+    ///
+    /// * `&[(String, Vec<SpriteLayerGroup>)]` - The layer sets of the Graphic.
+    #[must_use]
+    pub fn get_layers(&self) -> &[(String, Vec<SpriteLayerGroup>)] {
+        self.layers.as_deref().unwrap_or_default()
+    }
+    /// Get the layer groups of one of the Graphic's layer sets.
+    ///
+    /// # Arguments
+    ///
+    /// * `layer_set` - The name of the layer set (e.g. `DEFAULT` or `PORTRAIT`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&[SpriteLayerGroup]>` - The layer groups of the layer set, or `None` if there isn't one.
+    #[must_use]
+    pub fn get_layer_set(&self, layer_set: &str) -> Option<&[SpriteLayerGroup]> {
+        self.get_layers()
+            .iter()
+            .find(|(name, _)| name == layer_set)
+            .map(|(_, layers)| layers.as_slice())
+    }
+    /// Get the growths of the Graphic, as pairs of the growth name and its sprites.
+    ///
+    /// # Returns
+    ///
+    /// * `&[(String, Vec<SpriteGraphic>)]` - The growths of the Graphic.
+    #[must_use]
+    pub fn get_growths(&self) -> &[(String, Vec<SpriteGraphic>)] {
+        self.growths.as_deref().unwrap_or_default()
+    }
+    /// Get the sprites of one growth, with any plant graphic templates (e.g. `STANDARD_LEAVES`)
+    /// expanded into the sprites they stand for.
+    ///
+    /// # Arguments
+    ///
+    /// * `growth` - The name of the growth (e.g. `FRUIT`).
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<SpriteGraphic>` - The growth's sprites (empty if the Graphic doesn't have the growth).
+    #[must_use]
+    pub fn get_expanded_growth(&self, growth: &str) -> Vec<SpriteGraphic> {
+        self.get_growths()
+            .iter()
+            .filter(|(name, _)| name == growth)
+            .flat_map(|(_, sprites)| sprites)
+            .flat_map(|sprite| {
+                sprite
+                    .expand_plant_template()
+                    .unwrap_or_else(|| vec![sprite.clone()])
+            })
+            .collect()
+    }
     /// Get the tile page IDs for the Graphic.
     ///
     /// # Returns
@@ -289,12 +475,17 @@ impl Graphic {
             }
         }
         if let Some(layers) = &self.layers {
-            for layer in layers {
-                for sprite in &layer.1 {
-                    vec.push(sprite.get_tile_page_id().to_string());
+            for (_, groups) in layers {
+                for layer in groups.iter().flat_map(SpriteLayerGroup::get_layers) {
+                    vec.push(layer.get_tile_page_id().to_string());
                 }
             }
         }
+        for (growth, _) in self.get_growths() {
+            for sprite in self.get_expanded_growth(growth) {
+                vec.push(sprite.get_tile_page_id().to_string());
+            }
+        }
         vec
     }
     /// Function to "clean" the creature. This is used to remove any empty list or strings,
@@ -343,12 +534,12 @@ impl Graphic {
 
         if let Some(layers) = &cleaned.layers {
             let mut new_layers = Vec::new();
-            for (name, sprites) in layers {
-                let mut new_sprites = Vec::new();
-                for sprite in sprites {
-                    new_sprites.push(sprite.cleaned());
+            for (name, groups) in layers {
+                let mut new_groups = Vec::new();
+                for group in groups {
+                    new_groups.push(group.cleaned());
                 }
-                new_layers.push((name.clone(), new_sprites));
+                new_layers.push((name.clone(), new_groups));
             }
             cleaned.layers = Some(new_layers);
         }
@@ -397,6 +588,11 @@ impl RawObject for Graphic {
     fn clean_self(&mut self) {
         *self = self.cleaned();
     }
+    fn set_metadata_hidden(&mut self, hidden: bool) {
+        if let Some(metadata) = self.metadata.as_mut() {
+            metadata.set_hidden(hidden);
+        }
+    }
 
     fn parse_tag(&mut self, key: &str, value: &str) {
         // Any tags should just be able to be handled by the sprite graphic, but it needs to call the right function
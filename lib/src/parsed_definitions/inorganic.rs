@@ -39,6 +39,24 @@ pub struct Inorganic {
 }
 
 impl Inorganic {
+    /// Get the material of the Inorganic object.
+    ///
+    /// # Returns
+    ///
+    /// The material the Inorganic object is made of.
+    #[must_use]
+    pub const fn get_material(&self) -> &Material {
+        &self.material
+    }
+    /// Get the tags of the Inorganic object.
+    ///
+    /// # Returns
+    ///
+    /// The tags of the Inorganic object.
+    #[must_use]
+    pub fn get_tags(&self) -> &[InorganicTag] {
+        self.tags.as_deref().unwrap_or_default()
+    }
     /// Create a new empty Inorganic object.
     ///
     /// This is used for creating a new Inorganic object with the metadata set to hidden.
@@ -205,6 +223,11 @@ impl RawObject for Inorganic {
     fn clean_self(&mut self) {
         *self = self.cleaned();
     }
+    fn set_metadata_hidden(&mut self, hidden: bool) {
+        if let Some(metadata) = self.metadata.as_mut() {
+            metadata.set_hidden(hidden);
+        }
+    }
 
     fn parse_tag(&mut self, key: &str, value: &str) {
         if INORGANIC_TOKENS.contains_key(key) {
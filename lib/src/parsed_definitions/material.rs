@@ -106,6 +106,78 @@ pub struct Material {
 }
 
 impl Material {
+    /// Get the name of the material
+    ///
+    /// # Returns
+    ///
+    /// The name of the material (empty if it doesn't have one)
+    #[must_use]
+    pub fn get_name(&self) -> &str {
+        self.name.as_deref().unwrap_or_default()
+    }
+    /// Get the type of the material
+    ///
+    /// # Returns
+    ///
+    /// The type of the material, if it has one
+    #[must_use]
+    pub const fn get_material_type(&self) -> Option<&MaterialTypeTag> {
+        self.material_type.as_ref()
+    }
+    /// Get the value of the material
+    ///
+    /// # Returns
+    ///
+    /// The value multiplier of the material, if it has one
+    #[must_use]
+    pub const fn get_value(&self) -> Option<u32> {
+        self.value
+    }
+    /// Get the syndromes of the material
+    ///
+    /// # Returns
+    ///
+    /// The syndromes the material causes (empty if it has none)
+    #[must_use]
+    pub fn get_syndromes(&self) -> &[Syndrome] {
+        self.syndromes.as_deref().unwrap_or_default()
+    }
+    /// Get the density of the material as a liquid
+    ///
+    /// # Returns
+    ///
+    /// The liquid density of the material, if it has one
+    #[must_use]
+    pub const fn get_liquid_density(&self) -> Option<i32> {
+        self.liquid_density
+    }
+    /// Get the molar mass of the material
+    ///
+    /// # Returns
+    ///
+    /// The molar mass of the material, if it has one
+    #[must_use]
+    pub const fn get_molar_mass(&self) -> Option<i32> {
+        self.molar_mass
+    }
+    /// Get the mechanical properties of the material
+    ///
+    /// # Returns
+    ///
+    /// The mechanical properties of the material, if it has any
+    #[must_use]
+    pub const fn get_mechanical_properties(&self) -> Option<&MaterialMechanics> {
+        self.mechanical_properties.as_ref()
+    }
+    /// Get the temperatures of the material
+    ///
+    /// # Returns
+    ///
+    /// The temperature properties of the material, if it has any
+    #[must_use]
+    pub const fn get_temperatures(&self) -> Option<&Temperatures> {
+        self.temperatures.as_ref()
+    }
     /// Create a new material
     ///
     /// # Returns
@@ -56,6 +56,78 @@ impl MaterialMechanics {
             && self.shear.is_none()
             && self.bending.is_none()
     }
+    /// Returns the properties of the material under impact stress.
+    ///
+    /// # Returns
+    ///
+    /// * The impact properties, if the material has them.
+    #[must_use]
+    pub const fn get_impact(&self) -> Option<&MechanicalProperties> {
+        self.impact.as_ref()
+    }
+    /// Returns the properties of the material under compressive stress.
+    ///
+    /// # Returns
+    ///
+    /// * The compressive properties, if the material has them.
+    #[must_use]
+    pub const fn get_compressive(&self) -> Option<&MechanicalProperties> {
+        self.compressive.as_ref()
+    }
+    /// Returns the properties of the material under tensile stress.
+    ///
+    /// # Returns
+    ///
+    /// * The tensile properties, if the material has them.
+    #[must_use]
+    pub const fn get_tensile(&self) -> Option<&MechanicalProperties> {
+        self.tensile.as_ref()
+    }
+    /// Returns the properties of the material under torsion stress.
+    ///
+    /// # Returns
+    ///
+    /// * The torsion properties, if the material has them.
+    #[must_use]
+    pub const fn get_torsion(&self) -> Option<&MechanicalProperties> {
+        self.torsion.as_ref()
+    }
+    /// Returns the properties of the material under shear stress.
+    ///
+    /// # Returns
+    ///
+    /// * The shear properties, if the material has them.
+    #[must_use]
+    pub const fn get_shear(&self) -> Option<&MechanicalProperties> {
+        self.shear.as_ref()
+    }
+    /// Returns the properties of the material under bending stress.
+    ///
+    /// # Returns
+    ///
+    /// * The bending properties, if the material has them.
+    #[must_use]
+    pub const fn get_bending(&self) -> Option<&MechanicalProperties> {
+        self.bending.as_ref()
+    }
+    /// Returns the sharpness of an edge made of the material.
+    ///
+    /// # Returns
+    ///
+    /// * The maximum edge, if the material has one.
+    #[must_use]
+    pub const fn get_max_edge(&self) -> Option<i32> {
+        self.max_edge
+    }
+    /// Returns the density of the material as a solid.
+    ///
+    /// # Returns
+    ///
+    /// * The solid density, if the material has one.
+    #[must_use]
+    pub const fn get_solid_density(&self) -> Option<i32> {
+        self.solid_density
+    }
     /// Parses a tag and value into the `Mechanics` struct.
     ///
     /// # Arguments
@@ -58,6 +58,15 @@ impl MaterialTemplate {
             ..Self::default()
         }
     }
+    /// Get the material of the material template
+    ///
+    /// # Returns
+    ///
+    /// The material the template defines
+    #[must_use]
+    pub const fn get_material(&self) -> &Material {
+        &self.material
+    }
 
     /// Function to "clean" the raw. This is used to remove any empty list or strings,
     /// and to remove any default values. By "removing" it means setting the value to None.
@@ -127,6 +136,11 @@ impl RawObject for MaterialTemplate {
     fn clean_self(&mut self) {
         *self = self.cleaned();
     }
+    fn set_metadata_hidden(&mut self, hidden: bool) {
+        if let Some(metadata) = self.metadata.as_mut() {
+            metadata.set_hidden(hidden);
+        }
+    }
 }
 
 impl Searchable for MaterialTemplate {
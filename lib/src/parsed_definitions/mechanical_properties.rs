@@ -30,6 +30,33 @@ impl MechanicalProperties {
     pub const fn is_empty(&self) -> bool {
         self.yield_stress == 0 && self.fracture == 0 && self.elasticity == 0
     }
+    /// Returns the yield stress of the material
+    ///
+    /// # Returns
+    ///
+    /// * The yield stress
+    #[must_use]
+    pub const fn get_yield(&self) -> i32 {
+        self.yield_stress
+    }
+    /// Returns the fracture of the material
+    ///
+    /// # Returns
+    ///
+    /// * The fracture
+    #[must_use]
+    pub const fn get_fracture(&self) -> i32 {
+        self.fracture
+    }
+    /// Returns the elasticity of the material
+    ///
+    /// # Returns
+    ///
+    /// * The elasticity
+    #[must_use]
+    pub const fn get_elasticity(&self) -> i32 {
+        self.elasticity
+    }
     /// Sets the yield stress of the material
     ///
     /// # Arguments
@@ -5,6 +5,7 @@ mod tag_parsing;
 pub mod body_size;
 pub mod caste;
 pub mod color;
+pub mod color_palette;
 pub mod creature;
 pub mod creature_effect;
 pub mod creature_variation;
@@ -22,6 +23,7 @@ pub mod mechanical_properties;
 pub mod milkable;
 pub mod name;
 pub mod plant;
+pub mod plant_graphic_template;
 pub mod plant_growth;
 pub mod position;
 pub mod seed_material;
@@ -29,6 +31,7 @@ pub mod select_creature;
 pub mod shrub;
 pub mod sprite_graphic;
 pub mod sprite_layer;
+pub mod sprite_layer_group;
 pub mod state_names;
 pub mod steam_data;
 pub mod syndrome;
@@ -41,6 +44,7 @@ pub mod tree;
 pub use body_size::BodySize;
 pub use caste::Caste;
 pub use color::Color;
+pub use color_palette::ColorPalette;
 pub use creature::Creature;
 pub use creature_effect::CreatureEffect;
 pub use creature_variation::CreatureVariation;
@@ -58,6 +62,7 @@ pub use mechanical_properties::MechanicalProperties;
 pub use milkable::Milkable;
 pub use name::Name;
 pub use plant::Plant;
+pub use plant_graphic_template::PlantTemplateTile;
 pub use plant_growth::PlantGrowth;
 pub use position::Position;
 pub use seed_material::SeedMaterial;
@@ -65,6 +70,7 @@ pub use select_creature::SelectCreature;
 pub use shrub::Shrub;
 pub use sprite_graphic::SpriteGraphic;
 pub use sprite_layer::SpriteLayer;
+pub use sprite_layer_group::SpriteLayerGroup;
 pub use state_names::StateNames;
 pub use steam_data::SteamData;
 pub use syndrome::Syndrome;
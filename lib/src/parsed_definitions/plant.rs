@@ -111,6 +111,65 @@ impl Plant {
             .as_ref()
             .map_or_else(Vec::new, std::clone::Clone::clone)
     }
+    /// Get how often the plant grows in its environment
+    ///
+    /// # Returns
+    ///
+    /// The frequency, if the plant has one (the game uses 50 otherwise)
+    #[must_use]
+    pub const fn get_frequency(&self) -> Option<u32> {
+        self.frequency
+    }
+    /// Get the depths the plant grows at underground
+    ///
+    /// # Returns
+    ///
+    /// The minimum and maximum depth, if the plant has them
+    #[must_use]
+    pub const fn get_underground_depth(&self) -> Option<[u32; 2]> {
+        self.underground_depth
+    }
+    /// Get the tags of the plant
+    ///
+    /// # Returns
+    ///
+    /// The tags of the plant
+    #[must_use]
+    pub fn get_tags(&self) -> &[PlantTag] {
+        self.tags.as_deref().unwrap_or_default()
+    }
+    /// Get the materials defined by the plant
+    #[must_use]
+    pub fn get_materials(&self) -> &[Material] {
+        self.materials.as_deref().unwrap_or_default()
+    }
+    /// Get the growths of the plant (leaves, fruit, etc.)
+    ///
+    /// # Returns
+    ///
+    /// The growths of the plant
+    #[must_use]
+    pub fn get_growths(&self) -> &[PlantGrowth] {
+        self.growths.as_deref().unwrap_or_default()
+    }
+    /// Get the tree details, if the plant is a tree
+    ///
+    /// # Returns
+    ///
+    /// The tree details of the plant
+    #[must_use]
+    pub const fn get_tree_details(&self) -> Option<&Tree> {
+        self.tree_details.as_ref()
+    }
+    /// Get the shrub details, if the plant is a shrub
+    ///
+    /// # Returns
+    ///
+    /// The shrub details of the plant
+    #[must_use]
+    pub const fn get_shrub_details(&self) -> Option<&Shrub> {
+        self.shrub_details.as_ref()
+    }
 
     /// Function to "clean" the raw. This is used to remove any empty list or strings,
     /// and to remove any default values. By "removing" it means setting the value to None.
@@ -276,6 +335,11 @@ impl RawObject for Plant {
     fn clean_self(&mut self) {
         *self = self.cleaned();
     }
+    fn set_metadata_hidden(&mut self, hidden: bool) {
+        if let Some(metadata) = self.metadata.as_mut() {
+            metadata.set_hidden(hidden);
+        }
+    }
     fn get_type(&self) -> &ObjectType {
         &ObjectType::Plant
     }
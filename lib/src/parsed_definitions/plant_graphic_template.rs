@@ -0,0 +1,81 @@
+//! The fixed layouts of the plant graphic templates (e.g. `STANDARD_FRUIT_2`).
+//!
+//! A template token points at the top-left tile of a block on a tile page. Each row of the block is
+//! one growth stage (the `_N` of the template is how many stages there are, `STANDARD_LEAVES` has
+//! one), and each column is where the growth is drawn: on tree twigs and branches, on a sapling, on
+//! a shrub, and for flowers and fruit on a crop. Fruit has one more column for the picked fruit.
+
+use crate::tags::{ConditionTag, PlantGraphicTemplateTag};
+
+/// One tile of a plant graphic template's layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlantTemplateTile {
+    /// What the tile is drawn for (`TreeTile`, `Sapling`, `Shrub`, `Crop` or `Picked`)
+    pub condition: ConditionTag,
+    /// The tree part, for `TreeTile` tiles (e.g. `TWIGS`)
+    pub tree_part: Option<&'static str>,
+    /// The column of the tile in the template (in tiles)
+    pub column: i32,
+}
+
+impl PlantTemplateTile {
+    const fn new(condition: ConditionTag, tree_part: Option<&'static str>, column: i32) -> Self {
+        Self {
+            condition,
+            tree_part,
+            column,
+        }
+    }
+}
+
+const LEAVES_LAYOUT: [PlantTemplateTile; 4] = [
+    PlantTemplateTile::new(ConditionTag::TreeTile, Some("TWIGS"), 0),
+    PlantTemplateTile::new(ConditionTag::TreeTile, Some("BRANCHES"), 1),
+    PlantTemplateTile::new(ConditionTag::Sapling, None, 2),
+    PlantTemplateTile::new(ConditionTag::Shrub, None, 3),
+];
+
+const FLOWERS_LAYOUT: [PlantTemplateTile; 5] = [
+    PlantTemplateTile::new(ConditionTag::TreeTile, Some("TWIGS"), 0),
+    PlantTemplateTile::new(ConditionTag::TreeTile, Some("BRANCHES"), 1),
+    PlantTemplateTile::new(ConditionTag::Sapling, None, 2),
+    PlantTemplateTile::new(ConditionTag::Shrub, None, 3),
+    PlantTemplateTile::new(ConditionTag::Crop, None, 4),
+];
+
+const FRUIT_LAYOUT: [PlantTemplateTile; 6] = [
+    PlantTemplateTile::new(ConditionTag::TreeTile, Some("TWIGS"), 0),
+    PlantTemplateTile::new(ConditionTag::TreeTile, Some("BRANCHES"), 1),
+    PlantTemplateTile::new(ConditionTag::Sapling, None, 2),
+    PlantTemplateTile::new(ConditionTag::Shrub, None, 3),
+    PlantTemplateTile::new(ConditionTag::Crop, None, 4),
+    PlantTemplateTile::new(ConditionTag::Picked, None, 5),
+];
+
+impl PlantGraphicTemplateTag {
+    /// The number of growth stages (rows) in the template.
+    #[must_use]
+    pub const fn get_stage_count(self) -> u8 {
+        match self {
+            Self::StandardLeaves | Self::StandardFruit1 | Self::StandardFlowers1 => 1,
+            Self::StandardFruit2 | Self::StandardFlowers2 => 2,
+            Self::StandardFruit3 | Self::StandardFlowers3 => 3,
+            Self::StandardFruit4 | Self::StandardFlowers4 => 4,
+        }
+    }
+    /// The tiles in each row (growth stage) of the template.
+    #[must_use]
+    pub const fn get_layout(self) -> &'static [PlantTemplateTile] {
+        match self {
+            Self::StandardLeaves => &LEAVES_LAYOUT,
+            Self::StandardFlowers1
+            | Self::StandardFlowers2
+            | Self::StandardFlowers3
+            | Self::StandardFlowers4 => &FLOWERS_LAYOUT,
+            Self::StandardFruit1
+            | Self::StandardFruit2
+            | Self::StandardFruit3
+            | Self::StandardFruit4 => &FRUIT_LAYOUT,
+        }
+    }
+}
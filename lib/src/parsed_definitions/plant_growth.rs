@@ -69,6 +69,78 @@ impl PlantGrowth {
             ..Self::default()
         }
     }
+    /// Get the name of the growth
+    ///
+    /// # Returns
+    ///
+    /// The name of the growth
+    #[must_use]
+    pub const fn get_name(&self) -> &Name {
+        &self.name
+    }
+    /// Get the type of the growth
+    ///
+    /// # Returns
+    ///
+    /// The type of the growth (leaves, fruit, etc.)
+    #[must_use]
+    pub const fn get_growth_type(&self) -> &PlantGrowthTypeTag {
+        &self.growth_type
+    }
+    /// Get the item the growth is
+    ///
+    /// # Returns
+    ///
+    /// The item token of the growth (`GROWTH_ITEM`)
+    #[must_use]
+    pub fn get_item(&self) -> &str {
+        &self.item
+    }
+    /// Get the density of the growth on the plant
+    ///
+    /// # Returns
+    ///
+    /// The density of the growth, if it has one
+    #[must_use]
+    pub const fn get_density(&self) -> Option<u32> {
+        self.density
+    }
+    /// Get when the growth is present during the year
+    ///
+    /// # Returns
+    ///
+    /// The first and last tick of the year the growth is present, if it has them
+    #[must_use]
+    pub const fn get_timing(&self) -> Option<[u32; 2]> {
+        self.timing
+    }
+    /// Get where on the trunk of a tree the growth can be
+    ///
+    /// # Returns
+    ///
+    /// The minimum and maximum trunk height percentage, if the growth has them
+    #[must_use]
+    pub const fn get_trunk_height_percentage(&self) -> Option<[i32; 2]> {
+        self.trunk_height_percentage
+    }
+    /// Get how the growth is displayed (`GROWTH_PRINT`)
+    ///
+    /// # Returns
+    ///
+    /// The print of the growth, if it has one
+    #[must_use]
+    pub fn get_print(&self) -> Option<&str> {
+        self.print.as_deref()
+    }
+    /// Get the tags of the growth
+    ///
+    /// # Returns
+    ///
+    /// The tags of the growth
+    #[must_use]
+    pub fn get_tags(&self) -> &[PlantGrowthTag] {
+        self.tags.as_deref().unwrap_or_default()
+    }
     /// Parses a tag and value into the plant growth
     ///
     /// # Arguments
@@ -85,6 +85,24 @@ pub struct Position {
 }
 
 impl Position {
+    /// Get the identifier of the position
+    ///
+    /// # Returns
+    ///
+    /// * `&str` - The identifier of the position
+    #[must_use]
+    pub fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// Get the name of the position
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&Name>` - The name of the position, if it has one
+    #[must_use]
+    pub const fn get_name(&self) -> Option<&Name> {
+        self.name.as_ref()
+    }
     /// Creates a new Position struct with the given identifier
     ///
     /// # Arguments
@@ -106,6 +106,11 @@ impl RawObject for SelectCreature {
     fn clean_self(&mut self) {
         *self = self.cleaned();
     }
+    fn set_metadata_hidden(&mut self, hidden: bool) {
+        if let Some(metadata) = self.metadata.as_mut() {
+            metadata.set_hidden(hidden);
+        }
+    }
     fn get_identifier(&self) -> &str {
         &self.identifier
     }
@@ -113,6 +113,24 @@ impl Shrub {
             ..Self::default()
         }
     }
+    /// The tile used for the plant when it is wild, alive, and has no growths.
+    ///
+    /// # Returns
+    ///
+    /// * `u8` - The CP437 code of the tile (defaults to 34).
+    #[must_use]
+    pub fn get_shrub_tile(&self) -> u8 {
+        self.shrub_tile.unwrap_or(34)
+    }
+    /// The color of the plant when it is alive, wild, and has no growths.
+    ///
+    /// # Returns
+    ///
+    /// * `Color` - The color of the plant (defaults to 2:0:0).
+    #[must_use]
+    pub fn get_shrub_color(&self) -> Color {
+        self.shrub_color.clone().unwrap_or(Color::new(2, 0, 0))
+    }
 
     /// Function to "clean" the raw. This is used to remove any empty list or strings,
     /// and to remove any default values. By "removing" it means setting the value to None.
@@ -6,8 +6,8 @@ use tracing::warn;
 use crate::{
     default_checks,
     dimensions::Dimensions,
-    raw_definitions::{CONDITION_TOKENS, GRAPHIC_TYPE_TOKENS},
-    tags::{ColorModificationTag, ConditionTag, GraphicTypeTag},
+    raw_definitions::{CONDITION_TOKENS, GRAPHIC_TYPE_TOKENS, PLANT_GRAPHIC_TEMPLATE_TOKENS},
+    tags::{ColorModificationTag, ConditionTag, GraphicTypeTag, PlantGraphicTemplateTag},
 };
 
 /// A struct representing a sprite graphic.
@@ -32,6 +32,8 @@ pub struct SpriteGraphic {
     target_identifier: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     extra_descriptor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    growth_stage: Option<u8>,
 }
 
 impl SpriteGraphic {
@@ -44,6 +46,144 @@ impl SpriteGraphic {
     pub fn get_tile_page_id(&self) -> &str {
         self.tile_page_id.as_str()
     }
+    /// Get the primary condition.
+    ///
+    /// # Returns
+    ///
+    /// The condition under which the sprite is used.
+    #[must_use]
+    pub const fn get_primary_condition(&self) -> ConditionTag {
+        self.primary_condition
+    }
+    /// Get the secondary condition (if any).
+    ///
+    /// # Returns
+    ///
+    /// The secondary condition, or `None` if there isn't one.
+    #[must_use]
+    pub fn get_secondary_condition(&self) -> Option<ConditionTag> {
+        self.secondary_condition
+            .filter(|condition| *condition != ConditionTag::None)
+    }
+    /// Get the offset of the sprite on the tile page (in tiles).
+    ///
+    /// For large images, this is the top-left tile.
+    ///
+    /// # Returns
+    ///
+    /// The offset of the sprite.
+    #[must_use]
+    pub const fn get_offset(&self) -> Dimensions {
+        self.offset
+    }
+    /// Get the second offset of the sprite on the tile page (in tiles).
+    ///
+    /// For large images, this is the bottom-right tile (inclusive).
+    ///
+    /// # Returns
+    ///
+    /// The second offset, or `None` if the sprite is a single tile.
+    #[must_use]
+    pub const fn get_offset2(&self) -> Option<Dimensions> {
+        self.offset2
+    }
+    /// Whether the sprite is a large image (spanning more than one tile).
+    ///
+    /// # Returns
+    ///
+    /// `true` if the sprite is a large image.
+    #[must_use]
+    pub fn is_large_image(&self) -> bool {
+        self.large_image.unwrap_or(false)
+    }
+    /// Get the extra descriptor (e.g. the tree part of a `TREE_TILE`).
+    ///
+    /// # Returns
+    ///
+    /// The extra descriptor, or `None` if there isn't one.
+    #[must_use]
+    pub fn get_extra_descriptor(&self) -> Option<&str> {
+        self.extra_descriptor.as_deref()
+    }
+    /// Get the growth stage the sprite is for (for sprites expanded from a plant graphic template).
+    ///
+    /// # Returns
+    ///
+    /// The growth stage (starting at 1), or `None` if the sprite isn't for a growth stage.
+    #[must_use]
+    pub const fn get_growth_stage(&self) -> Option<u8> {
+        self.growth_stage
+    }
+    /// Get the plant graphic template this sprite stands for (e.g. `STANDARD_LEAVES`).
+    ///
+    /// # Returns
+    ///
+    /// The template, or `None` if the sprite isn't a plant graphic template.
+    #[must_use]
+    pub fn get_plant_graphic_template(&self) -> Option<PlantGraphicTemplateTag> {
+        if self.primary_condition != ConditionTag::CopyOfTemplate {
+            return None;
+        }
+        let (key, _) = self.tile_page_id.split_once(':')?;
+        PLANT_GRAPHIC_TEMPLATE_TOKENS.get(key).copied()
+    }
+    /// Expand a plant graphic template into the sprites it stands for: one for each tile of the
+    /// template's layout, in each growth stage.
+    ///
+    /// The sprites have the condition of the tile (e.g. `Shrub`), the tree part as the extra
+    /// descriptor (for `TreeTile` sprites) and the growth stage.
+    ///
+    /// # Returns
+    ///
+    /// The expanded sprites, or `None` if the sprite isn't a plant graphic template (or its tile
+    /// page and offset can't be read).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dfraw_parser::{tags::{ConditionTag, GraphicTypeTag}, SpriteGraphic};
+    ///
+    /// let template =
+    ///     SpriteGraphic::from_token("STANDARD_FRUIT_2", "PLANT_GROWTHS:0:4", GraphicTypeTag::Template)
+    ///         .unwrap();
+    /// let sprites = template.expand_plant_template().unwrap();
+    ///
+    /// // Six tiles in each of two growth stages
+    /// assert_eq!(sprites.len(), 12);
+    /// let picked = &sprites[11];
+    /// assert_eq!(picked.get_primary_condition(), ConditionTag::Picked);
+    /// assert_eq!(picked.get_tile_page_id(), "PLANT_GROWTHS");
+    /// assert_eq!((picked.get_offset().get_x(), picked.get_offset().get_y()), (5, 5));
+    /// assert_eq!(picked.get_growth_stage(), Some(2));
+    /// ```
+    #[must_use]
+    pub fn expand_plant_template(&self) -> Option<Vec<Self>> {
+        // [STANDARD_FRUIT_2:PLANT_GROWTHS:0:4]
+        // (template, tile_page_id, offset_x, offset_y) kept as the tile page id
+        let template = self.get_plant_graphic_template()?;
+        let mut split = self.tile_page_id.split(':').skip(1);
+        let tile_page_id = split.next()?;
+        let origin_x: i32 = split.next()?.parse().ok()?;
+        let origin_y: i32 = split.next()?.parse().ok()?;
+
+        let mut sprites = Vec::new();
+        for stage in 1..=template.get_stage_count() {
+            for tile in template.get_layout() {
+                sprites.push(Self {
+                    primary_condition: tile.condition,
+                    tile_page_id: String::from(tile_page_id),
+                    offset: Dimensions::from_xy(
+                        origin_x + tile.column,
+                        origin_y + i32::from(stage) - 1,
+                    ),
+                    extra_descriptor: tile.tree_part.map(String::from),
+                    growth_stage: Some(stage),
+                    ..Self::default()
+                });
+            }
+        }
+        Some(sprites)
+    }
     /// Create a new sprite graphic by parsing a token.
     ///
     /// # Arguments
@@ -116,6 +256,49 @@ impl SpriteGraphic {
             }
         }
     }
+    /// Parse the value of a `TREE_TILE` token into a sprite graphic.
+    ///
+    /// The tree part (e.g. `TRUNK` or `BRANCHES`) is kept as the extra descriptor, and can come
+    /// before the tile page or after the offset.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value of the token (e.g. `TRUNK:TREES_OAK:0:2`).
+    ///
+    /// # Returns
+    ///
+    /// An option containing the sprite graphic.
+    #[must_use]
+    pub fn from_tree_tile_value(value: &str) -> Option<Self> {
+        // [TREE_TILE:  TRUNK:      TREES_OAK:  0:  2]
+        // [TREE_TILE:  TREES_OAK:  0:  2:  TRUNK]
+        //             (tree_part)  tile_page_id  x   y  (tree_part)
+        let split: Vec<&str> = value.split(':').collect();
+        let is_offset = |index: usize| split.get(index).is_some_and(|v| v.parse::<i32>().is_ok());
+
+        let (tree_part, tile_page_id, offset_index) = if is_offset(1) && is_offset(2) {
+            (split.get(3..).map(|rest| rest.join(":")), split[0], 1)
+        } else if is_offset(2) && is_offset(3) {
+            (Some(String::from(split[0])), split[1], 2)
+        } else {
+            warn!(
+                "from_tree_tile_value: Failed to find the tile offset in {}",
+                value
+            );
+            return None;
+        };
+
+        let offset_x: i32 = split[offset_index].parse().ok()?;
+        let offset_y: i32 = split[offset_index + 1].parse().ok()?;
+
+        Some(Self {
+            primary_condition: ConditionTag::TreeTile,
+            tile_page_id: String::from(tile_page_id),
+            offset: Dimensions::from_xy(offset_x, offset_y),
+            extra_descriptor: tree_part.filter(|part| !part.is_empty()),
+            ..Self::default()
+        })
+    }
     fn parse_plant_from_token(token: &str) -> Option<Self> {
         // [SHRUB:PLANT_STANDARD:0:0]
         // [PICKED:PLANT_STANDARD:1:0]
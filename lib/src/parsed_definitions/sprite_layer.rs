@@ -30,6 +30,55 @@ impl SpriteLayer {
     pub fn get_tile_page_id(&self) -> &str {
         self.tile_page_id.as_str()
     }
+    /// Returns the name of the `SpriteLayer`.
+    ///
+    /// # Returns
+    ///
+    /// * `&str` - The name of the `SpriteLayer`.
+    #[must_use]
+    pub fn get_layer_name(&self) -> &str {
+        self.layer_name.as_str()
+    }
+    /// Returns the offset of the `SpriteLayer` on the tile page (in tiles).
+    ///
+    /// For large images, this is the top-left tile.
+    ///
+    /// # Returns
+    ///
+    /// * `Dimensions` - The offset of the `SpriteLayer`.
+    #[must_use]
+    pub const fn get_offset(&self) -> Dimensions {
+        self.offset
+    }
+    /// Returns the second offset of the `SpriteLayer` on the tile page (in tiles).
+    ///
+    /// For large images, this is the bottom-right tile (inclusive).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Dimensions>` - The second offset, or `None` if the layer is a single tile.
+    #[must_use]
+    pub const fn get_offset2(&self) -> Option<Dimensions> {
+        self.offset_2
+    }
+    /// Returns whether the `SpriteLayer` is a large image (spanning more than one tile).
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `true` if the `SpriteLayer` is a large image.
+    #[must_use]
+    pub fn is_large_image(&self) -> bool {
+        self.large_image.unwrap_or(false)
+    }
+    /// Returns the conditions of the `SpriteLayer`, with the value given for each condition.
+    ///
+    /// # Returns
+    ///
+    /// * `&[(ConditionTag, String)]` - The conditions of the `SpriteLayer`.
+    #[must_use]
+    pub fn get_conditions(&self) -> &[(ConditionTag, String)] {
+        self.conditions.as_deref().unwrap_or_default()
+    }
     /// Parse a condition token into a `LayerCondition`.
     ///
     /// # Parameters
@@ -0,0 +1,92 @@
+//! Contains the `SpriteLayerGroup` struct and associated functions.
+
+use tracing::warn;
+
+use crate::{raw_definitions::CONDITION_TOKENS, sprite_layer::SpriteLayer, tags::ConditionTag};
+
+/// A group of `SpriteLayer`s in a layer set, with the conditions they share.
+///
+/// Layers between `[LAYER_GROUP]` and `[END_LAYER_GROUP]` (or the next `[LAYER_GROUP]`) form a
+/// group. Conditions given before the group's first layer, or after an `LG_CONDITION_BP`, belong to
+/// the group and apply to every layer in it. Layers which aren't in a `LAYER_GROUP` are kept in
+/// groups without conditions, so the drawing order of the layer set is preserved.
+#[allow(clippy::module_name_repetitions)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SpriteLayerGroup {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    conditions: Option<Vec<(ConditionTag, String)>>,
+    layers: Vec<SpriteLayer>,
+}
+
+impl SpriteLayerGroup {
+    /// Returns the conditions shared by every layer in the group.
+    ///
+    /// # Returns
+    ///
+    /// * `&[(ConditionTag, String)]` - The conditions of the group.
+    #[must_use]
+    pub fn get_conditions(&self) -> &[(ConditionTag, String)] {
+        self.conditions.as_deref().unwrap_or_default()
+    }
+    /// Returns the layers in the group, in drawing order.
+    ///
+    /// # Returns
+    ///
+    /// * `&[SpriteLayer]` - The layers of the group.
+    #[must_use]
+    pub const fn get_layers(&self) -> &[SpriteLayer] {
+        self.layers.as_slice()
+    }
+    /// Add a layer to the end of the group.
+    ///
+    /// # Parameters
+    ///
+    /// * `layer` - The layer to add.
+    pub fn add_layer(&mut self, layer: SpriteLayer) {
+        self.layers.push(layer);
+    }
+    /// Returns the last layer in the group, for attaching conditions to it.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&mut SpriteLayer>` - The last layer, or `None` if the group has no layers yet.
+    pub fn last_layer_mut(&mut self) -> Option<&mut SpriteLayer> {
+        self.layers.last_mut()
+    }
+    /// Parse a condition token into a condition shared by the group.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The key of the condition token.
+    /// * `value` - The value of the condition token.
+    pub fn parse_condition_token(&mut self, key: &str, value: &str) {
+        if let Some(condition) = CONDITION_TOKENS.get(key) {
+            self.conditions
+                .get_or_insert_with(Vec::new)
+                .push((*condition, String::from(value)));
+        } else {
+            warn!(
+                "Failed to parse {} as LayerGroup condition, unknown key {}",
+                value, key
+            );
+        }
+    }
+    /// Function to "clean" the group. This is used to remove any empty list or strings,
+    /// and to remove any default values. By "removing" it means setting the value to None.
+    ///
+    /// # Returns
+    ///
+    /// * `SpriteLayerGroup` - The cleaned `SpriteLayerGroup`.
+    #[must_use]
+    pub fn cleaned(&self) -> Self {
+        Self {
+            conditions: self
+                .conditions
+                .as_ref()
+                .filter(|conditions| !conditions.is_empty())
+                .cloned(),
+            layers: self.layers.iter().map(SpriteLayer::cleaned).collect(),
+        }
+    }
+}
@@ -3,6 +3,7 @@
 use tracing::{debug, warn};
 
 use crate::{
+    creature_effect::CreatureEffect,
     default_checks,
     raw_definitions::{CREATURE_EFFECT_TOKENS, SYNDROME_TOKENS},
     tags::SyndromeTag,
@@ -38,6 +39,11 @@ pub struct Syndrome {
     #[serde(skip_serializing_if = "Option::is_none")]
     tags: Option<Vec<SyndromeTag>>,
 
+    /// The creature effects (`CE_*` tokens) of the syndrome, in the order they are listed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    effects: Option<Vec<CreatureEffect>>,
+
+    /// The values of `CE` tokens (e.g. `PERIODIC` or `COUNTER_TRIGGER` conditions)
     #[serde(skip_serializing_if = "Option::is_none")]
     conditions: Option<Vec<String>>,
 }
@@ -68,6 +74,69 @@ impl Syndrome {
             ..Self::default()
         }
     }
+    /// Get the name of the syndrome.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The name of the syndrome, if it has one
+    #[must_use]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// Get the identifier of the syndrome (`SYN_IDENTIFIER`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The identifier of the syndrome, if it has one
+    #[must_use]
+    pub fn get_identifier(&self) -> Option<&str> {
+        self.identifier.as_deref()
+    }
+    /// Get the creature effects of the syndrome.
+    ///
+    /// # Returns
+    ///
+    /// * `&[CreatureEffect]` - The creature effects, in the order they are listed
+    #[must_use]
+    pub fn get_effects(&self) -> &[CreatureEffect] {
+        self.effects.as_deref().unwrap_or_default()
+    }
+    /// Get the creature classes the syndrome affects (`SYN_AFFECTED_CLASS`).
+    ///
+    /// # Returns
+    ///
+    /// * `&[String]` - The affected creature classes
+    #[must_use]
+    pub fn get_affected_classes(&self) -> &[String] {
+        self.affected_classes.as_deref().unwrap_or_default()
+    }
+    /// Get the creature classes which are immune to the syndrome (`SYN_IMMUNE_CLASS`).
+    ///
+    /// # Returns
+    ///
+    /// * `&[String]` - The immune creature classes
+    #[must_use]
+    pub fn get_immune_classes(&self) -> &[String] {
+        self.immune_classes.as_deref().unwrap_or_default()
+    }
+    /// Get the creatures the syndrome affects (`SYN_AFFECTED_CREATURE`).
+    ///
+    /// # Returns
+    ///
+    /// * `&[(String, String)]` - The affected creatures and castes (the caste can be `ALL`)
+    #[must_use]
+    pub fn get_affected_creatures(&self) -> &[(String, String)] {
+        self.affected_creatures.as_deref().unwrap_or_default()
+    }
+    /// Get the creatures which are immune to the syndrome (`SYN_IMMUNE_CREATURE`).
+    ///
+    /// # Returns
+    ///
+    /// * `&[(String, String)]` - The immune creatures and castes (the caste can be `ALL`)
+    #[must_use]
+    pub fn get_immune_creatures(&self) -> &[(String, String)] {
+        self.immune_creatures.as_deref().unwrap_or_default()
+    }
 
     /// Function to "clean" the raw. This is used to remove any empty list or strings,
     /// and to remove any default values. By "removing" it means setting the value to None.
@@ -126,6 +195,13 @@ impl Syndrome {
                 cleaned.tags = None;
             }
         }
+        if let Some(effects) = &cleaned.effects {
+            if effects.is_empty() {
+                cleaned.effects = None;
+            } else {
+                cleaned.effects = Some(effects.iter().map(CreatureEffect::cleaned).collect());
+            }
+        }
         if let Some(conditions) = &cleaned.conditions {
             if conditions.is_empty() {
                 cleaned.conditions = None;
@@ -142,13 +218,13 @@ impl Syndrome {
     /// * `value` - The value of the tag
     #[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
     pub fn parse_tag(&mut self, key: &str, value: &str) {
-        if CREATURE_EFFECT_TOKENS.contains_key(key) {
-            if self.conditions.is_none() {
-                self.conditions = Some(Vec::new());
+        if let Some(effect) = CREATURE_EFFECT_TOKENS.get(key) {
+            if self.effects.is_none() {
+                self.effects = Some(Vec::new());
             }
 
-            if let Some(conditions) = self.conditions.as_mut() {
-                conditions.push(String::from(value));
+            if let Some(effects) = self.effects.as_mut() {
+                effects.push(CreatureEffect::from_value(effect.clone(), value));
             }
             return;
         }
@@ -325,6 +401,13 @@ impl Searchable for Syndrome {
             }
         }
 
+        // Effects
+        if let Some(effects) = &self.effects {
+            for effect in effects {
+                vec.push(format!("{:?}", effect.get_effect()));
+            }
+        }
+
         // Conditions
         if let Some(conditions) = &self.conditions {
             for condition in conditions {
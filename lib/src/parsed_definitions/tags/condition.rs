@@ -89,6 +89,8 @@ pub enum ConditionTag {
     CropR,
     /// Condition of being a dead shrub
     ShrubDead,
+    /// Condition of being a tile of a tree
+    TreeTile,
     /// Condition of not being a child
     NotChild,
     /// Condition of being at least so many hauled
@@ -133,6 +135,14 @@ pub enum ConditionTag {
     TissueNotShaped,
     /// Condition of being a swapped tissue
     TissueSwap,
+    /// Condition of being a specific body part (selects the body part for the `BP_` conditions)
+    BodyPart,
+    /// Condition of the body part being present
+    BodyPartPresent,
+    /// Condition of the body part being scarred
+    BodyPartScarred,
+    /// Condition of the body part having an appearance modifier in a range
+    BodyPartAppearanceModifierRange,
     /// Condition of being a specific layer (start layer definition)
     Layer,
     /// Condition of being a specific layer set of layers
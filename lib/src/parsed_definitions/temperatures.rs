@@ -54,6 +54,69 @@ impl Temperatures {
             && self.cold_damage_point.is_none()
             && self.material_fixed_temperature.is_none()
     }
+    /// Returns the specific heat of the material
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The specific heat, if the material has one
+    #[must_use]
+    pub const fn get_specific_heat(&self) -> Option<u32> {
+        self.specific_heat
+    }
+    /// Returns the ignition point of the material
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The ignition point, if the material has one
+    #[must_use]
+    pub const fn get_ignition_point(&self) -> Option<u32> {
+        self.ignition_point
+    }
+    /// Returns the melting point of the material
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The melting point, if the material has one
+    #[must_use]
+    pub const fn get_melting_point(&self) -> Option<u32> {
+        self.melting_point
+    }
+    /// Returns the boiling point of the material
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The boiling point, if the material has one
+    #[must_use]
+    pub const fn get_boiling_point(&self) -> Option<u32> {
+        self.boiling_point
+    }
+    /// Returns the heat damage point of the material
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The heat damage point, if the material has one
+    #[must_use]
+    pub const fn get_heat_damage_point(&self) -> Option<u32> {
+        self.heat_damage_point
+    }
+    /// Returns the cold damage point of the material
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The cold damage point, if the material has one
+    #[must_use]
+    pub const fn get_cold_damage_point(&self) -> Option<u32> {
+        self.cold_damage_point
+    }
+    /// Returns the fixed temperature of the material
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The fixed temperature, if the material has one
+    #[must_use]
+    pub const fn get_material_fixed_temperature(&self) -> Option<u32> {
+        self.material_fixed_temperature
+    }
     /// Updates the specific heat of the material
     ///
     /// # Arguments
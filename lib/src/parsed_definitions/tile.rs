@@ -1,23 +1,63 @@
 //! Tile definition for DF Classic
 
 use super::color::Color;
+use crate::constants::CP437_TO_UNICODE;
 
 #[allow(clippy::module_name_repetitions)]
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, specta::Type)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", from = "TileParts")]
 /// Representation of a character tile (literally a single character) that is used in DF Classic
+///
+/// The Unicode characters are worked out whenever a character is set (including when the tile is
+/// deserialized), so they always match the characters.
 pub struct Tile {
     character: String,
+    /// The Unicode character the game draws for `character`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unicode_character: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     alt_character: Option<String>,
+    /// The Unicode character the game draws for `alt_character`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unicode_alt_character: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     color: Option<Color>,
     #[serde(skip_serializing_if = "Option::is_none")]
     glow_character: Option<String>,
+    /// The Unicode character the game draws for `glow_character`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unicode_glow_character: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     glow_color: Option<Color>,
 }
 
+/// The parts of a serialized `Tile` which are read back (the Unicode characters are worked out
+/// again).
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TileParts {
+    character: String,
+    alt_character: Option<String>,
+    color: Option<Color>,
+    glow_character: Option<String>,
+    glow_color: Option<Color>,
+}
+
+impl From<TileParts> for Tile {
+    fn from(parts: TileParts) -> Self {
+        Self {
+            unicode_character: Self::to_unicode(&parts.character),
+            unicode_alt_character: parts.alt_character.as_deref().and_then(Self::to_unicode),
+            unicode_glow_character: parts.glow_character.as_deref().and_then(Self::to_unicode),
+            character: parts.character,
+            alt_character: parts.alt_character,
+            color: parts.color,
+            glow_character: parts.glow_character,
+            glow_color: parts.glow_color,
+        }
+    }
+}
+
 impl Tile {
     /// Sets the character of the tile
     ///
@@ -26,6 +66,7 @@ impl Tile {
     /// * `character` - The character to set
     pub fn set_character(&mut self, character: &str) {
         self.character = String::from(character);
+        self.unicode_character = Self::to_unicode(character);
     }
     /// Sets the alternate character of the tile
     ///
@@ -34,6 +75,7 @@ impl Tile {
     /// * `character` - The character to set
     pub fn set_alt_character(&mut self, character: &str) {
         self.alt_character = Some(String::from(character));
+        self.unicode_alt_character = Self::to_unicode(character);
     }
     /// Sets the color of the tile
     ///
@@ -58,6 +100,7 @@ impl Tile {
     /// * `character` - The character to set
     pub fn set_glow_character(&mut self, character: &str) {
         self.glow_character = Some(String::from(character));
+        self.unicode_glow_character = Self::to_unicode(character);
     }
     /// Returns whether the tile is the default tile
     ///
@@ -81,6 +124,82 @@ impl Tile {
     pub fn get_character(&self) -> &str {
         &self.character
     }
+    /// Returns the CP437 code of the tile's character.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u8>` - The CP437 code, or `None` if the character can't be read.
+    #[must_use]
+    pub fn get_character_code(&self) -> Option<u8> {
+        Self::parse_character_code(&self.character)
+    }
+    /// Parse a tile value from the raws into its CP437 code.
+    ///
+    /// Tiles are either a CP437 number (`219`) or a character between single quotes (`'X'`).
+    /// Raw files are read as Latin-1, so a quoted character's code point is its CP437 code.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The tile value (e.g. `'X'` or `219`).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u8>` - The CP437 code, or `None` if the value isn't a tile.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dfraw_parser::Tile;
+    ///
+    /// assert_eq!(Tile::parse_character_code("'X'"), Some(88));
+    /// assert_eq!(Tile::parse_character_code("219"), Some(219));
+    /// ```
+    #[must_use]
+    pub fn parse_character_code(value: &str) -> Option<u8> {
+        let mut quoted = value.chars();
+        if let (Some('\''), Some(character), Some('\''), None) =
+            (quoted.next(), quoted.next(), quoted.next(), quoted.next())
+        {
+            return u8::try_from(u32::from(character)).ok();
+        }
+        value.parse().ok()
+    }
+    /// Returns the tile's character as the Unicode character the game draws for it.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<char>` - The Unicode character, or `None` if the character can't be read.
+    #[must_use]
+    pub fn get_unicode_character(&self) -> Option<char> {
+        self.get_character_code().map(Self::code_to_unicode)
+    }
+    /// Convert a CP437 code into the Unicode character the game draws for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The CP437 code.
+    ///
+    /// # Returns
+    ///
+    /// * `char` - The Unicode character.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dfraw_parser::Tile;
+    ///
+    /// assert_eq!(Tile::code_to_unicode(1), '☺');
+    /// assert_eq!(Tile::code_to_unicode(88), 'X');
+    /// assert_eq!(Tile::code_to_unicode(219), '█');
+    /// ```
+    #[must_use]
+    pub const fn code_to_unicode(code: u8) -> char {
+        CP437_TO_UNICODE[code as usize]
+    }
+    /// Convert a tile value from the raws into the Unicode character the game draws for it.
+    fn to_unicode(value: &str) -> Option<String> {
+        Self::parse_character_code(value).map(|code| String::from(Self::code_to_unicode(code)))
+    }
     /// Returns the alternate character of the tile
     ///
     /// # Returns
@@ -1,6 +1,6 @@
 //! A module for the `TilePage` object.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use tracing::warn;
 
@@ -64,6 +64,33 @@ impl TilePage {
             ..Self::default()
         }
     }
+    /// Get the path to the image file of the `TilePage`.
+    ///
+    /// # Returns
+    ///
+    /// * `&Path` - The path to the image file.
+    #[must_use]
+    pub fn get_file(&self) -> &Path {
+        self.file.as_path()
+    }
+    /// Get the dimensions of a single tile (in pixels).
+    ///
+    /// # Returns
+    ///
+    /// * `Dimensions` - The dimensions of a tile.
+    #[must_use]
+    pub const fn get_tile_dim(&self) -> Dimensions {
+        self.tile_dim
+    }
+    /// Get the dimensions of the whole page (in pixels).
+    ///
+    /// # Returns
+    ///
+    /// * `Dimensions` - The dimensions of the page.
+    #[must_use]
+    pub const fn get_page_dim(&self) -> Dimensions {
+        self.page_dim
+    }
     /// Function to "clean" the creature. This is used to remove any empty list or strings,
     /// and to remove any default values. By "removing" it means setting the value to None.
     ///
@@ -120,6 +147,11 @@ impl RawObject for TilePage {
     fn clean_self(&mut self) {
         *self = self.cleaned();
     }
+    fn set_metadata_hidden(&mut self, hidden: bool) {
+        if let Some(metadata) = self.metadata.as_mut() {
+            metadata.set_hidden(hidden);
+        }
+    }
 
     fn parse_tag(&mut self, key: &str, value: &str) {
         match TILE_PAGE_TOKENS.get(key).unwrap_or(&TilePageTag::Unknown) {
@@ -8,6 +8,7 @@ use crate::{
     name::Name,
     raw_definitions::TREE_TOKENS,
     tags::{TreeTag, TwigPlacementTag},
+    tile::Tile,
 };
 
 /// A struct representing a tree.
@@ -146,6 +147,27 @@ impl Tree {
             ..Default::default()
         }
     }
+    /// The tile used for trees of this type on the world map.
+    ///
+    /// # Returns
+    ///
+    /// The CP437 code of the tile (defaults to 24).
+    #[must_use]
+    pub fn get_tree_tile(&self) -> u8 {
+        self.tree_tile
+            .as_deref()
+            .and_then(Tile::parse_character_code)
+            .unwrap_or(24)
+    }
+    /// The color of trees of this type on the map.
+    ///
+    /// # Returns
+    ///
+    /// The color of the tree (defaults to 2:0:0).
+    #[must_use]
+    pub fn get_tree_color(&self) -> Color {
+        self.tree_color.clone().unwrap_or(Color::new(2, 0, 0))
+    }
 
     /// Function to "clean" the raw. This is used to remove any empty list or strings,
     /// and to remove any default values. By "removing" it means setting the value to None.
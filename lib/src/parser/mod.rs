@@ -2,13 +2,16 @@
 
 pub mod info_file;
 pub mod parse;
+pub mod parse_iter;
 pub mod raw_location;
 pub mod raw_module;
+mod resolve;
 pub mod results;
 
 pub use info_file::parse_module_info_file_in_module;
 pub use info_file::parse_module_info_files;
 pub use info_file::parse_module_info_files_at_location;
+pub use parse_iter::{parse_iter, ParseIter};
 pub use raw_location::parse_location;
 pub use raw_module::parse_module;
 pub use results::ParseResult;
@@ -1,18 +1,18 @@
 use std::path::Path;
 
-use tracing::{error, info};
+use tracing::info;
 
 use crate::{
+    cache::parse_raw_file,
     legends_export,
-    metadata::{ObjectType, ParserOptions, RawModuleLocation},
+    metadata::{ParserOptions, RawModuleLocation},
     parser::{parse_location, parse_module},
-    reader::{parse_raw_file, UnprocessedRaw},
-    traits::RawObject,
-    utilities::{clone_raw_object_box, log_summary, summarize_raws, validate_options},
-    Creature, CreatureVariation, ParserError,
+    reader::UnprocessedRaw,
+    utilities::{log_summary, summarize_raws, validate_options},
+    ParserError,
 };
 
-use super::{info_file::parse_module_info_files, ParseResult};
+use super::{info_file::parse_module_info_files, resolve::resolve_unprocessed_raws, ParseResult};
 
 #[allow(clippy::too_many_lines)]
 /// Given the supplied `ParserOptions`, parse the raws and return a vector of boxed dynamic raw objects.
@@ -133,115 +133,7 @@ pub fn parse(options: &ParserOptions) -> Result<ParseResult, ParserError> {
     }
 
     // Resolve the unprocessed creatures
-    // Prerequisites: build a list of creature variations
-    let creature_variations: Vec<CreatureVariation> = results
-        .raws
-        .iter()
-        .filter_map(|raw| {
-            if raw.get_type() == &ObjectType::CreatureVariation {
-                if let Some(cv) = raw
-                    .as_ref()
-                    .as_any()
-                    .downcast_ref::<CreatureVariation>()
-                    .cloned()
-                {
-                    return Some(cv);
-                }
-                error!(
-                    "Matched CreatureVariation but failed to downcast for {}",
-                    raw.get_identifier()
-                );
-            }
-            None
-        })
-        .collect();
-
-    info!(
-        "Resolving {} unprocessed creatures using {} creature variation definitions",
-        unprocessed_raws.len(),
-        creature_variations.len()
-    );
-
-    // Write the unprocessed raws to a file
-    // let _ = serde_json::to_writer_pretty(
-    //     std::fs::File::create("unprocessed_raws.json").unwrap(),
-    //     &unprocessed_raws,
-    // );
-
-    let mut simple_unprocessed: Vec<UnprocessedRaw> = Vec::new();
-    let mut complex_unprocessed: Vec<UnprocessedRaw> = Vec::new();
-
-    // Split the unprocessed raws into simple and complex
-    for unprocessed_raw in unprocessed_raws {
-        if unprocessed_raw.is_simple() {
-            simple_unprocessed.push(unprocessed_raw);
-        } else {
-            complex_unprocessed.push(unprocessed_raw);
-        }
-    }
-
-    // Resolve the simple creatures first
-    let resolved_simple_creatures: Vec<Creature> = simple_unprocessed
-        .iter_mut()
-        .filter(|raw| raw.raw_type() == ObjectType::Creature)
-        .filter_map(|raw| {
-            match raw.resolve(creature_variations.as_slice(), results.raws.as_slice()) {
-                Ok(c) => Some(c),
-                Err(e) => {
-                    error!(
-                        "Unable to resolve simple creature {}: {:?}",
-                        raw.get_identifier(),
-                        e
-                    );
-                    None
-                }
-            }
-        })
-        .map(|c| clone_raw_object_box(&c))
-        .filter_map(|c| {
-            c.as_ref().as_any().downcast_ref::<Creature>().map_or_else(
-                || {
-                    error!("Downcast failed for simple creature {}", c.get_identifier());
-                    None
-                },
-                |creature| Some(creature.clone()),
-            )
-        })
-        .collect();
-
-    info!(
-        "Resolved {} simple creatures",
-        resolved_simple_creatures.len()
-    );
-
-    results.raws.extend(
-        resolved_simple_creatures
-            .iter()
-            .map(|c| Box::new(c.clone()) as Box<dyn RawObject>),
-    );
-
-    // Now we can do the second pass through the unprocessed creatures, but add the complex creatures
-    // to the results.raws vector as they are resolved.
-    let mut resolved_complex_creatures = 0_usize;
-    for unprocessed_raw in &mut complex_unprocessed {
-        if unprocessed_raw.raw_type() == ObjectType::Creature {
-            match unprocessed_raw.resolve(creature_variations.as_slice(), results.raws.as_slice()) {
-                Ok(c) => {
-                    resolved_complex_creatures += 1;
-                    results.raws.push(clone_raw_object_box(&c));
-                }
-                Err(e) => {
-                    error!(
-                        "Unable to resolve complex creature {}: {:?}",
-                        unprocessed_raw.get_identifier(),
-                        e
-                    );
-                }
-            }
-        }
-    }
-
-    info!("Resolved {resolved_complex_creatures} complex creatures");
+    resolve_unprocessed_raws(unprocessed_raws, &mut results.raws);
 
     // Parse the info modules
     results.info_files = parse_module_info_files(&options)?;
@@ -0,0 +1,266 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    path::{Path, PathBuf},
+};
+
+use tracing::{debug, info};
+
+use crate::{
+    cache::parse_raw_file,
+    legends_export,
+    metadata::{ObjectType, ParserOptions, RawModuleLocation},
+    reader::UnprocessedRaw,
+    traits::RawObject,
+    utilities::{clone_raw_object_box, log_summary, subdirectories, validate_options},
+    ParserError,
+};
+
+use super::{raw_module::module_raw_files, resolve::resolve_unprocessed_raws};
+
+/// A piece of work that the `ParseIter` still has to do, in the order `parse` would do it.
+enum Pending {
+    /// A module location (e.g. `data/vanilla`), which expands into its modules.
+    Location(PathBuf),
+    /// A raw module, which expands into its raw files.
+    ///
+    /// Errors are only reported for modules which were requested directly.
+    Module { path: PathBuf, required: bool },
+    /// A raw file to parse.
+    ///
+    /// Errors are only reported for raw files which were requested directly.
+    File { path: PathBuf, required: bool },
+    /// A legends export to parse.
+    LegendsExport(PathBuf),
+    /// Resolve the creatures once everything else is parsed.
+    ResolveCreatures,
+}
+
+/// An iterator over the raws described by a `ParserOptions`. Created by `parse_iter`.
+///
+/// Raws which don't need resolving (plants, inorganics, graphics, etc.) are yielded as soon as the
+/// file they are in has been parsed. Creatures need every creature variation and every creature
+/// they copy tags from, so they are yielded last, after all of the files have been parsed.
+///
+/// The raws are yielded in the same order that `parse` puts them in `ParseResult::raws`.
+pub struct ParseIter {
+    /// The (validated) options for parsing.
+    options: ParserOptions,
+    /// The work left to do.
+    pending: VecDeque<Pending>,
+    /// Parsed raws waiting to be yielded.
+    ready: VecDeque<Box<dyn RawObject>>,
+    /// The unprocessed raws (creatures), which are resolved at the end.
+    unprocessed_raws: Vec<UnprocessedRaw>,
+    /// The creatures and creature variations seen so far, which are needed to resolve creatures.
+    creature_sources: Vec<Box<dyn RawObject>>,
+    /// A count of the yielded raws by type, for the summary.
+    summary: HashMap<ObjectType, usize>,
+    /// An error to yield before anything else (i.e. invalid options).
+    error: Option<ParserError>,
+}
+
+/// Given the supplied `ParserOptions`, parse the raws and return an iterator over them.
+///
+/// This parses the same raws as `parse`, but yields them as they become available instead of
+/// collecting them all first. This is useful for streaming the results somewhere (e.g. as NDJSON)
+/// or showing partial results while a large set of modules is parsed.
+///
+/// Note: This does not parse the `info.txt` files for the modules. Use `parse_module_info_files`
+/// for that.
+///
+/// Errors are yielded as items. An error for a location, module, raw file or legends export which
+/// would stop `parse` is yielded in its place, and the iterator then carries on with the rest of
+/// the work, so callers can decide whether to stop or continue. Invalid options are yielded as a
+/// single error.
+///
+/// # Arguments
+///
+/// * `options` - A reference to a `ParserOptions` struct that contains the parsing options.
+///
+/// # Returns
+///
+/// An iterator of boxed dynamic raw objects (or errors).
+///
+/// # Example
+///
+/// ```rust
+/// use dfraw_parser::{metadata::ParserOptions, parse_iter};
+///
+/// let options = ParserOptions::new("path/to/dwarf_fortress");
+/// for raw in parse_iter(&options) {
+///     match raw {
+///         Ok(raw) => println!("{}", raw.get_object_id()),
+///         Err(e) => eprintln!("{e}"),
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn parse_iter(options: &ParserOptions) -> ParseIter {
+    let mut iter = ParseIter {
+        options: options.clone(),
+        pending: VecDeque::new(),
+        ready: VecDeque::new(),
+        unprocessed_raws: Vec::new(),
+        creature_sources: Vec::new(),
+        summary: HashMap::new(),
+        error: None,
+    };
+
+    // Guard against invalid paths
+    match validate_options(options) {
+        Ok(options) => {
+            iter.pending = ParseIter::plan(&options);
+            iter.options = options;
+        }
+        Err(e) => iter.error = Some(e),
+    }
+
+    iter
+}
+
+impl ParseIter {
+    /// Build the list of work to do, in the same order as `parse`.
+    fn plan(options: &ParserOptions) -> VecDeque<Pending> {
+        let mut pending = VecDeque::new();
+
+        // Locations can only contain the predefined locations.
+        if !options.locations_to_parse.is_empty() {
+            let target_path = Path::new(&options.dwarf_fortress_directory);
+            let data_path = target_path.join("data");
+
+            for (location, path) in [
+                (RawModuleLocation::Vanilla, data_path.join("vanilla")),
+                (
+                    RawModuleLocation::InstalledMods,
+                    data_path.join("installed_mods"),
+                ),
+                (RawModuleLocation::Mods, target_path.join("mods")),
+            ] {
+                if options.locations_to_parse.contains(&location) {
+                    info!("Dispatching parse for {location} raws");
+                    pending.push_back(Pending::Location(path));
+                }
+            }
+        }
+
+        for raw_module in &options.raw_modules_to_parse {
+            // Modules without an info.txt are skipped, like in `parse`.
+            if raw_module.join("info.txt").exists() {
+                pending.push_back(Pending::Module {
+                    path: raw_module.clone(),
+                    required: true,
+                });
+            }
+        }
+
+        for raw_file in &options.raw_files_to_parse {
+            pending.push_back(Pending::File {
+                path: raw_file.clone(),
+                required: true,
+            });
+        }
+
+        for legends_export in &options.legends_exports_to_parse {
+            pending.push_back(Pending::LegendsExport(legends_export.clone()));
+        }
+
+        pending.push_back(Pending::ResolveCreatures);
+        pending
+    }
+    /// Queue up parsed raws to be yielded, keeping a copy of any that are needed to resolve
+    /// creatures later.
+    fn add_parsed_raws(&mut self, parsed_raws: Vec<Box<dyn RawObject>>) {
+        for raw in parsed_raws {
+            if matches!(
+                raw.get_type(),
+                ObjectType::Creature | ObjectType::CreatureVariation
+            ) {
+                self.creature_sources.push(clone_raw_object_box(&raw));
+            }
+            self.ready.push_back(raw);
+        }
+    }
+    /// Do the next piece of pending work.
+    ///
+    /// Returns an error if the work failed in a way that `parse` would report.
+    fn do_pending(&mut self, pending: Pending) -> Result<(), ParserError> {
+        match pending {
+            Pending::Location(path) => {
+                let raw_modules = subdirectories(path)?;
+                debug!("Found {} raw modules in location", raw_modules.len());
+                // Modules go to the front (in order), so they are parsed before the next location.
+                for raw_module in raw_modules.into_iter().rev() {
+                    self.pending.push_front(Pending::Module {
+                        path: raw_module.path().to_path_buf(),
+                        required: false,
+                    });
+                }
+            }
+            Pending::Module { path, required } => {
+                info!(
+                    "Dispatching parse for module {:?}",
+                    path.file_name().unwrap_or_default()
+                );
+                match module_raw_files(&path, &self.options) {
+                    Ok(raw_files) => {
+                        for raw_file in raw_files.into_iter().rev() {
+                            self.pending.push_front(Pending::File {
+                                path: raw_file,
+                                required: false,
+                            });
+                        }
+                    }
+                    Err(e) if required => return Err(e),
+                    Err(e) => debug!("Skipping parsing module: {:?}", e),
+                }
+            }
+            Pending::File { path, required } => match parse_raw_file(&path, &self.options) {
+                Ok(file_parse_result) => {
+                    self.unprocessed_raws
+                        .extend(file_parse_result.unprocessed_raws);
+                    self.add_parsed_raws(file_parse_result.parsed_raws);
+                }
+                Err(e) if required => return Err(e),
+                Err(e) => debug!("Skipping parsing raw file: {:?}", e),
+            },
+            Pending::LegendsExport(path) => {
+                let parsed_raws = legends_export::parse(&path, &self.options)?;
+                self.add_parsed_raws(parsed_raws);
+            }
+            Pending::ResolveCreatures => {
+                let unprocessed_raws = std::mem::take(&mut self.unprocessed_raws);
+                let mut creature_sources = std::mem::take(&mut self.creature_sources);
+                let already_parsed = creature_sources.len();
+                resolve_unprocessed_raws(unprocessed_raws, &mut creature_sources);
+                self.ready.extend(creature_sources.drain(already_parsed..));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for ParseIter {
+    type Item = Result<Box<dyn RawObject>, ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.error.take() {
+            return Some(Err(error));
+        }
+        loop {
+            if let Some(raw) = self.ready.pop_front() {
+                *self.summary.entry(raw.get_type().clone()).or_insert(0) += 1;
+                return Some(Ok(raw));
+            }
+            let Some(pending) = self.pending.pop_front() else {
+                // Print a summary of what we parsed (sum by ObjectType), once.
+                if self.options.log_summary && !self.summary.is_empty() {
+                    log_summary(&std::mem::take(&mut self.summary));
+                }
+                return None;
+            };
+            if let Err(e) = self.do_pending(pending) {
+                return Some(Err(e));
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use std::path::{Path, PathBuf};
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use tracing::{debug, info};
 use walkdir::DirEntry;
 
@@ -14,6 +16,9 @@ use crate::{
 
 /// Parses the raws in the provided location path, and returns a vector of boxed dynamic raw objects.
 ///
+/// With the `parallel` feature, the modules in the location are parsed concurrently. The results
+/// are still merged in module order, so the output is the same as the sequential parse.
+///
 /// # Arguments
 ///
 /// * `location_path` - A reference to the path to parse.
@@ -46,9 +51,20 @@ pub fn parse_location<P: AsRef<Path>>(
             .unwrap_or(&RawModuleLocation::Unknown)
     );
 
-    // Loop over each module and parse it
-    for raw_module in raw_modules_in_location {
-        match parse_module(&raw_module.path(), options) {
+    // Parse each module
+    #[cfg(feature = "parallel")]
+    let module_results: Vec<_> = raw_modules_in_location
+        .par_iter()
+        .map(|raw_module| parse_module(&raw_module.path(), options))
+        .collect();
+    #[cfg(not(feature = "parallel"))]
+    let module_results = raw_modules_in_location
+        .iter()
+        .map(|raw_module| parse_module(&raw_module.path(), options));
+
+    // Merge the results in module order
+    for module_result in module_results {
+        match module_result {
             Ok(module_results) => {
                 results.extend(module_results.parsed_raws);
                 unprocessed_raws.extend(module_results.unprocessed_raws);
@@ -1,19 +1,24 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use tracing::{debug, info};
 use walkdir::WalkDir;
 
 use crate::{
+    cache::parse_raw_file,
     metadata::{ObjectType, ParserOptions},
-    reader::{parse_raw_file, FileParseResult, UnprocessedRaw},
+    reader::{FileParseResult, UnprocessedRaw},
     traits::RawObject,
     InfoFile, ParserError,
 };
 
-#[allow(clippy::too_many_lines)]
 /// The `parse_module` function parses raw files from a module directory and returns a vector of parsed
 /// objects.
 ///
+/// With the `parallel` feature, the raw files are parsed concurrently. The results are still
+/// merged in file order, so the output is the same as the sequential parse.
+///
 /// Arguments:
 ///
 /// * `module_path`: the path to the module directory that contains the raw files to parse.
@@ -30,6 +35,62 @@ pub fn parse_module<P: AsRef<Path>>(
     module_path: &P,
     options: &ParserOptions,
 ) -> Result<FileParseResult, ParserError> {
+    let mut results: Vec<Box<dyn RawObject>> = Vec::new();
+    let mut unprocessed_raws: Vec<UnprocessedRaw> = Vec::new();
+
+    let raw_files = module_raw_files(module_path, options)?;
+
+    #[cfg(feature = "parallel")]
+    let file_results: Vec<_> = raw_files
+        .par_iter()
+        .map(|file_path| parse_raw_file(file_path, options))
+        .collect();
+    #[cfg(not(feature = "parallel"))]
+    let file_results = raw_files
+        .iter()
+        .map(|file_path| parse_raw_file(file_path, options));
+
+    for file_result in file_results {
+        match file_result {
+            Ok(mut file_parse_results) => {
+                results.append(&mut file_parse_results.parsed_raws);
+                unprocessed_raws.append(&mut file_parse_results.unprocessed_raws);
+            }
+            Err(e) => {
+                debug!("Skipping parsing raw file: {:?}", e);
+            }
+        }
+    }
+
+    Ok(FileParseResult {
+        parsed_raws: results,
+        unprocessed_raws,
+    })
+}
+
+#[allow(clippy::too_many_lines)]
+/// The `module_raw_files` function lists the raw files in a module directory which `parse_module`
+/// would parse, in the order it would parse them.
+///
+/// The raw files in the `objects` directory come first, followed by the raw files in the `graphics`
+/// directory (only if graphics are included in the `options`).
+///
+/// Arguments:
+///
+/// * `module_path`: the path to the module directory that contains the raw files.
+/// * `options`: The parsing options which determine what to parse.
+///
+/// Returns:
+///
+/// The paths of the raw files in the module.
+///
+/// # Errors
+///
+/// * `ParserError::Io` - If we can't read the module's `info.txt` file
+pub fn module_raw_files<P: AsRef<Path>>(
+    module_path: &P,
+    options: &ParserOptions,
+) -> Result<Vec<PathBuf>, ParserError> {
     // Get information from the module info file
     let module_info_file_path = module_path.as_ref().join("info.txt");
     let module_info_file = match InfoFile::parse(&module_info_file_path) {
@@ -80,21 +141,12 @@ pub fn parse_module<P: AsRef<Path>>(
         parse_graphics = false;
     }
 
-    // Guard against having nothing to parse.
-    if !parse_graphics && !parse_objects {
-        return Ok(FileParseResult {
-            parsed_raws: Vec::new(),
-            unprocessed_raws: Vec::new(),
-        });
-    }
-
-    let mut results: Vec<Box<dyn RawObject>> = Vec::new();
-    let mut unprocessed_raws: Vec<UnprocessedRaw> = Vec::new();
+    let mut raw_files: Vec<PathBuf> = Vec::new();
 
-    // Parse the objects
+    // Collect the object raw files
     if parse_objects {
         info!(
-            "Parsing objects for {} v{}",
+            "Finding objects for {} v{}",
             module_info_file.get_identifier(),
             module_info_file.get_version(),
         );
@@ -111,24 +163,16 @@ pub fn parse_module<P: AsRef<Path>>(
                     .extension()
                     .map_or(false, |ext| ext.eq_ignore_ascii_case("txt"))
                 {
-                    match parse_raw_file(&file_path, options) {
-                        Ok(mut file_parse_results) => {
-                            results.append(&mut file_parse_results.parsed_raws);
-                            unprocessed_raws.append(&mut file_parse_results.unprocessed_raws);
-                        }
-                        Err(e) => {
-                            debug!("Skipping parsing objects: {:?}", e);
-                        }
-                    }
+                    raw_files.push(file_path.to_path_buf());
                 }
             }
         }
     }
 
-    // Parse the graphics
+    // Collect the graphics raw files
     if parse_graphics {
         info!(
-            "Parsing graphics for {} v{}",
+            "Finding graphics for {} v{}",
             module_info_file.get_identifier(),
             module_info_file.get_version(),
         );
@@ -145,22 +189,11 @@ pub fn parse_module<P: AsRef<Path>>(
                     .extension()
                     .map_or(false, |ext| ext.eq_ignore_ascii_case("txt"))
                 {
-                    match parse_raw_file(&file_path, options) {
-                        Ok(mut graphics) => {
-                            results.append(&mut graphics.parsed_raws);
-                            unprocessed_raws.append(&mut graphics.unprocessed_raws);
-                        }
-                        Err(e) => {
-                            debug!("Skipping parsing graphics: {:?}", e);
-                        }
-                    }
+                    raw_files.push(file_path.to_path_buf());
                 }
             }
         }
     }
 
-    Ok(FileParseResult {
-        parsed_raws: results,
-        unprocessed_raws,
-    })
+    Ok(raw_files)
 }
@@ -0,0 +1,134 @@
+use tracing::{error, info};
+
+use crate::{
+    metadata::ObjectType, reader::UnprocessedRaw, traits::RawObject,
+    utilities::clone_raw_object_box, Creature, CreatureVariation,
+};
+
+/// Resolve the unprocessed raws (creatures) into parsed raws, and add them to `raws`.
+///
+/// The creature variations are collected from `raws` first. Then the simple creatures (the ones
+/// without any `COPY_TAGS_FROM` or `APPLY_CREATURE_VARIATION`) are resolved, followed by the complex
+/// creatures. Each complex creature is added to `raws` as soon as it is resolved, so that later
+/// creatures can copy tags from it.
+///
+/// Only the creatures and creature variations in `raws` are used for the resolution, so callers
+/// can pass a vector holding just those.
+///
+/// Arguments:
+///
+/// * `unprocessed_raws`: The unprocessed raws to resolve.
+/// * `raws`: The parsed raws so far. The resolved creatures are appended to this.
+pub fn resolve_unprocessed_raws(
+    unprocessed_raws: Vec<UnprocessedRaw>,
+    raws: &mut Vec<Box<dyn RawObject>>,
+) {
+    // Prerequisites: build a list of creature variations
+    let creature_variations: Vec<CreatureVariation> = raws
+        .iter()
+        .filter_map(|raw| {
+            if raw.get_type() == &ObjectType::CreatureVariation {
+                if let Some(cv) = raw
+                    .as_ref()
+                    .as_any()
+                    .downcast_ref::<CreatureVariation>()
+                    .cloned()
+                {
+                    return Some(cv);
+                }
+                error!(
+                    "Matched CreatureVariation but failed to downcast for {}",
+                    raw.get_identifier()
+                );
+            }
+            None
+        })
+        .collect();
+
+    info!(
+        "Resolving {} unprocessed creatures using {} creature variation definitions",
+        unprocessed_raws.len(),
+        creature_variations.len()
+    );
+
+    // Write the unprocessed raws to a file
+    // let _ = serde_json::to_writer_pretty(
+    //     std::fs::File::create("unprocessed_raws.json").unwrap(),
+    //     &unprocessed_raws,
+    // );
+
+    let mut simple_unprocessed: Vec<UnprocessedRaw> = Vec::new();
+    let mut complex_unprocessed: Vec<UnprocessedRaw> = Vec::new();
+
+    // Split the unprocessed raws into simple and complex
+    for unprocessed_raw in unprocessed_raws {
+        if unprocessed_raw.is_simple() {
+            simple_unprocessed.push(unprocessed_raw);
+        } else {
+            complex_unprocessed.push(unprocessed_raw);
+        }
+    }
+
+    // Resolve the simple creatures first
+    let resolved_simple_creatures: Vec<Creature> = simple_unprocessed
+        .iter_mut()
+        .filter(|raw| raw.raw_type() == ObjectType::Creature)
+        .filter_map(
+            |raw| match raw.resolve(creature_variations.as_slice(), raws.as_slice()) {
+                Ok(c) => Some(c),
+                Err(e) => {
+                    error!(
+                        "Unable to resolve simple creature {}: {:?}",
+                        raw.get_identifier(),
+                        e
+                    );
+                    None
+                }
+            },
+        )
+        .map(|c| clone_raw_object_box(&c))
+        .filter_map(|c| {
+            c.as_ref().as_any().downcast_ref::<Creature>().map_or_else(
+                || {
+                    error!("Downcast failed for simple creature {}", c.get_identifier());
+                    None
+                },
+                |creature| Some(creature.clone()),
+            )
+        })
+        .collect();
+
+    info!(
+        "Resolved {} simple creatures",
+        resolved_simple_creatures.len()
+    );
+
+    raws.extend(
+        resolved_simple_creatures
+            .iter()
+            .map(|c| Box::new(c.clone()) as Box<dyn RawObject>),
+    );
+
+    // Now we can do the second pass through the unprocessed creatures, but add the complex creatures
+    // to the raws vector as they are resolved.
+    let mut resolved_complex_creatures = 0_usize;
+    for unprocessed_raw in &mut complex_unprocessed {
+        if unprocessed_raw.raw_type() == ObjectType::Creature {
+            match unprocessed_raw.resolve(creature_variations.as_slice(), raws.as_slice()) {
+                Ok(c) => {
+                    resolved_complex_creatures += 1;
+                    raws.push(clone_raw_object_box(&c));
+                }
+                Err(e) => {
+                    error!(
+                        "Unable to resolve complex creature {}: {:?}",
+                        unprocessed_raw.get_identifier(),
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    info!("Resolved {resolved_complex_creatures} complex creatures");
+}
@@ -0,0 +1,492 @@
+//! The conditions a query is made of, and how they are checked against the raws.
+
+use std::any::Any;
+
+use crate::{
+    body_size::BodySize,
+    caste::Caste,
+    creature::Creature,
+    entity::Entity,
+    inorganic::Inorganic,
+    material::Material,
+    metadata::{RawModuleLocation, OBJECT_TOKEN_MAP},
+    plant::Plant,
+    raw_definitions::{
+        BIOME_TOKENS, CASTE_TOKENS, CREATURE_TOKENS, ENTITY_TOKENS, INORGANIC_TOKENS, PLANT_TOKENS,
+    },
+    traits::RawObject,
+    ParserError,
+};
+
+/// A field of a raw which can be queried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueryField {
+    /// The type of the raw (`type:creature`)
+    ObjectType,
+    /// The identifier of the raw (`id:DWARF`)
+    Identifier,
+    /// The name of the raw (`name:"giant cave spider"`)
+    Name,
+    /// The name of the module the raw is from, or the location of the module (`module:vanilla`)
+    Module,
+    /// The location of the module the raw is from (`location:mods`)
+    Location,
+    /// A tag of the raw (`tag:LARGE_ROAMING`). For creatures, the tags of their castes count too.
+    Tag,
+    /// A biome the creature or plant is found in (`biome:MOUNTAIN`)
+    Biome,
+    /// Text anywhere in the raw's search terms (a bare word, like `spider`)
+    Text,
+    /// A creature class of one of the creature's castes (`class:GENERAL_POISON`)
+    CreatureClass,
+    /// The identifier of one of the creature's castes (`caste:FEMALE`)
+    CasteId,
+    /// A tag of one of the creature's castes (`caste.tag:FLIER`)
+    CasteTag,
+    /// The adult body size of one of the creature's castes (`body_size>50000`)
+    BodySize,
+    /// The pet value of one of the creature's castes (`pet_value>=100`)
+    PetValue,
+    /// The difficulty of one of the creature's castes (`difficulty>5`)
+    Difficulty,
+    /// The population ratio of one of the creature's castes (`pop_ratio<10`)
+    PopRatio,
+    /// The age one of the creature's castes dies of old age at (`max_age>100`)
+    MaxAge,
+    /// How often the creature or plant appears (`frequency>=50`)
+    Frequency,
+    /// How many of the creature are in each world map tile (`population_number>10`)
+    PopulationNumber,
+    /// How many of the creature spawn together (`cluster_number>1`)
+    ClusterNumber,
+    /// The depth the creature or plant is found at underground (`underground_depth:3`)
+    UndergroundDepth,
+    /// The value of the material of an inorganic or of one of a plant's materials (`value>10`)
+    MaterialValue,
+}
+
+/// How a condition compares the value of a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    /// The field matches the value (`:` or `=`)
+    Equals,
+    /// The field is greater than the value (`>`)
+    GreaterThan,
+    /// The field is greater than or equal to the value (`>=`)
+    GreaterOrEqual,
+    /// The field is less than the value (`<`)
+    LessThan,
+    /// The field is less than or equal to the value (`<=`)
+    LessOrEqual,
+}
+
+/// A single condition of a query, like `biome:MOUNTAIN` or `body_size>50000`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condition {
+    field: QueryField,
+    comparison: Comparison,
+    value: String,
+    /// The value as a number, for the numeric fields
+    number: Option<u32>,
+}
+
+impl QueryField {
+    /// Get the field with a name, as it's written in a query.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the field (e.g. `type` or `caste.tag`), ignoring case.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<QueryField>` - The field, if there is one with the name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_lowercase().as_str() {
+            "type" => Self::ObjectType,
+            "id" | "identifier" => Self::Identifier,
+            "name" => Self::Name,
+            "module" => Self::Module,
+            "location" => Self::Location,
+            "tag" => Self::Tag,
+            "biome" => Self::Biome,
+            "text" => Self::Text,
+            "class" | "caste.class" => Self::CreatureClass,
+            "caste" | "caste.id" => Self::CasteId,
+            "caste.tag" => Self::CasteTag,
+            "body_size" | "caste.body_size" => Self::BodySize,
+            "pet_value" | "caste.pet_value" => Self::PetValue,
+            "difficulty" | "caste.difficulty" => Self::Difficulty,
+            "pop_ratio" | "caste.pop_ratio" => Self::PopRatio,
+            "max_age" | "caste.max_age" => Self::MaxAge,
+            "frequency" => Self::Frequency,
+            "population_number" => Self::PopulationNumber,
+            "cluster_number" => Self::ClusterNumber,
+            "underground_depth" => Self::UndergroundDepth,
+            "value" | "material.value" => Self::MaterialValue,
+            _ => return None,
+        })
+    }
+    /// Whether the field holds numbers (and so can be compared with `>`, `<`, etc.).
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the field is numeric.
+    #[must_use]
+    pub const fn is_numeric(self) -> bool {
+        matches!(
+            self,
+            Self::BodySize
+                | Self::PetValue
+                | Self::Difficulty
+                | Self::PopRatio
+                | Self::MaxAge
+                | Self::Frequency
+                | Self::PopulationNumber
+                | Self::ClusterNumber
+                | Self::UndergroundDepth
+                | Self::MaterialValue
+        )
+    }
+    /// Get the values of a numeric field of a raw.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw to get the values from.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<[u32; 2]>` - The values as `[min, max]` ranges (single values have `min == max`).
+    ///   There can be more than one, e.g. one for each caste of a creature, or none if the raw
+    ///   doesn't have the field (or the field isn't numeric).
+    #[must_use]
+    pub fn numeric_values(self, raw: &dyn RawObject) -> Vec<[u32; 2]> {
+        let any = raw.as_any();
+        let single = |value: u32| [value, value];
+
+        if let Some(creature) = any.downcast_ref::<Creature>() {
+            let castes = creature.get_castes().iter();
+            return match self {
+                Self::BodySize => castes
+                    .filter_map(|caste| {
+                        caste
+                            .get_body_sizes()
+                            .iter()
+                            .map(BodySize::get_size_cm3)
+                            .max()
+                    })
+                    .map(single)
+                    .collect(),
+                Self::PetValue => castes
+                    .filter_map(Caste::get_pet_value)
+                    .map(single)
+                    .collect(),
+                Self::Difficulty => castes
+                    .filter_map(Caste::get_difficulty)
+                    .map(single)
+                    .collect(),
+                Self::PopRatio => castes
+                    .filter_map(Caste::get_pop_ratio)
+                    .map(single)
+                    .collect(),
+                Self::MaxAge => castes.filter_map(Caste::get_max_age).collect(),
+                Self::Frequency => creature.get_frequency().map(single).into_iter().collect(),
+                Self::PopulationNumber => creature.get_population_number().into_iter().collect(),
+                Self::ClusterNumber => creature.get_cluster_number().into_iter().collect(),
+                Self::UndergroundDepth => creature.get_underground_depth().into_iter().collect(),
+                _ => Vec::new(),
+            };
+        }
+        if let Some(plant) = any.downcast_ref::<Plant>() {
+            return match self {
+                Self::Frequency => plant.get_frequency().map(single).into_iter().collect(),
+                Self::UndergroundDepth => plant.get_underground_depth().into_iter().collect(),
+                Self::MaterialValue => plant
+                    .get_materials()
+                    .iter()
+                    .filter_map(Material::get_value)
+                    .map(single)
+                    .collect(),
+                _ => Vec::new(),
+            };
+        }
+        if let Some(inorganic) = any.downcast_ref::<Inorganic>() {
+            if self == Self::MaterialValue {
+                return inorganic
+                    .get_material()
+                    .get_value()
+                    .map(single)
+                    .into_iter()
+                    .collect();
+            }
+        }
+        Vec::new()
+    }
+}
+
+impl std::fmt::Display for QueryField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::ObjectType => "type",
+            Self::Identifier => "id",
+            Self::Name => "name",
+            Self::Module => "module",
+            Self::Location => "location",
+            Self::Tag => "tag",
+            Self::Biome => "biome",
+            Self::Text => "text",
+            Self::CreatureClass => "class",
+            Self::CasteId => "caste",
+            Self::CasteTag => "caste.tag",
+            Self::BodySize => "body_size",
+            Self::PetValue => "pet_value",
+            Self::Difficulty => "difficulty",
+            Self::PopRatio => "pop_ratio",
+            Self::MaxAge => "max_age",
+            Self::Frequency => "frequency",
+            Self::PopulationNumber => "population_number",
+            Self::ClusterNumber => "cluster_number",
+            Self::UndergroundDepth => "underground_depth",
+            Self::MaterialValue => "value",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl Comparison {
+    /// Check whether a range of numbers has a number which compares to a value.
+    const fn matches_range(self, [min, max]: [u32; 2], value: u32) -> bool {
+        match self {
+            Self::Equals => min <= value && value <= max,
+            Self::GreaterThan => max > value,
+            Self::GreaterOrEqual => max >= value,
+            Self::LessThan => min < value,
+            Self::LessOrEqual => min <= value,
+        }
+    }
+}
+
+impl Condition {
+    /// Create a condition, checking that the value makes sense for the field.
+    ///
+    /// Text values are compared ignoring case, and may use `*` as a wildcard (e.g. `id:GIANT_*`).
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The field to check.
+    /// * `comparison` - How to compare the field to the value.
+    /// * `value` - The value to compare with.
+    ///
+    /// # Returns
+    ///
+    /// * `Condition` - The condition.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::InvalidQuery` - If the value isn't a number for a numeric field, a text
+    ///   field is compared with anything but `Equals`, or the value isn't a known object type,
+    ///   location, biome or tag for those fields.
+    pub fn new(
+        field: QueryField,
+        comparison: Comparison,
+        value: &str,
+    ) -> Result<Self, ParserError> {
+        let mut condition = Self {
+            field,
+            comparison,
+            value: value.to_string(),
+            number: None,
+        };
+        if field.is_numeric() {
+            condition.number = Some(value.parse().map_err(|_| {
+                ParserError::InvalidQuery(format!("`{field}` needs a number, not `{value}`"))
+            })?);
+            return Ok(condition);
+        }
+        if comparison != Comparison::Equals {
+            return Err(ParserError::InvalidQuery(format!(
+                "`{field}` can only be matched with `:`"
+            )));
+        }
+
+        let upper = value.to_uppercase();
+        let known = match field {
+            QueryField::ObjectType => OBJECT_TOKEN_MAP.contains_key(&upper),
+            QueryField::Location => location_named(value).is_some(),
+            QueryField::Biome => BIOME_TOKENS.contains_key(&upper),
+            QueryField::Tag => {
+                CREATURE_TOKENS.contains_key(&upper)
+                    || CASTE_TOKENS.contains_key(&upper)
+                    || PLANT_TOKENS.contains_key(&upper)
+                    || INORGANIC_TOKENS.contains_key(&upper)
+                    || ENTITY_TOKENS.contains_key(&upper)
+            }
+            QueryField::CasteTag => CASTE_TOKENS.contains_key(&upper),
+            _ => true,
+        };
+        if !known {
+            return Err(ParserError::InvalidQuery(format!(
+                "`{value}` isn't a known value for `{field}`"
+            )));
+        }
+        Ok(condition)
+    }
+    /// Get the field the condition checks.
+    ///
+    /// # Returns
+    ///
+    /// * `QueryField` - The field.
+    #[must_use]
+    pub const fn get_field(&self) -> QueryField {
+        self.field
+    }
+    /// Get how the condition compares the field.
+    ///
+    /// # Returns
+    ///
+    /// * `Comparison` - The comparison.
+    #[must_use]
+    pub const fn get_comparison(&self) -> Comparison {
+        self.comparison
+    }
+    /// Get the value the condition compares the field with.
+    ///
+    /// # Returns
+    ///
+    /// * `&str` - The value, as it was given.
+    #[must_use]
+    pub fn get_value(&self) -> &str {
+        &self.value
+    }
+    /// Check whether a raw meets the condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw to check.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the raw meets the condition (fields the raw doesn't have never match).
+    #[must_use]
+    pub fn matches(&self, raw: &dyn RawObject) -> bool {
+        if let Some(number) = self.number {
+            return self
+                .field
+                .numeric_values(raw)
+                .into_iter()
+                .any(|range| self.comparison.matches_range(range, number));
+        }
+
+        let any = raw.as_any();
+        let upper = self.value.to_uppercase();
+        match self.field {
+            QueryField::ObjectType => OBJECT_TOKEN_MAP.get(&upper) == Some(raw.get_type()),
+            QueryField::Identifier => matches_pattern(&self.value, raw.get_identifier()),
+            QueryField::Name => matches_pattern(&self.value, raw.get_name()),
+            QueryField::Module => {
+                let metadata = raw.get_metadata();
+                matches_pattern(&self.value, metadata.get_module_name())
+                    || location_named(&self.value) == Some(*metadata.get_location())
+            }
+            QueryField::Location => {
+                location_named(&self.value) == Some(*raw.get_metadata().get_location())
+            }
+            QueryField::Text => {
+                let text = self.value.to_lowercase();
+                raw.get_search_vec()
+                    .iter()
+                    .any(|term| term.to_lowercase().contains(&text))
+            }
+            QueryField::Tag => has_tag(any, &upper),
+            QueryField::Biome => BIOME_TOKENS.get(&upper).is_some_and(|biome| {
+                any.downcast_ref::<Creature>()
+                    .is_some_and(|creature| creature.has_biome(biome))
+                    || any
+                        .downcast_ref::<Plant>()
+                        .is_some_and(|plant| plant.has_biome(biome))
+            }),
+            QueryField::CreatureClass => any.downcast_ref::<Creature>().is_some_and(|creature| {
+                creature.get_castes().iter().any(|caste| {
+                    caste
+                        .get_creature_classes()
+                        .iter()
+                        .any(|class| matches_pattern(&self.value, class))
+                })
+            }),
+            QueryField::CasteId => any.downcast_ref::<Creature>().is_some_and(|creature| {
+                creature
+                    .get_castes()
+                    .iter()
+                    .any(|caste| matches_pattern(&self.value, caste.get_identifier()))
+            }),
+            QueryField::CasteTag => CASTE_TOKENS.get(&upper).is_some_and(|tag| {
+                any.downcast_ref::<Creature>()
+                    .is_some_and(|creature| creature.has_caste_tag(tag))
+            }),
+            _ => false,
+        }
+    }
+}
+
+/// Check whether a raw has a tag (in uppercase), looking the tag up in the tokens for the type of
+/// raw.
+pub fn has_tag(raw: &dyn Any, tag: &str) -> bool {
+    if let Some(creature) = raw.downcast_ref::<Creature>() {
+        return CREATURE_TOKENS
+            .get(tag)
+            .is_some_and(|tag| creature.has_tag(tag))
+            || CASTE_TOKENS
+                .get(tag)
+                .is_some_and(|tag| creature.has_caste_tag(tag));
+    }
+    if let Some(plant) = raw.downcast_ref::<Plant>() {
+        return PLANT_TOKENS.get(tag).is_some_and(|tag| plant.has_tag(tag));
+    }
+    if let Some(inorganic) = raw.downcast_ref::<Inorganic>() {
+        return INORGANIC_TOKENS
+            .get(tag)
+            .is_some_and(|tag| inorganic.has_tag(tag));
+    }
+    if let Some(entity) = raw.downcast_ref::<Entity>() {
+        return ENTITY_TOKENS
+            .get(tag)
+            .is_some_and(|tag| entity.has_tag(tag));
+    }
+    false
+}
+
+/// Get the module location with a name (ignoring case and underscores, so `installed_mods` works).
+fn location_named(name: &str) -> Option<RawModuleLocation> {
+    let name = name.replace('_', "").to_lowercase();
+    [
+        RawModuleLocation::Vanilla,
+        RawModuleLocation::InstalledMods,
+        RawModuleLocation::Mods,
+        RawModuleLocation::LegendsExport,
+    ]
+    .into_iter()
+    .find(|location| location.to_string().to_lowercase() == name)
+}
+
+/// Check whether text matches a pattern, ignoring case. A `*` in the pattern matches any text.
+fn matches_pattern(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let text = text.to_lowercase();
+    let mut parts = pattern.split('*');
+    // There's always a first part (which is the whole pattern if it has no wildcards)
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
@@ -0,0 +1,154 @@
+//! The `Query` type, which combines conditions with AND, OR and NOT.
+
+use std::str::FromStr;
+
+use crate::{traits::RawObject, ParseResult, ParserError};
+
+use super::{parser::parse_query, Condition};
+
+/// A query over the raws, made of conditions combined with AND, OR and NOT.
+///
+/// Queries can be parsed from text (see the module documentation for the language) or built up
+/// from `Condition`s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    /// A single condition
+    Condition(Condition),
+    /// All of the queries match (an empty list matches every raw)
+    And(Vec<Self>),
+    /// Any of the queries match (an empty list matches nothing)
+    Or(Vec<Self>),
+    /// The query doesn't match
+    Not(Box<Self>),
+}
+
+impl Query {
+    /// Parse a query from text (e.g. `type:creature biome:MOUNTAIN body_size>50000`).
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The query. An empty query matches every raw.
+    ///
+    /// # Returns
+    ///
+    /// * `Query` - The parsed query.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::InvalidQuery` - If the query can't be parsed, uses an unknown field, or has a
+    ///   value which doesn't make sense for its field.
+    pub fn parse(text: &str) -> Result<Self, ParserError> {
+        parse_query(text)
+    }
+    /// Combine the query with another, so both must match.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other query.
+    ///
+    /// # Returns
+    ///
+    /// * `Query` - The combined query.
+    #[must_use]
+    pub fn and(self, other: Self) -> Self {
+        match self {
+            Self::And(mut queries) => {
+                queries.push(other);
+                Self::And(queries)
+            }
+            query => Self::And(vec![query, other]),
+        }
+    }
+    /// Combine the query with another, so either may match.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other query.
+    ///
+    /// # Returns
+    ///
+    /// * `Query` - The combined query.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            Self::Or(mut queries) => {
+                queries.push(other);
+                Self::Or(queries)
+            }
+            query => Self::Or(vec![query, other]),
+        }
+    }
+    /// Negate the query, so it matches the raws it didn't.
+    ///
+    /// # Returns
+    ///
+    /// * `Query` - The negated query.
+    #[must_use]
+    pub fn negate(self) -> Self {
+        match self {
+            Self::Not(query) => *query,
+            query => Self::Not(Box::new(query)),
+        }
+    }
+    /// Check whether a raw matches the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw to check.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the raw matches.
+    #[must_use]
+    pub fn matches(&self, raw: &dyn RawObject) -> bool {
+        match self {
+            Self::Condition(condition) => condition.matches(raw),
+            Self::And(queries) => queries.iter().all(|query| query.matches(raw)),
+            Self::Or(queries) => queries.iter().any(|query| query.matches(raw)),
+            Self::Not(query) => !query.matches(raw),
+        }
+    }
+    /// Get the raws which match the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `raws` - The raws to search.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&dyn RawObject>` - The matching raws, in the order they were given.
+    #[must_use]
+    pub fn filter<'a>(&self, raws: &'a [Box<dyn RawObject>]) -> Vec<&'a dyn RawObject> {
+        raws.iter()
+            .map(AsRef::as_ref)
+            .filter(|raw| self.matches(*raw))
+            .collect()
+    }
+    /// Get the raws in a parse result which match the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The result of parsing.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&dyn RawObject>` - The matching raws, in the order they were parsed.
+    #[must_use]
+    pub fn filter_parse_result<'a>(&self, result: &'a ParseResult) -> Vec<&'a dyn RawObject> {
+        self.filter(&result.raws)
+    }
+}
+
+impl From<Condition> for Query {
+    fn from(condition: Condition) -> Self {
+        Self::Condition(condition)
+    }
+}
+
+impl FromStr for Query {
+    type Err = ParserError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text)
+    }
+}
@@ -0,0 +1,54 @@
+//! Query the parsed raws with a small query language (or with `Query`s built up in code).
+//!
+//! A query is a list of conditions, which must all match. Conditions are `field:value` (or
+//! `field=value`), `field!=value`, or a numeric comparison like `body_size>50000` (also `>=`, `<`
+//! and `<=`). A bare word matches text anywhere in the raw's search terms, and values with spaces
+//! can be quoted (`name:"giant cave spider"`). Text values ignore case and may use `*` as a
+//! wildcard (`id:GIANT_*`). Conditions can be combined with `AND`, `OR` and `NOT` (or a leading
+//! `-`), and grouped with parentheses.
+//!
+//! | Field | Matches |
+//! | --- | --- |
+//! | `type` | The object type (`type:creature`, `type:inorganic`) |
+//! | `id`, `name` | The identifier or name of the raw |
+//! | `module`, `location` | The module name, or its location (`module:vanilla`, `location:mods`) |
+//! | `tag` | A tag of the raw (for creatures, the tags of their castes count too) |
+//! | `biome` | A biome of a creature or plant (`biome:MOUNTAIN`) |
+//! | `caste`, `caste.tag`, `class` | The identifier, a tag or a creature class of one of a creature's castes |
+//! | `body_size`, `pet_value`, `difficulty`, `pop_ratio`, `max_age` | A number from one of a creature's castes |
+//! | `frequency`, `population_number`, `cluster_number`, `underground_depth` | A number from a creature (or plant) |
+//! | `value` | The material value of an inorganic (or one of a plant's materials) |
+//!
+//! The caste conditions each match if any caste matches, so `caste.tag:FLIER caste:FEMALE` matches
+//! creatures with a flying caste and a female caste. Ranges (like `underground_depth`) match if any
+//! number in the range does.
+//!
+//! ```rust
+//! use dfraw_parser::{creature::Creature, metadata::RawMetadata, query::Query, traits::RawObject};
+//!
+//! let mut roc = Creature::new("BIRD_ROC", &RawMetadata::default());
+//! roc.parse_tag("BIOME", "MOUNTAIN");
+//! roc.parse_tag("FLIER", "");
+//! roc.parse_tag("BODY_SIZE", "2:0:150000");
+//! let mut dwarf = Creature::new("DWARF", &RawMetadata::default());
+//! dwarf.parse_tag("BODY_SIZE", "12:0:60000");
+//! let raws: Vec<Box<dyn RawObject>> = vec![Box::new(roc), Box::new(dwarf)];
+//!
+//! let query = Query::parse("type:creature biome:MOUNTAIN caste.tag:FLIER body_size>50000").unwrap();
+//! let found = query.filter(&raws);
+//! assert_eq!(found.len(), 1);
+//! assert_eq!(found[0].get_identifier(), "BIRD_ROC");
+//!
+//! let query = Query::parse("body_size>50000 AND NOT (tag:FLIER OR id:BIRD_*)").unwrap();
+//! assert_eq!(query.filter(&raws)[0].get_identifier(), "DWARF");
+//!
+//! assert!(Query::parse("body_size>big").is_err());
+//! ```
+
+mod condition;
+mod expression;
+mod parser;
+
+pub(crate) use condition::has_tag;
+pub use condition::{Comparison, Condition, QueryField};
+pub use expression::Query;
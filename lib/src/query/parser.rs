@@ -0,0 +1,346 @@
+//! Parsing of the query language into a `Query`.
+
+use crate::ParserError;
+
+use super::{Comparison, Condition, Query, QueryField};
+
+/// A piece of a query.
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    /// A condition (`field:value` or a bare word), which is negated if it starts with `-`
+    Term {
+        field: Option<String>,
+        operator: String,
+        value: String,
+        negated: bool,
+    },
+}
+
+/// The comparison operators, longest first so `>=` isn't read as `>`.
+const OPERATORS: [&str; 7] = ["!=", ">=", "<=", ":", "=", ">", "<"];
+
+/// Split a query into its tokens.
+fn tokenize(text: &str) -> Result<Vec<Token>, ParserError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(&next) = chars.peek() {
+        if next.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if next == '(' || next == ')' {
+            chars.next();
+            tokens.push(if next == '(' {
+                Token::Open
+            } else {
+                Token::Close
+            });
+            continue;
+        }
+
+        let negated = next == '-';
+        if negated {
+            chars.next();
+            // `-(...)` negates the whole group
+            if chars.peek() == Some(&'(') {
+                tokens.push(Token::Not);
+                continue;
+            }
+        }
+        let mut word = String::new();
+        let mut field = None;
+        let mut operator = String::new();
+        let mut quoted = false;
+        let mut in_quotes = false;
+        while let Some(&c) = chars.peek() {
+            if !in_quotes && (c.is_whitespace() || c == '(' || c == ')') {
+                break;
+            }
+            chars.next();
+            if c == '"' {
+                in_quotes = !in_quotes;
+                quoted = true;
+                continue;
+            }
+            word.push(c);
+            if in_quotes || field.is_some() || quoted {
+                continue;
+            }
+            if let Some(op) = OPERATORS.iter().find(|op| word.ends_with(**op)) {
+                // Check for the two character operators (e.g. `>=` after `>`)
+                let mut op = (*op).to_string();
+                if let Some(&c) = chars.peek() {
+                    if c == '=' && (op == ">" || op == "<") {
+                        chars.next();
+                        op.push(c);
+                        word.push(c);
+                    }
+                }
+                field = Some(word[..word.len() - op.len()].to_string());
+                operator = op;
+                word.clear();
+            }
+        }
+        if in_quotes {
+            return Err(ParserError::InvalidQuery(String::from(
+                "a quote (\") isn't closed",
+            )));
+        }
+
+        let keyword = if field.is_none() && !quoted && !negated {
+            match word.to_uppercase().as_str() {
+                "AND" => Some(Token::And),
+                "OR" => Some(Token::Or),
+                "NOT" => Some(Token::Not),
+                _ => None,
+            }
+        } else {
+            None
+        };
+        if let Some(keyword) = keyword {
+            tokens.push(keyword);
+        } else if field.is_some() || !word.is_empty() || quoted {
+            tokens.push(Token::Term {
+                field,
+                operator,
+                value: word,
+                negated,
+            });
+        } else {
+            return Err(ParserError::InvalidQuery(String::from(
+                "`-` must be followed by a condition",
+            )));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// A recursive descent parser over the tokens of a query.
+struct QueryParser {
+    tokens: std::iter::Peekable<std::vec::IntoIter<Token>>,
+}
+
+impl QueryParser {
+    /// `or := and (OR and)*`
+    fn parse_or(&mut self) -> Result<Query, ParserError> {
+        let mut queries = vec![self.parse_and()?];
+        while self.tokens.next_if_eq(&Token::Or).is_some() {
+            queries.push(self.parse_and()?);
+        }
+        Ok(if queries.len() == 1 {
+            queries.remove(0)
+        } else {
+            Query::Or(queries)
+        })
+    }
+    /// `and := unary ([AND] unary)*`
+    fn parse_and(&mut self) -> Result<Query, ParserError> {
+        let mut queries = vec![self.parse_unary()?];
+        loop {
+            match self.tokens.peek() {
+                None | Some(Token::Close | Token::Or) => break,
+                Some(Token::And) => {
+                    self.tokens.next();
+                }
+                _ => (),
+            }
+            queries.push(self.parse_unary()?);
+        }
+        Ok(if queries.len() == 1 {
+            queries.remove(0)
+        } else {
+            Query::And(queries)
+        })
+    }
+    /// `unary := NOT unary | ( or ) | term`
+    fn parse_unary(&mut self) -> Result<Query, ParserError> {
+        match self.tokens.next() {
+            Some(Token::Not) => Ok(self.parse_unary()?.negate()),
+            Some(Token::Open) => {
+                let query = self.parse_or()?;
+                if self.tokens.next() == Some(Token::Close) {
+                    Ok(query)
+                } else {
+                    Err(ParserError::InvalidQuery(String::from(
+                        "a parenthesis isn't closed",
+                    )))
+                }
+            }
+            Some(Token::Term {
+                field,
+                operator,
+                value,
+                negated,
+            }) => {
+                let query = parse_term(field.as_deref(), &operator, &value)?;
+                Ok(if negated { query.negate() } else { query })
+            }
+            Some(Token::Close) => Err(ParserError::InvalidQuery(String::from("unexpected `)`"))),
+            Some(Token::And | Token::Or) => Err(ParserError::InvalidQuery(String::from(
+                "AND and OR must be between two conditions",
+            ))),
+            None => Err(ParserError::InvalidQuery(String::from(
+                "the query ends too soon",
+            ))),
+        }
+    }
+}
+
+/// Make the condition for a term (e.g. `body_size>50000`, or a bare word).
+fn parse_term(field: Option<&str>, operator: &str, value: &str) -> Result<Query, ParserError> {
+    let Some(field_name) = field else {
+        return Ok(Query::Condition(Condition::new(
+            QueryField::Text,
+            Comparison::Equals,
+            value,
+        )?));
+    };
+    let field = QueryField::from_name(field_name)
+        .ok_or_else(|| ParserError::InvalidQuery(format!("unknown field `{field_name}`")))?;
+    let comparison = match operator {
+        ">" => Comparison::GreaterThan,
+        ">=" => Comparison::GreaterOrEqual,
+        "<" => Comparison::LessThan,
+        "<=" => Comparison::LessOrEqual,
+        _ => Comparison::Equals,
+    };
+    let condition = Query::Condition(Condition::new(field, comparison, value)?);
+    Ok(if operator == "!=" {
+        condition.negate()
+    } else {
+        condition
+    })
+}
+
+/// Parse a query.
+pub fn parse_query(text: &str) -> Result<Query, ParserError> {
+    let tokens = tokenize(text)?;
+    if tokens.is_empty() {
+        return Ok(Query::And(Vec::new()));
+    }
+    let mut parser = QueryParser {
+        tokens: tokens.into_iter().peekable(),
+    };
+    let query = parser.parse_or()?;
+    if parser.tokens.next().is_some() {
+        return Err(ParserError::InvalidQuery(String::from("unexpected `)`")));
+    }
+    Ok(query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(field: QueryField, comparison: Comparison, value: &str) -> Query {
+        Query::Condition(Condition::new(field, comparison, value).expect("Invalid condition"))
+    }
+
+    fn tag(value: &str) -> Query {
+        condition(QueryField::Tag, Comparison::Equals, value)
+    }
+
+    fn parse(text: &str) -> Query {
+        parse_query(text).expect("Failed to parse the query")
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        assert_eq!(
+            parse("tag:FLIER tag:AMPHIBIOUS OR tag:LARGE_ROAMING AND NOT tag:MEGABEAST"),
+            Query::Or(vec![
+                Query::And(vec![tag("FLIER"), tag("AMPHIBIOUS")]),
+                Query::And(vec![
+                    tag("LARGE_ROAMING"),
+                    Query::Not(Box::new(tag("MEGABEAST"))),
+                ]),
+            ])
+        );
+        assert_eq!(
+            parse("tag:FLIER (tag:AMPHIBIOUS OR tag:LARGE_ROAMING)"),
+            Query::And(vec![
+                tag("FLIER"),
+                Query::Or(vec![tag("AMPHIBIOUS"), tag("LARGE_ROAMING")]),
+            ])
+        );
+    }
+
+    #[test]
+    fn a_dash_negates_a_condition_or_a_group() {
+        assert_eq!(parse("-tag:FLIER"), Query::Not(Box::new(tag("FLIER"))));
+        let group = Query::Or(vec![tag("FLIER"), tag("AMPHIBIOUS")]);
+        assert_eq!(
+            parse("-(tag:FLIER OR tag:AMPHIBIOUS)"),
+            Query::Not(Box::new(group.clone()))
+        );
+        assert_eq!(
+            parse("NOT (tag:FLIER OR tag:AMPHIBIOUS)"),
+            parse("-(tag:FLIER OR tag:AMPHIBIOUS)")
+        );
+        assert_eq!(
+            parse("tag:LARGE_ROAMING -(tag:FLIER OR tag:AMPHIBIOUS)"),
+            Query::And(vec![tag("LARGE_ROAMING"), Query::Not(Box::new(group))])
+        );
+    }
+
+    #[test]
+    fn quoted_values_keep_their_spaces_and_keywords() {
+        assert_eq!(
+            parse("name:\"giant cave spider\""),
+            condition(QueryField::Name, Comparison::Equals, "giant cave spider")
+        );
+        // Quoted words and operators are text, not keywords or comparisons
+        assert_eq!(
+            parse("\"OR\" \"a>b\""),
+            Query::And(vec![
+                condition(QueryField::Text, Comparison::Equals, "OR"),
+                condition(QueryField::Text, Comparison::Equals, "a>b"),
+            ])
+        );
+        assert!(parse_query("name:\"giant cave").is_err());
+    }
+
+    #[test]
+    fn comparisons_are_read_whole() {
+        assert_eq!(parse("tag!=FLIER"), Query::Not(Box::new(tag("FLIER"))));
+        assert_eq!(
+            parse("body_size>=50000 pet_value<=100 difficulty>5 max_age<100 frequency=50"),
+            Query::And(vec![
+                condition(QueryField::BodySize, Comparison::GreaterOrEqual, "50000"),
+                condition(QueryField::PetValue, Comparison::LessOrEqual, "100"),
+                condition(QueryField::Difficulty, Comparison::GreaterThan, "5"),
+                condition(QueryField::MaxAge, Comparison::LessThan, "100"),
+                condition(QueryField::Frequency, Comparison::Equals, "50"),
+            ])
+        );
+    }
+
+    #[test]
+    fn malformed_queries_are_errors() {
+        for text in [
+            "(tag:FLIER",
+            "tag:FLIER)",
+            "tag:FLIER OR",
+            "AND tag:FLIER",
+            "NOT",
+            "- tag:FLIER",
+            "()",
+            "colour:red",
+            "body_size>big",
+            "tag>FLIER",
+        ] {
+            assert!(
+                matches!(parse_query(text), Err(ParserError::InvalidQuery(_))),
+                "`{text}` should be an invalid query"
+            );
+        }
+        assert_eq!(parse("  "), Query::And(Vec::new()));
+    }
+}
@@ -0,0 +1,236 @@
+//! The `RawCollection`, which filters, sorts and pages through parsed raws.
+
+use std::cmp::Ordering;
+
+use crate::{traits::RawObject, ParseResult, ParserError};
+
+use super::{
+    page::PageCursor,
+    sort::{compare, SortKey, SortOrder, SortValue},
+    PageRequest, RawFacets, RawFilter, RawPage,
+};
+
+/// A collection of parsed raws, which can be filtered, sorted, paged through and counted.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Default)]
+pub struct RawCollection {
+    raws: Vec<Box<dyn RawObject>>,
+}
+
+impl RawCollection {
+    /// Create a collection of raws.
+    ///
+    /// # Arguments
+    ///
+    /// * `raws` - The raws.
+    ///
+    /// # Returns
+    ///
+    /// * `RawCollection` - The collection.
+    #[must_use]
+    pub fn new(raws: Vec<Box<dyn RawObject>>) -> Self {
+        Self { raws }
+    }
+    /// Create a collection of the raws in a parse result.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The result of parsing (its info files aren't kept).
+    ///
+    /// # Returns
+    ///
+    /// * `RawCollection` - The collection.
+    #[must_use]
+    pub fn from_parse_result(result: ParseResult) -> Self {
+        Self::new(result.raws)
+    }
+    /// Get the raws in the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `&[Box<dyn RawObject>]` - The raws, in the order they were added.
+    #[must_use]
+    pub fn get_raws(&self) -> &[Box<dyn RawObject>] {
+        &self.raws
+    }
+    /// Take the raws out of the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Box<dyn RawObject>>` - The raws, in the order they were added.
+    #[must_use]
+    pub fn into_raws(self) -> Vec<Box<dyn RawObject>> {
+        self.raws
+    }
+    /// Get the number of raws in the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of raws.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.raws.len()
+    }
+    /// Check whether the collection is empty.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether there are no raws.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.raws.is_empty()
+    }
+    /// Get a raw by its object id.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_id` - The object id of the raw.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&dyn RawObject>` - The raw, if it's in the collection.
+    #[must_use]
+    pub fn get(&self, object_id: &str) -> Option<&dyn RawObject> {
+        self.raws
+            .iter()
+            .find(|raw| raw.get_object_id() == object_id)
+            .map(AsRef::as_ref)
+    }
+    /// Get the raws which pass all of a list of filters.
+    ///
+    /// # Arguments
+    ///
+    /// * `filters` - The filters (no filters lets every raw through).
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&dyn RawObject>` - The raws, in the order they were added.
+    #[must_use]
+    pub fn filter(&self, filters: &[RawFilter]) -> Vec<&dyn RawObject> {
+        self.raws
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(|raw| filters.iter().all(|filter| filter.matches(*raw)))
+            .collect()
+    }
+    /// Get the raws which pass all of a list of filters, sorted.
+    ///
+    /// Raws with the same value are sorted by their object ids, so the order is the same every
+    /// time.
+    ///
+    /// # Arguments
+    ///
+    /// * `filters` - The filters.
+    /// * `key` - What to sort the raws by.
+    /// * `order` - Which way to sort them.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&dyn RawObject>` - The sorted raws.
+    #[must_use]
+    pub fn sorted(
+        &self,
+        filters: &[RawFilter],
+        key: SortKey,
+        order: SortOrder,
+    ) -> Vec<&dyn RawObject> {
+        self.sort(filters, key, order)
+            .into_iter()
+            .map(|(_, raw)| raw)
+            .collect()
+    }
+    /// Get a page of raws.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Which raws to get, in what order, and where the page starts.
+    ///
+    /// # Returns
+    ///
+    /// * `RawPage` - The raws in the page, the total number of raws which passed the filters, and
+    ///   the cursor for the next page (if there is one).
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::InvalidCursor` - If the request's cursor can't be read, or is for a page
+    ///   which was sorted differently.
+    pub fn page(&self, request: &PageRequest) -> Result<RawPage<'_>, ParserError> {
+        let cursor = request.get_cursor()?;
+        let (key, order) = request.get_sorting();
+        let sorted = self.sort(request.get_filters(), key, order);
+
+        let start = cursor.map_or(0, |cursor| {
+            sorted.partition_point(|(value, raw)| {
+                compare(
+                    (value, raw.get_object_id()),
+                    (&cursor.value, &cursor.object_id),
+                    order,
+                ) != Ordering::Greater
+            })
+        });
+        let end = start.saturating_add(request.get_limit()).min(sorted.len());
+        let next_cursor = if end < sorted.len() && end > start {
+            let (value, raw) = &sorted[end - 1];
+            Some(PageCursor::new(request, value.clone(), raw.get_object_id()).encode())
+        } else {
+            None
+        };
+
+        Ok(RawPage {
+            total: sorted.len(),
+            raws: sorted
+                .into_iter()
+                .skip(start)
+                .take(end - start)
+                .map(|(_, raw)| raw)
+                .collect(),
+            next_cursor,
+        })
+    }
+    /// Count the raws which pass all of a list of filters, by object type, module and location.
+    ///
+    /// # Arguments
+    ///
+    /// * `filters` - The filters.
+    ///
+    /// # Returns
+    ///
+    /// * `RawFacets` - The counts.
+    #[must_use]
+    pub fn facets(&self, filters: &[RawFilter]) -> RawFacets {
+        RawFacets::from_raws(self.filter(filters))
+    }
+    /// Filter the raws, and sort them with the values they're sorted by.
+    fn sort(
+        &self,
+        filters: &[RawFilter],
+        key: SortKey,
+        order: SortOrder,
+    ) -> Vec<(SortValue, &dyn RawObject)> {
+        let mut sorted: Vec<(SortValue, &dyn RawObject)> = self
+            .filter(filters)
+            .into_iter()
+            .map(|raw| (key.value_of(raw), raw))
+            .collect();
+        sorted.sort_by(|(a_value, a), (b_value, b)| {
+            compare(
+                (a_value, a.get_object_id()),
+                (b_value, b.get_object_id()),
+                order,
+            )
+        });
+        sorted
+    }
+}
+
+impl From<Vec<Box<dyn RawObject>>> for RawCollection {
+    fn from(raws: Vec<Box<dyn RawObject>>) -> Self {
+        Self::new(raws)
+    }
+}
+
+impl From<ParseResult> for RawCollection {
+    fn from(result: ParseResult) -> Self {
+        Self::from_parse_result(result)
+    }
+}
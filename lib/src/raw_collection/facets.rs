@@ -0,0 +1,58 @@
+//! Counts of raws by object type, module and location.
+
+use std::collections::HashMap;
+
+use crate::{
+    metadata::{ObjectType, RawModuleLocation},
+    traits::RawObject,
+};
+
+/// The number of raws of each object type, from each module and from each location.
+///
+/// This extends `utilities::summarize_raws` (which only counts object types), and is meant for
+/// showing how many results each choice of a filter would have.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RawFacets {
+    /// The number of raws counted
+    pub total: usize,
+    /// The number of raws of each object type
+    pub object_types: HashMap<ObjectType, usize>,
+    /// The number of raws from each module (by module name)
+    pub modules: HashMap<String, usize>,
+    /// The number of raws from each location
+    pub locations: HashMap<RawModuleLocation, usize>,
+}
+
+impl RawFacets {
+    /// Count raws.
+    ///
+    /// # Arguments
+    ///
+    /// * `raws` - The raws to count.
+    ///
+    /// # Returns
+    ///
+    /// * `RawFacets` - The counts.
+    #[must_use]
+    pub fn from_raws<'a>(raws: impl IntoIterator<Item = &'a dyn RawObject>) -> Self {
+        let mut facets = Self::default();
+        for raw in raws {
+            let metadata = raw.get_metadata();
+            facets.total += 1;
+            *facets
+                .object_types
+                .entry(raw.get_type().clone())
+                .or_default() += 1;
+            *facets
+                .modules
+                .entry(metadata.get_module_name().to_string())
+                .or_default() += 1;
+            *facets
+                .locations
+                .entry(*metadata.get_location())
+                .or_default() += 1;
+        }
+        facets
+    }
+}
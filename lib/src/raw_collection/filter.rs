@@ -0,0 +1,54 @@
+//! Filters for the raws in a `RawCollection`.
+
+use crate::{
+    metadata::{ObjectType, RawModuleLocation},
+    query::{has_tag, Query},
+    traits::RawObject,
+};
+
+/// A filter on the raws in a `RawCollection`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RawFilter {
+    /// Raws of an object type
+    ObjectType(ObjectType),
+    /// Raws from a module (by the name of the module, ignoring case)
+    Module(String),
+    /// Raws from the modules in a location
+    Location(RawModuleLocation),
+    /// Raws with a tag, by its raw token name (e.g. `FLIER`). For creatures, the tags of their
+    /// castes count too.
+    Tag(String),
+    /// Raws which match a query (see the `query` module)
+    Query(Query),
+}
+
+impl RawFilter {
+    /// Check whether a raw passes the filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw to check.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the raw passes the filter.
+    #[must_use]
+    pub fn matches(&self, raw: &dyn RawObject) -> bool {
+        match self {
+            Self::ObjectType(object_type) => raw.get_type() == object_type,
+            Self::Module(module) => raw
+                .get_metadata()
+                .get_module_name()
+                .eq_ignore_ascii_case(module),
+            Self::Location(location) => raw.get_metadata().get_location() == location,
+            Self::Tag(tag) => has_tag(raw.as_any(), &tag.to_uppercase()),
+            Self::Query(query) => query.matches(raw),
+        }
+    }
+}
+
+impl From<Query> for RawFilter {
+    fn from(query: Query) -> Self {
+        Self::Query(query)
+    }
+}
@@ -0,0 +1,54 @@
+//! Filter, sort and page through parsed raws, e.g. to show them a page at a time in an app.
+//!
+//! A `RawCollection` holds the raws from a `ParseResult`. Filters pick raws by object type,
+//! module, location, tag or a `query::Query`, and they can be sorted by name, identifier, object
+//! type, module or a numeric field (like body size). Pages are linked by cursors, which continue
+//! from the last raw of the previous page, and facets count the filtered raws by object type,
+//! module and location.
+//!
+//! ```rust
+//! use dfraw_parser::{
+//!     creature::Creature,
+//!     metadata::{ObjectType, RawMetadata},
+//!     query::QueryField,
+//!     raw_collection::{PageRequest, RawCollection, RawFilter, SortKey, SortOrder},
+//!     traits::RawObject,
+//! };
+//!
+//! let mut raws: Vec<Box<dyn RawObject>> = Vec::new();
+//! for (identifier, size) in [("CAT", "5000"), ("DWARF", "60000"), ("ELEPHANT", "5000000")] {
+//!     let mut creature = Creature::new(identifier, &RawMetadata::default());
+//!     creature.parse_tag("BODY_SIZE", &format!("0:0:{size}"));
+//!     raws.push(Box::new(creature));
+//! }
+//! let collection = RawCollection::new(raws);
+//!
+//! let request = PageRequest::new()
+//!     .with_filter(RawFilter::ObjectType(ObjectType::Creature))
+//!     .sorted_by(SortKey::Numeric(QueryField::BodySize), SortOrder::Descending)
+//!     .with_limit(2);
+//! let page = collection.page(&request).unwrap();
+//! assert_eq!(page.total, 3);
+//! assert_eq!(page.raws[0].get_identifier(), "ELEPHANT");
+//! assert_eq!(page.raws[1].get_identifier(), "DWARF");
+//!
+//! let cursor = page.next_cursor.unwrap();
+//! let page = collection.page(&request.after(&cursor)).unwrap();
+//! assert_eq!(page.raws[0].get_identifier(), "CAT");
+//! assert!(page.next_cursor.is_none());
+//!
+//! let facets = collection.facets(&[]);
+//! assert_eq!(facets.object_types[&ObjectType::Creature], 3);
+//! ```
+
+mod collection;
+mod facets;
+mod filter;
+mod page;
+mod sort;
+
+pub use collection::RawCollection;
+pub use facets::RawFacets;
+pub use filter::RawFilter;
+pub use page::{PageRequest, RawPage, DEFAULT_PAGE_SIZE};
+pub use sort::{SortKey, SortOrder};
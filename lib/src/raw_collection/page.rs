@@ -0,0 +1,196 @@
+//! Requesting pages of raws from a `RawCollection`, and the cursors that link them.
+
+use crate::{traits::RawObject, ParserError};
+
+use super::{
+    sort::{SortKey, SortOrder, SortValue},
+    RawFilter,
+};
+
+/// The number of raws in a page, if the request doesn't say.
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// A request for a page of raws from a `RawCollection`: which raws, in what order, and where the
+/// page starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageRequest {
+    filters: Vec<RawFilter>,
+    sort_key: SortKey,
+    sort_order: SortOrder,
+    limit: usize,
+    cursor: Option<String>,
+}
+
+/// A page of raws from a `RawCollection`.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawPage<'a> {
+    /// The raws in the page
+    pub raws: Vec<&'a dyn RawObject>,
+    /// The number of raws which passed the filters (in all the pages)
+    pub total: usize,
+    /// The cursor for the next page, if there are more raws
+    pub next_cursor: Option<String>,
+}
+
+/// Where a page ends: the sort value and object id of its last raw, and how the raws were sorted.
+///
+/// Pages continue after the position of the raw rather than after a count of raws, so a cursor
+/// still works (and doesn't skip or repeat raws) if raws are added or removed between requests.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PageCursor {
+    /// The key the page was sorted by
+    pub sort_key: String,
+    /// The order the page was sorted in
+    pub sort_order: SortOrder,
+    /// The sort value of the last raw in the page
+    pub value: SortValue,
+    /// The object id of the last raw in the page
+    pub object_id: String,
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self {
+            filters: Vec::new(),
+            sort_key: SortKey::default(),
+            sort_order: SortOrder::default(),
+            limit: DEFAULT_PAGE_SIZE,
+            cursor: None,
+        }
+    }
+}
+
+impl PageRequest {
+    /// Create a request for the first page of all the raws, sorted by identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `PageRequest` - The request.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Only include the raws which pass a filter (as well as any other filters).
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The filter.
+    ///
+    /// # Returns
+    ///
+    /// * `PageRequest` - The request with the filter.
+    #[must_use]
+    pub fn with_filter(mut self, filter: RawFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+    /// Sort the raws.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - What to sort the raws by.
+    /// * `order` - Which way to sort them.
+    ///
+    /// # Returns
+    ///
+    /// * `PageRequest` - The request with the sorting.
+    #[must_use]
+    pub const fn sorted_by(mut self, key: SortKey, order: SortOrder) -> Self {
+        self.sort_key = key;
+        self.sort_order = order;
+        self
+    }
+    /// Set the most raws in the page.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The size of the page. A limit of 0 uses `DEFAULT_PAGE_SIZE` (an empty page
+    ///   would have no cursor to continue from).
+    ///
+    /// # Returns
+    ///
+    /// * `PageRequest` - The request with the limit.
+    #[must_use]
+    pub const fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = if limit == 0 { DEFAULT_PAGE_SIZE } else { limit };
+        self
+    }
+    /// Start the page after the end of another page.
+    ///
+    /// # Arguments
+    ///
+    /// * `cursor` - The `next_cursor` of the other page (which must have been requested with the
+    ///   same sorting).
+    ///
+    /// # Returns
+    ///
+    /// * `PageRequest` - The request for the next page.
+    #[must_use]
+    pub fn after(mut self, cursor: &str) -> Self {
+        self.cursor = Some(cursor.to_string());
+        self
+    }
+    /// Get the filters of the request.
+    ///
+    /// # Returns
+    ///
+    /// * `&[RawFilter]` - The filters.
+    #[must_use]
+    pub fn get_filters(&self) -> &[RawFilter] {
+        &self.filters
+    }
+    /// Get what the raws are sorted by.
+    ///
+    /// # Returns
+    ///
+    /// * `(SortKey, SortOrder)` - The sort key and order.
+    #[must_use]
+    pub const fn get_sorting(&self) -> (SortKey, SortOrder) {
+        (self.sort_key, self.sort_order)
+    }
+    /// Get the most raws in the page.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The size of the page.
+    #[must_use]
+    pub const fn get_limit(&self) -> usize {
+        self.limit
+    }
+    /// Read the cursor the page starts after.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::InvalidCursor` - If the cursor can't be read, or is for different sorting.
+    pub(crate) fn get_cursor(&self) -> Result<Option<PageCursor>, ParserError> {
+        let Some(cursor) = &self.cursor else {
+            return Ok(None);
+        };
+        let cursor: PageCursor =
+            serde_json::from_str(cursor).map_err(|_| ParserError::InvalidCursor(cursor.clone()))?;
+        if cursor.sort_key != format!("{:?}", self.sort_key) || cursor.sort_order != self.sort_order
+        {
+            return Err(ParserError::InvalidCursor(String::from(
+                "the cursor is for a page with different sorting",
+            )));
+        }
+        Ok(Some(cursor))
+    }
+}
+
+impl PageCursor {
+    /// Make the cursor for the raw at the end of a page.
+    pub fn new(request: &PageRequest, value: SortValue, object_id: &str) -> Self {
+        Self {
+            sort_key: format!("{:?}", request.sort_key),
+            sort_order: request.sort_order,
+            value,
+            object_id: object_id.to_string(),
+        }
+    }
+    /// Write the cursor as an opaque string.
+    pub fn encode(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
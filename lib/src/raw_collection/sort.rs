@@ -0,0 +1,89 @@
+//! Sorting the raws in a `RawCollection`.
+
+use std::cmp::Ordering;
+
+use crate::{query::QueryField, traits::RawObject};
+
+/// What to sort raws by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SortKey {
+    /// The name of the raw (ignoring case)
+    Name,
+    /// The identifier of the raw (ignoring case)
+    #[default]
+    Identifier,
+    /// The object type of the raw
+    ObjectType,
+    /// The name of the module the raw is from (ignoring case)
+    Module,
+    /// A numeric field of the raw (like `QueryField::BodySize`). Raws are sorted by their largest
+    /// value for the field, and raws without the field are always sorted last.
+    Numeric(QueryField),
+}
+
+/// Which way to sort raws.
+#[derive(
+    serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default,
+)]
+pub enum SortOrder {
+    /// Smallest (or first alphabetically) first
+    #[default]
+    Ascending,
+    /// Largest (or last alphabetically) first
+    Descending,
+}
+
+/// The value a raw is sorted by.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum SortValue {
+    /// A lowercase name, identifier, object type or module name
+    Text(String),
+    /// The largest value of a numeric field, if the raw has the field
+    Number(Option<u32>),
+}
+
+impl SortKey {
+    /// Get the value a raw is sorted by.
+    pub fn value_of(self, raw: &dyn RawObject) -> SortValue {
+        match self {
+            Self::Name => SortValue::Text(raw.get_name().to_lowercase()),
+            Self::Identifier => SortValue::Text(raw.get_identifier().to_lowercase()),
+            Self::ObjectType => SortValue::Text(raw.get_type().to_string().to_lowercase()),
+            Self::Module => SortValue::Text(raw.get_metadata().get_module_name().to_lowercase()),
+            Self::Numeric(field) => SortValue::Number(
+                field
+                    .numeric_values(raw)
+                    .into_iter()
+                    .map(|[_, max]| max)
+                    .max(),
+            ),
+        }
+    }
+}
+
+/// Compare two sorted raws by their values, then by their object ids (so the order is the same
+/// every time, even for raws with the same value).
+pub fn compare(
+    (a_value, a_id): (&SortValue, &str),
+    (b_value, b_id): (&SortValue, &str),
+    order: SortOrder,
+) -> Ordering {
+    let ordering = match (a_value, b_value) {
+        // Raws without a number are last either way. (Values of different kinds aren't sorted by
+        // the same key, so their order only has to be consistent.)
+        (SortValue::Number(Some(_)), SortValue::Number(None))
+        | (SortValue::Number(_), SortValue::Text(_)) => Ordering::Less,
+        (SortValue::Number(None), SortValue::Number(Some(_)))
+        | (SortValue::Text(_), SortValue::Number(_)) => Ordering::Greater,
+        (SortValue::Number(a), SortValue::Number(b)) => ordered(a.cmp(b), order),
+        (SortValue::Text(a), SortValue::Text(b)) => ordered(a.cmp(b), order),
+    };
+    ordering.then_with(|| a_id.cmp(b_id))
+}
+
+const fn ordered(ordering: Ordering, order: SortOrder) -> Ordering {
+    match order {
+        SortOrder::Ascending => ordering,
+        SortOrder::Descending => ordering.reverse(),
+    }
+}
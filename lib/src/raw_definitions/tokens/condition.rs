@@ -43,6 +43,7 @@ pub static CONDITION_TOKENS: phf::Map<&'static str, ConditionTag> = phf::phf_map
   "CROP_R" => ConditionTag::CropR,
   "SHRUB_DEAD" => ConditionTag::ShrubDead,
   "SAPLING" => ConditionTag::Sapling,
+  "TREE_TILE" => ConditionTag::TreeTile,
 
   "CONDITION_NOT_CHILD" => ConditionTag::NotChild,
   "CONDITION_CLASS" => ConditionTag::Class,
@@ -71,6 +72,11 @@ pub static CONDITION_TOKENS: phf::Map<&'static str, ConditionTag> = phf::phf_map
   "TISSUE_SWAP" => ConditionTag::TissueSwap,
   "TISSUE_MIN_CURLY" => ConditionTag::TissueMinCurly,
   "TISSUE_MAX_CURLY" => ConditionTag::TissueMaxCurly,
+  "CONDITION_BP" => ConditionTag::BodyPart,
+  "LG_CONDITION_BP" => ConditionTag::BodyPart,
+  "BP_PRESENT" => ConditionTag::BodyPartPresent,
+  "BP_SCARRED" => ConditionTag::BodyPartScarred,
+  "BP_APPEARANCE_MODIFIER_RANGE" => ConditionTag::BodyPartAppearanceModifierRange,
   // Generic condition (or something)
   "CONDITION" => ConditionTag::Condition,
 
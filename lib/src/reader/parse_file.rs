@@ -337,6 +337,8 @@ pub fn parse_raw_file_with_info<P: AsRef<Path>>(
                 }
                 "CREATURE_GRAPHICS"
                 | "CREATURE_CASTE_GRAPHICS"
+                | "STATUE_CREATURE_GRAPHICS"
+                | "STATUE_CREATURE_CASTE_GRAPHICS"
                 | "TILE_GRAPHICS"
                 | "PLANT_GRAPHICS" => {
                     // Starting a new graphic, so we can just add a graphic to the list.
@@ -86,6 +86,15 @@ impl UnprocessedRaw {
         &self.identifier
     }
 
+    /// Sets whether the metadata passed on to the final object is hidden
+    ///
+    /// # Arguments
+    ///
+    /// * `hidden` - Whether the metadata is hidden
+    pub const fn set_metadata_hidden(&mut self, hidden: bool) {
+        self.metadata.set_hidden(hidden);
+    }
+
     /// Checks if the only modifications are
     ///
     /// * `MainRawBody`
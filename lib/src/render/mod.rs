@@ -0,0 +1,72 @@
+//! Render graphics to images using the tile pages they reference (requires the `render` feature).
+//!
+//! A `SpriteRenderer` loads the images for a set of `TilePage`s, and can then crop the tiles that a
+//! `SpriteGraphic` or `SpriteLayer` points at (including `LARGE_IMAGE` rectangles). Whole `Graphic`s
+//! are rendered for a chosen set of `ConditionTag`s: either by picking the matching sprite, or by
+//! compositing the layers of the matching `LAYER_SET`. Layered graphics can also be rendered for a
+//! `CreatureRenderState` (see `layer_conditions`), which draws the layers that creature would show.
+//! Plant growths are rendered with their plant graphic templates expanded into concrete sprites.
+//!
+//! Tile pages can also be sliced into one PNG per tile (`TilePage::export_tiles`), or packed into a
+//! single `TileAtlas` image with a JSON `AtlasIndex` of where each `(tile_page_id, x, y)` tile is,
+//! for displaying sprites without knowing about tile pages.
+//!
+//! For classic (ASCII mode) previews, a `TilesetRenderer` draws the CP437 glyph of a `Tile` (or any
+//! tile code and `Color`) from a 16x16 tileset image, in the colors of a `ColorPalette`.
+//!
+//! ```rust,no_run
+//! use dfraw_parser::{
+//!     graphic::Graphic,
+//!     metadata::{ObjectType, ParserOptions, RawModuleLocation},
+//!     render::{save_png, SpriteRenderer},
+//!     tags::ConditionTag,
+//! };
+//!
+//! let mut options = ParserOptions::new("path/to/dwarf_fortress");
+//! options.add_location_to_parse(RawModuleLocation::Vanilla);
+//! options.include_graphics();
+//! let raws = dfraw_parser::parse(&options).unwrap().raws;
+//!
+//! let renderer = SpriteRenderer::from_raws(&raws);
+//! for raw in &raws {
+//!     if raw.get_type() != &ObjectType::Graphics {
+//!         continue;
+//!     }
+//!     if let Some(graphic) = raw.as_any().downcast_ref::<Graphic>() {
+//!         let image = renderer.render_graphic(graphic, &[ConditionTag::Default]).unwrap();
+//!         save_png(&image, &format!("{}.png", raw.get_identifier())).unwrap();
+//!     }
+//! }
+//! ```
+
+mod sprite_renderer;
+mod tile_atlas;
+mod tile_page_images;
+mod tileset_renderer;
+
+pub use image::RgbaImage;
+pub use sprite_renderer::SpriteRenderer;
+pub use tile_atlas::{AtlasEntry, AtlasIndex, TileAtlas};
+pub use tileset_renderer::TilesetRenderer;
+
+use std::path::Path;
+
+use crate::ParserError;
+
+/// Save a rendered image as a PNG.
+///
+/// # Arguments
+///
+/// * `image` - The rendered image.
+/// * `path` - Where to save the PNG.
+///
+/// # Errors
+///
+/// * `ParserError::Render` - If the PNG can't be written.
+pub fn save_png<P: AsRef<Path>>(image: &RgbaImage, path: &P) -> Result<(), ParserError> {
+    image
+        .save_with_format(path, image::ImageFormat::Png)
+        .map_err(|e| {
+            ParserError::Render(format!("Unable to save {}: {e}", path.as_ref().display()))
+        })
+}
@@ -0,0 +1,364 @@
+use std::collections::HashMap;
+
+use image::{imageops, RgbaImage};
+use tracing::{debug, warn};
+
+use crate::{
+    dimensions::Dimensions, graphic::Graphic, layer_conditions::CreatureRenderState,
+    metadata::ObjectType, sprite_graphic::SpriteGraphic, sprite_layer::SpriteLayer,
+    sprite_layer_group::SpriteLayerGroup, tags::ConditionTag, tile_page::TilePage,
+    traits::RawObject, ParserError,
+};
+
+/// A loaded tile page image.
+struct TileSheet {
+    /// The whole tile page image.
+    image: RgbaImage,
+    /// The size of a single tile (in pixels).
+    tile_dim: Dimensions,
+}
+
+/// Renders sprites, layers and graphics from the tile page images they reference.
+#[derive(Default)]
+pub struct SpriteRenderer {
+    /// The loaded tile pages, by tile page identifier.
+    tile_sheets: HashMap<String, TileSheet>,
+}
+
+impl SpriteRenderer {
+    /// Create a renderer with no tile pages loaded.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Create a renderer with every tile page in the raws loaded.
+    ///
+    /// Tile pages which can't be loaded are logged and skipped. If more than one tile page has the
+    /// same identifier, the last one wins (like a mod overriding vanilla).
+    ///
+    /// # Arguments
+    ///
+    /// * `raws` - The parsed raws (which should include `ObjectType::TilePage`).
+    #[must_use]
+    pub fn from_raws(raws: &[Box<dyn RawObject>]) -> Self {
+        let mut renderer = Self::new();
+        for raw in raws {
+            if raw.get_type() != &ObjectType::TilePage {
+                continue;
+            }
+            if let Some(tile_page) = raw.as_any().downcast_ref::<TilePage>() {
+                if let Err(e) = renderer.add_tile_page(tile_page) {
+                    warn!("Skipping tile page {}: {e}", tile_page.get_identifier());
+                }
+            }
+        }
+        renderer
+    }
+    /// Load the image for a tile page.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_page` - The tile page to load.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Render` - If the image can't be loaded, or the tile size is invalid.
+    pub fn add_tile_page(&mut self, tile_page: &TilePage) -> Result<(), ParserError> {
+        let tile_dim = tile_page.get_tile_dim();
+        if tile_dim.get_x() <= 0 || tile_dim.get_y() <= 0 {
+            return Err(ParserError::Render(format!(
+                "Tile page {} has an invalid tile size",
+                tile_page.get_identifier()
+            )));
+        }
+        let image = tile_page.load_image()?;
+
+        debug!(
+            "Loaded tile page {} ({}x{})",
+            tile_page.get_identifier(),
+            image.width(),
+            image.height()
+        );
+        self.tile_sheets.insert(
+            tile_page.get_identifier().to_string(),
+            TileSheet { image, tile_dim },
+        );
+        Ok(())
+    }
+    /// Whether a tile page is loaded.
+    #[must_use]
+    pub fn has_tile_page(&self, tile_page_id: &str) -> bool {
+        self.tile_sheets.contains_key(tile_page_id)
+    }
+    /// Crop tiles from a tile page.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_page_id` - The identifier of the tile page.
+    /// * `offset` - The (top-left) tile to crop.
+    /// * `offset2` - The bottom-right tile (inclusive) for large images, or `None` for one tile.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Render` - If the tile page isn't loaded, or the tiles are outside of it.
+    pub fn crop(
+        &self,
+        tile_page_id: &str,
+        offset: Dimensions,
+        offset2: Option<Dimensions>,
+    ) -> Result<RgbaImage, ParserError> {
+        let tile_sheet = self.tile_sheets.get(tile_page_id).ok_or_else(|| {
+            ParserError::Render(format!("Tile page {tile_page_id} is not loaded"))
+        })?;
+        let offset2 = offset2.unwrap_or(offset);
+
+        // The offsets come from the raws, so any of this can overflow for a malformed offset
+        let invalid_offset = || {
+            ParserError::Render(format!(
+                "Invalid tile offset {offset:?}..{offset2:?} on tile page {tile_page_id}"
+            ))
+        };
+        let tile_dim = tile_sheet.tile_dim;
+        let tiles_x = offset2
+            .get_x()
+            .checked_sub(offset.get_x())
+            .and_then(|tiles| tiles.checked_add(1));
+        let tiles_y = offset2
+            .get_y()
+            .checked_sub(offset.get_y())
+            .and_then(|tiles| tiles.checked_add(1));
+        let pixels = |tiles: Option<i32>, size: i32| {
+            tiles
+                .and_then(|tiles| tiles.checked_mul(size))
+                .and_then(|pixels| u32::try_from(pixels).ok())
+        };
+        let (Some(x), Some(y), Some(width), Some(height)) = (
+            pixels(Some(offset.get_x()), tile_dim.get_x()),
+            pixels(Some(offset.get_y()), tile_dim.get_y()),
+            pixels(tiles_x, tile_dim.get_x()),
+            pixels(tiles_y, tile_dim.get_y()),
+        ) else {
+            return Err(invalid_offset());
+        };
+        let (Some(right), Some(bottom)) = (x.checked_add(width), y.checked_add(height)) else {
+            return Err(invalid_offset());
+        };
+
+        if width == 0
+            || height == 0
+            || right > tile_sheet.image.width()
+            || bottom > tile_sheet.image.height()
+        {
+            return Err(ParserError::Render(format!(
+                "Tiles {offset:?}..{offset2:?} are outside of tile page {tile_page_id}"
+            )));
+        }
+
+        Ok(imageops::crop_imm(&tile_sheet.image, x, y, width, height).to_image())
+    }
+    /// Render a single sprite.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Render` - If the sprite's tile page isn't loaded, or its tiles are outside it.
+    pub fn render_sprite(&self, sprite: &SpriteGraphic) -> Result<RgbaImage, ParserError> {
+        let offset2 = sprite
+            .is_large_image()
+            .then(|| sprite.get_offset2())
+            .flatten();
+        self.crop(sprite.get_tile_page_id(), sprite.get_offset(), offset2)
+    }
+    /// Render a single layer.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Render` - If the layer's tile page isn't loaded, or its tiles are outside it.
+    pub fn render_layer(&self, layer: &SpriteLayer) -> Result<RgbaImage, ParserError> {
+        let offset2 = layer
+            .is_large_image()
+            .then(|| layer.get_offset2())
+            .flatten();
+        self.crop(layer.get_tile_page_id(), layer.get_offset(), offset2)
+    }
+    /// Render a graphic for a set of conditions.
+    ///
+    /// If the graphic has layer sets, the first layer set whose name is one of the `conditions` is
+    /// used (or the `DEFAULT` layer set, or the first one). Each layer in it is drawn (in order) if
+    /// all of its (and its layer group's) conditions are in `conditions`; `SHUT_OFF_IF_ITEM_PRESENT` instead turns the layer
+    /// off when it is in `conditions`.
+    ///
+    /// Otherwise the first sprite whose primary condition is one of the `conditions` is used, in the
+    /// order of the `conditions` (preferring sprites whose secondary condition also matches). If no
+    /// sprite matches, the `DEFAULT` sprite (or the first sprite) is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `graphic` - The graphic to render.
+    /// * `conditions` - The conditions to render the graphic for (e.g. `[ConditionTag::Child]`).
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Render` - If the graphic has nothing to draw, or a tile page isn't loaded.
+    pub fn render_graphic(
+        &self,
+        graphic: &Graphic,
+        conditions: &[ConditionTag],
+    ) -> Result<RgbaImage, ParserError> {
+        if let Some((layer_set, groups)) = select_layer_set(graphic, conditions) {
+            debug!(
+                "Rendering layer set {layer_set} of {}",
+                graphic.get_identifier()
+            );
+            let images = groups
+                .iter()
+                .filter(|group| conditions_are_shown(group.get_conditions(), conditions))
+                .flat_map(SpriteLayerGroup::get_layers)
+                .filter(|layer| conditions_are_shown(layer.get_conditions(), conditions))
+                .map(|layer| self.render_layer(layer))
+                .collect::<Result<Vec<RgbaImage>, ParserError>>()?;
+            return composite(&images).ok_or_else(|| {
+                ParserError::Render(format!(
+                    "No layers of {} are shown for {conditions:?}",
+                    graphic.get_identifier()
+                ))
+            });
+        }
+
+        let sprite = select_sprite(graphic.get_sprites(), conditions).ok_or_else(|| {
+            ParserError::Render(format!(
+                "Graphic {} has no sprites or layers",
+                graphic.get_identifier()
+            ))
+        })?;
+        self.render_sprite(sprite)
+    }
+    /// Render one of a plant graphic's growths for a set of conditions.
+    ///
+    /// Plant graphic templates (e.g. `STANDARD_FRUIT_1`) are expanded first, so the sprite is picked
+    /// from the template's tiles like any other sprite (see `render_graphic`). Template tiles for
+    /// tree parts use the `TreeTile` condition, and the first growth stage is the one drawn.
+    ///
+    /// # Arguments
+    ///
+    /// * `graphic` - The plant graphic.
+    /// * `growth` - The name of the growth (e.g. `FRUIT`).
+    /// * `conditions` - The conditions to render the growth for (e.g. `[ConditionTag::Shrub]`).
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Render` - If the graphic doesn't have the growth, or a tile page isn't loaded.
+    pub fn render_growth(
+        &self,
+        graphic: &Graphic,
+        growth: &str,
+        conditions: &[ConditionTag],
+    ) -> Result<RgbaImage, ParserError> {
+        let sprites = graphic.get_expanded_growth(growth);
+        let sprite = select_sprite(&sprites, conditions).ok_or_else(|| {
+            ParserError::Render(format!(
+                "Graphic {} has no sprites for growth {growth}",
+                graphic.get_identifier()
+            ))
+        })?;
+        self.render_sprite(sprite)
+    }
+    /// Render one of a graphic's layer sets for a creature, drawing the layers which are visible
+    /// for its state (in order).
+    ///
+    /// # Arguments
+    ///
+    /// * `graphic` - The graphic to render.
+    /// * `layer_set` - The name of the layer set (e.g. `PORTRAIT`).
+    /// * `state` - The state of the creature.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Render` - If no layers are visible, or a tile page isn't loaded.
+    pub fn render_creature(
+        &self,
+        graphic: &Graphic,
+        layer_set: &str,
+        state: &CreatureRenderState,
+    ) -> Result<RgbaImage, ParserError> {
+        let images = state
+            .visible_graphic_layers(graphic, layer_set)
+            .into_iter()
+            .map(|layer| self.render_layer(layer))
+            .collect::<Result<Vec<RgbaImage>, ParserError>>()?;
+        composite(&images).ok_or_else(|| {
+            ParserError::Render(format!(
+                "No layers of {} layer set {layer_set} are visible",
+                graphic.get_identifier()
+            ))
+        })
+    }
+}
+
+/// Pick the layer set to render, if the graphic has any.
+fn select_layer_set<'a>(
+    graphic: &'a Graphic,
+    conditions: &[ConditionTag],
+) -> Option<&'a (String, Vec<SpriteLayerGroup>)> {
+    let layer_sets = graphic.get_layers();
+    conditions
+        .iter()
+        .find_map(|condition| {
+            layer_sets
+                .iter()
+                .find(|(name, _)| ConditionTag::from_token(name) == Some(*condition))
+        })
+        .or_else(|| layer_sets.iter().find(|(name, _)| name == "DEFAULT"))
+        .or_else(|| layer_sets.first())
+}
+
+/// Whether a layer (or layer group) with these conditions is drawn for a set of conditions.
+fn conditions_are_shown(
+    layer_conditions: &[(ConditionTag, String)],
+    conditions: &[ConditionTag],
+) -> bool {
+    layer_conditions
+        .iter()
+        .all(|(condition, _)| match condition {
+            ConditionTag::ShutOffIfItemPresent => !conditions.contains(condition),
+            _ => conditions.contains(condition),
+        })
+}
+
+/// Pick the sprite to render from a list of sprites.
+fn select_sprite<'a>(
+    sprites: &'a [SpriteGraphic],
+    conditions: &[ConditionTag],
+) -> Option<&'a SpriteGraphic> {
+    let matching = |condition: &ConditionTag| {
+        let mut candidates = sprites
+            .iter()
+            .filter(|sprite| sprite.get_primary_condition() == *condition)
+            .peekable();
+        let first = candidates.peek().copied();
+        candidates
+            .find(|sprite| {
+                sprite
+                    .get_secondary_condition()
+                    .is_some_and(|secondary| conditions.contains(&secondary))
+            })
+            .or(first)
+    };
+
+    conditions
+        .iter()
+        .find_map(matching)
+        .or_else(|| matching(&ConditionTag::Default))
+        .or_else(|| sprites.first())
+}
+
+/// Draw images on top of each other (in order), on a canvas large enough for all of them.
+fn composite(images: &[RgbaImage]) -> Option<RgbaImage> {
+    let width = images.iter().map(RgbaImage::width).max()?;
+    let height = images.iter().map(RgbaImage::height).max()?;
+
+    let mut canvas = RgbaImage::new(width, height);
+    for image in images {
+        imageops::overlay(&mut canvas, image, 0, 0);
+    }
+    Some(canvas)
+}
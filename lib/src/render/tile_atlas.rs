@@ -0,0 +1,222 @@
+use std::path::Path;
+
+use image::{imageops, RgbaImage};
+use tracing::warn;
+
+use crate::{
+    dimensions::Dimensions, metadata::ObjectType, tile_page::TilePage, traits::RawObject,
+    ParserError,
+};
+
+use super::save_png;
+
+/// Where one tile of a tile page is in an atlas image.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AtlasEntry {
+    /// The identifier of the tile page the tile is from
+    pub tile_page_id: String,
+    /// The tile's x offset on the tile page (in tiles)
+    pub x: i32,
+    /// The tile's y offset on the tile page (in tiles)
+    pub y: i32,
+    /// The left edge of the tile in the atlas (in pixels)
+    pub pixel_x: u32,
+    /// The top edge of the tile in the atlas (in pixels)
+    pub pixel_y: u32,
+    /// The width of the tile (in pixels)
+    pub width: u32,
+    /// The height of the tile (in pixels)
+    pub height: u32,
+}
+
+/// The JSON index of an atlas image, mapping tiles to their place in the image.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AtlasIndex {
+    /// The file name of the atlas image (set when the atlas is saved)
+    pub image: String,
+    /// The width of the atlas image (in pixels)
+    pub width: u32,
+    /// The height of the atlas image (in pixels)
+    pub height: u32,
+    /// Every tile in the atlas
+    pub tiles: Vec<AtlasEntry>,
+}
+
+impl AtlasIndex {
+    /// Find where a tile is in the atlas.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_page_id` - The identifier of the tile page.
+    /// * `offset` - The tile's offset on the tile page (in tiles).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&AtlasEntry>` - The tile's entry, or `None` if it isn't in the atlas.
+    #[must_use]
+    pub fn find(&self, tile_page_id: &str, offset: Dimensions) -> Option<&AtlasEntry> {
+        self.tiles.iter().find(|entry| {
+            entry.tile_page_id == tile_page_id
+                && entry.x == offset.get_x()
+                && entry.y == offset.get_y()
+        })
+    }
+}
+
+/// A sprite atlas: the tiles of several tile pages packed into one image, with an index of where
+/// each tile is.
+///
+/// Tiles are packed in rows (in tile page order), skipping tiles which are completely transparent.
+#[derive(Debug, Clone, Default)]
+pub struct TileAtlas {
+    image: RgbaImage,
+    index: AtlasIndex,
+}
+
+impl TileAtlas {
+    /// The widest an atlas is made (unless a single tile is wider).
+    pub const MAX_WIDTH: u32 = 2048;
+
+    /// Build an atlas from some tile pages.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_pages` - The tile pages to pack into the atlas.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Render` - If a tile page's image can't be loaded, or its tile size is invalid.
+    pub fn build(tile_pages: &[&TilePage]) -> Result<Self, ParserError> {
+        let mut tiles = Vec::new();
+        for tile_page in tile_pages {
+            for (offset, tile) in tile_page.slice_tiles()? {
+                tiles.push((tile_page.get_identifier().to_string(), offset, tile));
+            }
+        }
+        Ok(Self::pack(&tiles))
+    }
+    /// Build an atlas from every tile page in the raws.
+    ///
+    /// Tile pages which can't be loaded are logged and skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `raws` - The parsed raws (which should include `ObjectType::TilePage`).
+    #[must_use]
+    pub fn from_raws(raws: &[Box<dyn RawObject>]) -> Self {
+        let mut tiles = Vec::new();
+        for raw in raws {
+            if raw.get_type() != &ObjectType::TilePage {
+                continue;
+            }
+            let Some(tile_page) = raw.as_any().downcast_ref::<TilePage>() else {
+                continue;
+            };
+            match tile_page.slice_tiles() {
+                Ok(page_tiles) => {
+                    tiles.extend(page_tiles.into_iter().map(|(offset, tile)| {
+                        (tile_page.get_identifier().to_string(), offset, tile)
+                    }));
+                }
+                Err(e) => warn!("Skipping tile page {}: {e}", tile_page.get_identifier()),
+            }
+        }
+        Self::pack(&tiles)
+    }
+    /// Pack tiles into an atlas, placing them on shelves (rows) and starting a new shelf when the
+    /// current one is full.
+    fn pack(tiles: &[(String, Dimensions, RgbaImage)]) -> Self {
+        let atlas_width = tiles
+            .iter()
+            .map(|(_, _, tile)| tile.width())
+            .fold(Self::MAX_WIDTH, u32::max);
+
+        let mut entries = Vec::with_capacity(tiles.len());
+        let (mut shelf_x, mut shelf_y, mut shelf_height) = (0, 0, 0);
+        for (tile_page_id, offset, tile) in tiles {
+            if shelf_x + tile.width() > atlas_width {
+                shelf_x = 0;
+                shelf_y += shelf_height;
+                shelf_height = 0;
+            }
+            entries.push(AtlasEntry {
+                tile_page_id: tile_page_id.clone(),
+                x: offset.get_x(),
+                y: offset.get_y(),
+                pixel_x: shelf_x,
+                pixel_y: shelf_y,
+                width: tile.width(),
+                height: tile.height(),
+            });
+            shelf_x += tile.width();
+            shelf_height = shelf_height.max(tile.height());
+        }
+
+        let width = entries
+            .iter()
+            .map(|entry| entry.pixel_x + entry.width)
+            .max()
+            .unwrap_or_default();
+        let height = shelf_y + shelf_height;
+
+        let mut image = RgbaImage::new(width, height);
+        for ((_, _, tile), entry) in tiles.iter().zip(&entries) {
+            imageops::replace(
+                &mut image,
+                tile,
+                i64::from(entry.pixel_x),
+                i64::from(entry.pixel_y),
+            );
+        }
+
+        Self {
+            image,
+            index: AtlasIndex {
+                image: String::new(),
+                width,
+                height,
+                tiles: entries,
+            },
+        }
+    }
+    /// The atlas image.
+    #[must_use]
+    pub const fn image(&self) -> &RgbaImage {
+        &self.image
+    }
+    /// The index of where each tile is in the atlas image.
+    #[must_use]
+    pub const fn index(&self) -> &AtlasIndex {
+        &self.index
+    }
+    /// Save the atlas as a PNG, with its index as JSON.
+    ///
+    /// The index's `image` is set to the file name of the PNG, so the two can be kept side by side.
+    ///
+    /// # Arguments
+    ///
+    /// * `image_path` - Where to save the atlas PNG.
+    /// * `index_path` - Where to save the JSON index.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Render` - If the PNG can't be written.
+    /// * `ParserError::Io` - If the index can't be written.
+    pub fn save<P: AsRef<Path>, Q: AsRef<Path>>(
+        &mut self,
+        image_path: &P,
+        index_path: &Q,
+    ) -> Result<(), ParserError> {
+        save_png(&self.image, image_path)?;
+        self.index.image = image_path
+            .as_ref()
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let index = serde_json::to_vec_pretty(&self.index).map_err(std::io::Error::from)?;
+        std::fs::write(index_path, index)?;
+        Ok(())
+    }
+}
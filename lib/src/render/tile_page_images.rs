@@ -0,0 +1,105 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use image::{imageops, RgbaImage};
+
+use crate::{dimensions::Dimensions, tile_page::TilePage, traits::RawObject, ParserError};
+
+use super::save_png;
+
+impl TilePage {
+    /// Load the image the tile page's `FILE` points at.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Render` - If the image can't be loaded.
+    pub fn load_image(&self) -> Result<RgbaImage, ParserError> {
+        Ok(image::open(self.get_file())
+            .map_err(|e| {
+                ParserError::Render(format!("Unable to load {}: {e}", self.get_file().display()))
+            })?
+            .into_rgba8())
+    }
+    /// Slice the tile page's image into its tiles.
+    ///
+    /// Tiles which are completely transparent are skipped.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(Dimensions, RgbaImage)>` - The offset of each tile on the page (in tiles, as used by
+    ///   sprites and layers) and its image, in row order.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Render` - If the image can't be loaded, or the tile size is invalid.
+    pub fn slice_tiles(&self) -> Result<Vec<(Dimensions, RgbaImage)>, ParserError> {
+        let tile_dim = self.get_tile_dim();
+        let (Ok(tile_width @ 1..), Ok(tile_height @ 1..)) = (
+            u32::try_from(tile_dim.get_x()),
+            u32::try_from(tile_dim.get_y()),
+        ) else {
+            return Err(ParserError::Render(format!(
+                "Tile page {} has an invalid tile size",
+                self.get_identifier()
+            )));
+        };
+        let image = self.load_image()?;
+
+        let mut tiles = Vec::new();
+        for tile_y in 0..image.height() / tile_height {
+            for tile_x in 0..image.width() / tile_width {
+                let tile = imageops::crop_imm(
+                    &image,
+                    tile_x * tile_width,
+                    tile_y * tile_height,
+                    tile_width,
+                    tile_height,
+                )
+                .to_image();
+                if tile.pixels().all(|pixel| pixel.0[3] == 0) {
+                    continue;
+                }
+                let (Ok(x), Ok(y)) = (i32::try_from(tile_x), i32::try_from(tile_y)) else {
+                    continue;
+                };
+                tiles.push((Dimensions::from_xy(x, y), tile));
+            }
+        }
+        Ok(tiles)
+    }
+    /// Write each tile of the tile page to its own PNG, named `<tile page id>_<x>_<y>.png`.
+    ///
+    /// Tiles which are completely transparent are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory to write the tiles to (created if it doesn't exist).
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<PathBuf>` - The paths of the written tiles.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Render` - If the image can't be loaded, or a tile can't be written.
+    /// * `ParserError::Io` - If the directory can't be created.
+    pub fn export_tiles<P: AsRef<Path>>(&self, directory: &P) -> Result<Vec<PathBuf>, ParserError> {
+        let directory = directory.as_ref();
+        fs::create_dir_all(directory)?;
+
+        let mut paths = Vec::new();
+        for (offset, tile) in self.slice_tiles()? {
+            let path = directory.join(format!(
+                "{}_{}_{}.png",
+                self.get_identifier(),
+                offset.get_x(),
+                offset.get_y()
+            ));
+            save_png(&tile, &path)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
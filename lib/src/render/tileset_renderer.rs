@@ -0,0 +1,147 @@
+use std::path::Path;
+
+use image::{imageops, GenericImageView, Rgba, RgbaImage};
+
+use crate::{color::Color, color_palette::ColorPalette, tile::Tile, ParserError};
+
+/// Renders classic (ASCII mode) tiles using a 16x16 CP437 tileset image, the way the game does
+/// without graphics.
+///
+/// The tileset is a grid of 16x16 glyphs in CP437 order. Glyphs are drawn in the tile's foreground
+/// color (white parts of the glyph become exactly the foreground color) over its background color.
+/// Transparent or magenta (`255:0:255`) parts of the tileset show the background.
+#[derive(Debug, Clone)]
+pub struct TilesetRenderer {
+    /// The tileset image.
+    tileset: RgbaImage,
+    /// The width of one glyph (in pixels).
+    glyph_width: u32,
+    /// The height of one glyph (in pixels).
+    glyph_height: u32,
+    /// The colors to draw with.
+    palette: ColorPalette,
+}
+
+impl TilesetRenderer {
+    /// Create a renderer from a tileset image.
+    ///
+    /// # Arguments
+    ///
+    /// * `tileset` - The tileset image (a 16x16 grid of glyphs).
+    /// * `palette` - The colors to draw with.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Render` - If the tileset isn't a 16x16 grid.
+    pub fn new(tileset: RgbaImage, palette: ColorPalette) -> Result<Self, ParserError> {
+        if tileset.width() < 16
+            || tileset.height() < 16
+            || !tileset.width().is_multiple_of(16)
+            || !tileset.height().is_multiple_of(16)
+        {
+            return Err(ParserError::Render(format!(
+                "A tileset must be a 16x16 grid of glyphs, not {}x{} pixels",
+                tileset.width(),
+                tileset.height()
+            )));
+        }
+        Ok(Self {
+            glyph_width: tileset.width() / 16,
+            glyph_height: tileset.height() / 16,
+            tileset,
+            palette,
+        })
+    }
+    /// Create a renderer from a tileset image file (e.g. `data/art/curses_640x300.png`).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the tileset image.
+    /// * `palette` - The colors to draw with.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Render` - If the image can't be loaded, or isn't a 16x16 grid.
+    pub fn from_file<P: AsRef<Path>>(path: &P, palette: ColorPalette) -> Result<Self, ParserError> {
+        let tileset = image::open(path)
+            .map_err(|e| {
+                ParserError::Render(format!("Unable to load {}: {e}", path.as_ref().display()))
+            })?
+            .into_rgba8();
+        Self::new(tileset, palette)
+    }
+    /// The palette the renderer draws with.
+    #[must_use]
+    pub const fn palette(&self) -> &ColorPalette {
+        &self.palette
+    }
+    /// The size of one glyph (in pixels), as `(width, height)`.
+    #[must_use]
+    pub const fn glyph_size(&self) -> (u32, u32) {
+        (self.glyph_width, self.glyph_height)
+    }
+    /// Render a glyph in a color.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The CP437 code of the glyph.
+    /// * `color` - The color to draw it in (`foreground:background:brightness`).
+    #[must_use]
+    pub fn render_glyph(&self, code: u8, color: &Color) -> RgbaImage {
+        let glyph = imageops::crop_imm(
+            &self.tileset,
+            u32::from(code % 16) * self.glyph_width,
+            u32::from(code / 16) * self.glyph_height,
+            self.glyph_width,
+            self.glyph_height,
+        );
+        let foreground = color.get_foreground_rgb(&self.palette);
+        let background = color.get_background_rgb(&self.palette);
+
+        let mut image = RgbaImage::new(self.glyph_width, self.glyph_height);
+        for (x, y, pixel) in glyph.pixels() {
+            let Rgba([r, g, b, a]) = pixel;
+            let alpha = if [r, g, b] == [255, 0, 255] { 0 } else { a };
+            let mut out = [0, 0, 0, 255];
+            for (channel, glyph_channel) in [r, g, b].into_iter().enumerate() {
+                out[channel] = blend(
+                    background[channel],
+                    tint(glyph_channel, foreground[channel]),
+                    alpha,
+                );
+            }
+            image.put_pixel(x, y, Rgba(out));
+        }
+        image
+    }
+    /// Render a tile (like a creature caste's `CREATURE_TILE` and `COLOR`).
+    ///
+    /// # Arguments
+    ///
+    /// * `tile` - The tile to render.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Render` - If the tile's character isn't a CP437 tile.
+    pub fn render_tile(&self, tile: &Tile) -> Result<RgbaImage, ParserError> {
+        let code = tile.get_character_code().ok_or_else(|| {
+            ParserError::Render(format!(
+                "{:?} is not a CP437 tile character",
+                tile.get_character()
+            ))
+        })?;
+        Ok(self.render_glyph(code, &tile.get_color()))
+    }
+}
+
+/// Multiply a glyph channel by a color channel, so white glyphs become the color.
+fn tint(glyph: u8, color: u8) -> u8 {
+    u8::try_from(u16::from(glyph) * u16::from(color) / 255).unwrap_or(u8::MAX)
+}
+
+/// Blend a channel over another by an alpha.
+fn blend(under: u8, over: u8, alpha: u8) -> u8 {
+    let alpha = u16::from(alpha);
+    u8::try_from((u16::from(over) * alpha + u16::from(under) * (255 - alpha)) / 255)
+        .unwrap_or(u8::MAX)
+}
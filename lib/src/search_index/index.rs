@@ -0,0 +1,349 @@
+//! The `SearchIndex`, an inverted index over the raws which ranks them by how well they match.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::{
+    creature::Creature, entity::Entity, inorganic::Inorganic, plant::Plant, traits::RawObject,
+    ParseResult,
+};
+
+use super::tokenize::{
+    edit_distance, names_of, tokenize, TokenNames, BIOME_TOKEN_NAMES, CASTE_TOKEN_NAMES,
+    CREATURE_TOKEN_NAMES, ENTITY_TOKEN_NAMES, INORGANIC_TOKEN_NAMES, PLANT_TOKEN_NAMES,
+};
+
+/// How much a match counts for a term which was only found by its prefix (scaled by how much of
+/// the term the prefix covers).
+const PREFIX_QUALITY: f32 = 0.8;
+/// How much a match counts for a term which is one edit away from the searched term.
+const ONE_EDIT_QUALITY: f32 = 0.6;
+/// How much a match counts for a term which is two edits away from the searched term.
+const TWO_EDIT_QUALITY: f32 = 0.4;
+
+/// The part of a raw a term was found in, which decides how much a match on it counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchField {
+    /// The name of the raw (or of one of a creature's castes)
+    Name,
+    /// The identifier of the raw
+    Identifier,
+    /// The raw token name of one of the raw's tags, biomes or creature classes (e.g. `FLIER`)
+    Token,
+    /// Any other search terms of the raw
+    Text,
+    /// The description of one of a creature's castes
+    Description,
+}
+
+/// A raw found by a search.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    /// The object id of the raw
+    pub object_id: String,
+    /// How well the raw matches the search (higher is better)
+    pub score: f32,
+}
+
+/// An inverted index over raws, for ranked text search with typo tolerance and prefix matching.
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    /// The object ids of the indexed raws
+    object_ids: Vec<String>,
+    /// The raws each term is found in (by their position in `object_ids`), with the boost of the
+    /// best field it's found in
+    postings: BTreeMap<String, Vec<(usize, f32)>>,
+}
+
+impl SearchField {
+    /// Get how much a match in the field counts, compared to other fields.
+    ///
+    /// # Returns
+    ///
+    /// * `f32` - The boost for the field (names count the most, descriptions the least).
+    #[must_use]
+    pub const fn get_boost(self) -> f32 {
+        match self {
+            Self::Name => 4.0,
+            Self::Identifier => 3.0,
+            Self::Token => 2.0,
+            Self::Text => 1.0,
+            Self::Description => 0.5,
+        }
+    }
+}
+
+impl SearchIndex {
+    /// Build an index over raws.
+    ///
+    /// # Arguments
+    ///
+    /// * `raws` - The raws to index.
+    ///
+    /// # Returns
+    ///
+    /// * `SearchIndex` - The index.
+    #[must_use]
+    pub fn new(raws: &[Box<dyn RawObject>]) -> Self {
+        let mut index = Self::default();
+        for raw in raws {
+            index.add_raw(raw.as_ref());
+        }
+        index
+    }
+    /// Build an index over the raws in a parse result.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The result of parsing.
+    ///
+    /// # Returns
+    ///
+    /// * `SearchIndex` - The index.
+    #[must_use]
+    pub fn from_parse_result(result: &ParseResult) -> Self {
+        Self::new(&result.raws)
+    }
+    /// Add a raw to the index.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw to add.
+    pub fn add_raw(&mut self, raw: &dyn RawObject) {
+        let document = self.object_ids.len();
+        self.object_ids.push(raw.get_object_id().to_string());
+
+        let fields = raw_fields(raw);
+        // Search terms which only come from descriptions count as description terms
+        let description_terms: HashSet<String> = fields
+            .iter()
+            .filter(|(field, _)| *field == SearchField::Description)
+            .flat_map(|(_, text)| tokenize(text))
+            .collect();
+        for (field, text) in fields {
+            for term in tokenize(&text) {
+                let boost = if field == SearchField::Text && description_terms.contains(&term) {
+                    SearchField::Description.get_boost()
+                } else {
+                    field.get_boost()
+                };
+                let postings = self.postings.entry(term).or_default();
+                match postings.last_mut() {
+                    Some((last, best)) if *last == document => *best = best.max(boost),
+                    _ => postings.push((document, boost)),
+                }
+            }
+        }
+    }
+    /// Get the number of raws in the index.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of raws.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.object_ids.len()
+    }
+    /// Check whether the index is empty.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether no raws are indexed.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.object_ids.is_empty()
+    }
+    /// Search the index.
+    ///
+    /// Every word searched for must match a term of the raw, either exactly, as the start of a
+    /// term (`cav` matches `cave`), or with a typo (one edit for words of 4 to 7 letters, two
+    /// for longer words). Raws are ranked by how well and where the words match, with matches in
+    /// names counting the most and matches in descriptions the least, and rare terms counting
+    /// more than common ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to search for (e.g. `giant cave spider`).
+    /// * `limit` - The most results to return.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<SearchHit>` - The object ids of the matching raws and their scores, best first (raws
+    ///   with the same score are in the order they were indexed).
+    #[must_use]
+    pub fn search(&self, text: &str, limit: usize) -> Vec<SearchHit> {
+        let mut words = tokenize(text);
+        words.sort();
+        words.dedup();
+        if words.is_empty() {
+            return Vec::new();
+        }
+
+        let mut scores: Option<HashMap<usize, f32>> = None;
+        for word in &words {
+            let word_scores = self.score_word(word);
+            scores = Some(match scores {
+                None => word_scores,
+                // Keep only the raws which matched the other words too
+                Some(scores) => scores
+                    .into_iter()
+                    .filter_map(|(document, score)| {
+                        word_scores
+                            .get(&document)
+                            .map(|word_score| (document, score + word_score))
+                    })
+                    .collect(),
+            });
+        }
+
+        let mut hits: Vec<(usize, f32)> = scores.unwrap_or_default().into_iter().collect();
+        hits.sort_by(|(a, a_score), (b, b_score)| b_score.total_cmp(a_score).then(a.cmp(b)));
+        hits.into_iter()
+            .take(limit)
+            .map(|(document, score)| SearchHit {
+                object_id: self.object_ids[document].clone(),
+                score,
+            })
+            .collect()
+    }
+    /// Complete the start of a word with the terms in the index.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The start of the word (e.g. `cav`).
+    /// * `limit` - The most completions to return.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<String>` - The terms starting with the prefix, the ones found in the most raws first.
+    #[must_use]
+    pub fn complete(&self, prefix: &str, limit: usize) -> Vec<String> {
+        let prefix = prefix.trim().to_lowercase();
+        if prefix.is_empty() {
+            return Vec::new();
+        }
+        let mut terms: Vec<(&String, usize)> = self
+            .postings
+            .range(prefix.clone()..)
+            .take_while(|(term, _)| term.starts_with(&prefix))
+            .map(|(term, postings)| (term, postings.len()))
+            .collect();
+        terms.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        terms
+            .into_iter()
+            .take(limit)
+            .map(|(term, _)| term.clone())
+            .collect()
+    }
+    /// Score the raws matching a searched word, by the best term each matches it with.
+    #[allow(clippy::cast_precision_loss)]
+    fn score_word(&self, word: &str) -> HashMap<usize, f32> {
+        let word_length = word.chars().count();
+        let max_edits = match word_length {
+            0..=3 => 0,
+            4..=7 => 1,
+            _ => 2,
+        };
+        let mut matches: Vec<(&Vec<(usize, f32)>, f32)> = Vec::new();
+        // Exact and prefix matches
+        for (term, postings) in self
+            .postings
+            .range(word.to_string()..)
+            .take_while(|(term, _)| term.starts_with(word))
+        {
+            let quality = if term == word {
+                1.0
+            } else {
+                PREFIX_QUALITY * word_length as f32 / term.chars().count() as f32
+            };
+            matches.push((postings, quality));
+        }
+        // Typos
+        if max_edits > 0 {
+            for (term, postings) in &self.postings {
+                if term.starts_with(word) {
+                    continue;
+                }
+                match edit_distance(word, term, max_edits) {
+                    Some(1) => matches.push((postings, ONE_EDIT_QUALITY)),
+                    Some(2) => matches.push((postings, TWO_EDIT_QUALITY)),
+                    _ => (),
+                }
+            }
+        }
+
+        let document_count = self.object_ids.len() as f32;
+        let mut scores: HashMap<usize, f32> = HashMap::new();
+        for (postings, quality) in matches {
+            let frequency = postings.len() as f32;
+            let rarity = ((document_count - frequency + 0.5) / (frequency + 0.5)).ln_1p();
+            for &(document, boost) in postings {
+                let score = quality * boost * rarity;
+                let best = scores.entry(document).or_default();
+                *best = best.max(score);
+            }
+        }
+        scores
+    }
+}
+
+/// Add the raw token names of tags to a list of fields.
+fn add_tokens<'a, T: 'a>(
+    fields: &mut Vec<(SearchField, String)>,
+    names: &TokenNames<T>,
+    tags: impl IntoIterator<Item = &'a T>,
+) {
+    for tag in tags {
+        for name in names_of(names, tag) {
+            fields.push((SearchField::Token, (*name).to_string()));
+        }
+    }
+}
+
+/// Get the text of a raw to index, and the fields it's from.
+fn raw_fields(raw: &dyn RawObject) -> Vec<(SearchField, String)> {
+    let mut fields = vec![
+        (SearchField::Name, raw.get_name().to_string()),
+        (SearchField::Identifier, raw.get_identifier().to_string()),
+    ];
+
+    let any = raw.as_any();
+    if let Some(creature) = any.downcast_ref::<Creature>() {
+        add_tokens(&mut fields, &CREATURE_TOKEN_NAMES, &creature.get_tags());
+        add_tokens(&mut fields, &BIOME_TOKEN_NAMES, &creature.get_biomes());
+        for caste in creature.get_castes() {
+            if let Some(name) = caste.get_caste_name() {
+                fields.extend(
+                    name.as_vec()
+                        .into_iter()
+                        .map(|name| (SearchField::Name, name)),
+                );
+            }
+            add_tokens(&mut fields, &CASTE_TOKEN_NAMES, caste.get_tags());
+            for class in caste.get_creature_classes() {
+                fields.push((SearchField::Token, class.clone()));
+            }
+            if let Some(description) = caste.get_description() {
+                fields.push((SearchField::Description, description.to_string()));
+            }
+        }
+    } else if let Some(plant) = any.downcast_ref::<Plant>() {
+        add_tokens(&mut fields, &PLANT_TOKEN_NAMES, plant.get_tags());
+        add_tokens(&mut fields, &BIOME_TOKEN_NAMES, &plant.get_biomes());
+    } else if let Some(inorganic) = any.downcast_ref::<Inorganic>() {
+        add_tokens(&mut fields, &INORGANIC_TOKEN_NAMES, inorganic.get_tags());
+    } else if let Some(entity) = any.downcast_ref::<Entity>() {
+        add_tokens(&mut fields, &ENTITY_TOKEN_NAMES, entity.get_tags());
+        if let Some(creature) = entity.get_creature() {
+            fields.push((SearchField::Token, creature.to_string()));
+        }
+    }
+
+    // Everything else the raw can be searched by
+    fields.extend(
+        raw.get_search_vec()
+            .into_iter()
+            .map(|text| (SearchField::Text, text)),
+    );
+    fields
+}
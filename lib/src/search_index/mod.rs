@@ -0,0 +1,38 @@
+//! Ranked full-text search over the parsed raws.
+//!
+//! A `SearchIndex` is an inverted index built from a `ParseResult` (or any list of raws). Unlike
+//! `build_search_string`, raw token names like `LARGE_ROAMING` are kept whole (and also indexed by
+//! their words), matches are ranked with names counting more than identifiers, tags and
+//! descriptions, and searches tolerate typos and unfinished words.
+//!
+//! ```rust
+//! use dfraw_parser::{
+//!     creature::Creature, metadata::RawMetadata, search_index::SearchIndex, traits::RawObject,
+//! };
+//!
+//! let mut spider = Creature::new("SPIDER_CAVE_GIANT", &RawMetadata::default());
+//! spider.parse_tag("NAME", "giant cave spider:giant cave spiders:giant cave spider");
+//! let mut cave_crab = Creature::new("CRAB_CAVE", &RawMetadata::default());
+//! cave_crab.parse_tag("NAME", "cave crab:cave crabs:cave crab");
+//! let raws: Vec<Box<dyn RawObject>> = vec![Box::new(cave_crab), Box::new(spider)];
+//!
+//! let index = SearchIndex::new(&raws);
+//! let hits = index.search("giant cav spider", 10);
+//! assert_eq!(hits.len(), 1);
+//! assert_eq!(hits[0].object_id, raws[1].get_object_id());
+//!
+//! // Typos still find the creature
+//! assert_eq!(index.search("spidr", 10)[0].object_id, raws[1].get_object_id());
+//! // Raw token names are kept whole
+//! assert_eq!(index.search("CRAB_CAVE", 10)[0].object_id, raws[0].get_object_id());
+//! assert_eq!(index.complete("spid", 5), vec!["spider", "spider_cave_giant", "spiders"]);
+//! ```
+
+mod index;
+mod tokenize;
+
+pub use index::{SearchField, SearchHit, SearchIndex};
+pub use tokenize::{
+    names_of, TokenNames, BIOME_TOKEN_NAMES, CASTE_TOKEN_NAMES, CREATURE_TOKEN_NAMES,
+    ENTITY_TOKEN_NAMES, INORGANIC_TOKEN_NAMES, PLANT_TOKEN_NAMES,
+};
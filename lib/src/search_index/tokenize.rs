@@ -0,0 +1,123 @@
+//! Splitting text into the terms of the search index, and finding the raw token names of tags.
+
+use std::{collections::HashMap, mem::Discriminant};
+
+use once_cell::sync::Lazy;
+
+use crate::{
+    raw_definitions::{
+        BIOME_TOKENS, CASTE_TOKENS, CREATURE_TOKENS, ENTITY_TOKENS, INORGANIC_TOKENS, PLANT_TOKENS,
+    },
+    tags::{BiomeTag, CasteTag, CreatureTag, EntityTag, InorganicTag, PlantTag},
+};
+
+/// The raw token names for each kind of tag (a tag can have more than one name).
+pub type TokenNames<T> = HashMap<Discriminant<T>, Vec<&'static str>>;
+
+/// The raw token names of creature tags.
+pub static CREATURE_TOKEN_NAMES: Lazy<TokenNames<CreatureTag>> =
+    Lazy::new(|| token_names(&CREATURE_TOKENS));
+/// The raw token names of caste tags.
+pub static CASTE_TOKEN_NAMES: Lazy<TokenNames<CasteTag>> = Lazy::new(|| token_names(&CASTE_TOKENS));
+/// The raw token names of plant tags.
+pub static PLANT_TOKEN_NAMES: Lazy<TokenNames<PlantTag>> = Lazy::new(|| token_names(&PLANT_TOKENS));
+/// The raw token names of inorganic tags.
+pub static INORGANIC_TOKEN_NAMES: Lazy<TokenNames<InorganicTag>> =
+    Lazy::new(|| token_names(&INORGANIC_TOKENS));
+/// The raw token names of entity tags.
+pub static ENTITY_TOKEN_NAMES: Lazy<TokenNames<EntityTag>> =
+    Lazy::new(|| token_names(&ENTITY_TOKENS));
+/// The raw token names of biomes.
+pub static BIOME_TOKEN_NAMES: Lazy<TokenNames<BiomeTag>> = Lazy::new(|| token_names(&BIOME_TOKENS));
+
+/// Reverse a map of raw tokens, so tags can be looked up by their kind (ignoring their values).
+///
+/// The names of each kind of tag are sorted, so the first one doesn't depend on the order of the
+/// map.
+fn token_names<T>(tokens: &'static phf::Map<&'static str, T>) -> TokenNames<T> {
+    let mut names: TokenNames<T> = HashMap::new();
+    for (name, tag) in tokens {
+        names
+            .entry(std::mem::discriminant(tag))
+            .or_default()
+            .push(name);
+    }
+    for tag_names in names.values_mut() {
+        tag_names.sort_unstable();
+    }
+    names
+}
+
+/// Get the raw token names of a tag (e.g. `LARGE_ROAMING` for `CreatureTag::LargeRoaming`),
+/// sorted by name.
+///
+/// # Arguments
+///
+/// * `names` - The token names of the kind of tag (e.g. `CREATURE_TOKEN_NAMES`).
+/// * `tag` - The tag to get the names of.
+///
+/// # Returns
+///
+/// * `&[&str]` - The raw token names (empty if the tag has no token).
+pub fn names_of<'a, T>(names: &'a TokenNames<T>, tag: &T) -> &'a [&'static str] {
+    names
+        .get(&std::mem::discriminant(tag))
+        .map_or(&[], Vec::as_slice)
+}
+
+/// Split text into lowercase terms.
+///
+/// Words are split on anything that isn't a letter, number or underscore, so raw token names
+/// (like `LARGE_ROAMING` or `GIANT_CAVE_SPIDER`) are kept whole. Their parts are added as terms
+/// too, so searching for `cave` still finds them.
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for word in text.split(|c: char| !(c.is_alphanumeric() || c == '_')) {
+        let word = word.trim_matches('_').to_lowercase();
+        if word.is_empty() {
+            continue;
+        }
+        if word.contains('_') {
+            terms.extend(
+                word.split('_')
+                    .filter(|part| !part.is_empty())
+                    .map(String::from),
+            );
+        }
+        terms.push(word);
+    }
+    terms
+}
+
+/// Get the edit distance between two terms (counting a swap of two letters as one edit), or
+/// `None` if it's more than `max`.
+pub fn edit_distance(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    // Rows of the (optimal string alignment) distance table
+    let mut before: Vec<usize> = Vec::new();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut current = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        if current.iter().min().is_some_and(|&min| min > max) {
+            return None;
+        }
+        before = previous;
+        previous = current;
+    }
+
+    Some(previous[b.len()]).filter(|&distance| distance <= max)
+}
@@ -0,0 +1,472 @@
+//! Work out how a syndrome plays out on a creature: which effects it gets, when they start, peak
+//! and end, and how severe they are at any tick.
+//!
+//! The raws give each effect's `START`, `PEAK` and `END` in ticks after exposure (multiplied by
+//! `DWF_STRETCH` in fortress mode). An effect ramps up from its start to its peak and back down to
+//! its end, unless it has `ABRUPT_START`/`ABRUPT_END` (or `ABRUPT`). Effects without an `END` last
+//! forever, and effects without a `PEAK` are at full severity from their start.
+//!
+//! Effects with `SIZE_DILUTES` or `SIZE_DELAYS` depend on how big the dose is compared to the
+//! creature. The values in the raws are taken to be for a standard dose (a `dose` of 100) on a
+//! creature of [`REFERENCE_BODY_SIZE`]; a bigger creature or a smaller dose dilutes the severity of
+//! `SIZE_DILUTES` effects and stretches out the onset, peak and end of `SIZE_DELAYS` effects by the
+//! same ratio. The game doesn't document its exact formula, so this is an estimate.
+//!
+//! ```rust
+//! use dfraw_parser::{
+//!     syndrome_timeline::{SyndromeTarget, SyndromeTimeline},
+//!     tags::CreatureEffectTag,
+//!     Creature, CreatureEffect,
+//! };
+//!
+//! let mut creature = Creature::empty();
+//! creature.add_caste("FEMALE");
+//! let target = SyndromeTarget::new(&creature, "FEMALE").with_body_size(70_000);
+//!
+//! let effects = vec![CreatureEffect::from_value(
+//!     CreatureEffectTag::Pain,
+//!     "SEV:100:PROB:100:START:10:PEAK:110:END:1000",
+//! )];
+//! let timeline = SyndromeTimeline::from_effects(&effects, &target, 100, false);
+//!
+//! assert_eq!(timeline.get_duration(), Some(1000));
+//! let pain = &timeline.get_effects()[0];
+//! assert_eq!(pain.severity_at(60), 50);
+//! assert_eq!(pain.severity_at(110), 100);
+//! ```
+
+use crate::{
+    tags::{CreatureEffectPropertyTag, CreatureEffectTag},
+    traits::RawObject,
+    Caste, Creature, CreatureEffect, Syndrome,
+};
+
+/// The body size (in cm³) the effects in the raws are taken to be written for.
+pub const REFERENCE_BODY_SIZE: u32 = 70_000;
+/// The size of a standard dose, which has the effects as written in the raws.
+pub const STANDARD_DOSE: u32 = 100;
+
+/// The creature (and caste) a syndrome is being applied to.
+#[derive(Debug, Clone)]
+pub struct SyndromeTarget<'a> {
+    creature: &'a Creature,
+    caste: String,
+    body_size: u32,
+}
+
+impl<'a> SyndromeTarget<'a> {
+    /// Create a target from a creature and one of its castes. The body size is the caste's adult
+    /// size (or the size for all castes, or [`REFERENCE_BODY_SIZE`] if neither has one).
+    ///
+    /// # Arguments
+    ///
+    /// * `creature` - The creature.
+    /// * `caste` - The identifier of the caste (e.g. `FEMALE`).
+    ///
+    /// # Returns
+    ///
+    /// * `SyndromeTarget` - The target.
+    #[must_use]
+    pub fn new(creature: &'a Creature, caste: &str) -> Self {
+        let body_size = [caste, "ALL"]
+            .iter()
+            .filter_map(|identifier| {
+                creature
+                    .get_castes()
+                    .iter()
+                    .find(|c| c.get_identifier() == *identifier)
+            })
+            .find_map(|c| c.get_body_sizes().last())
+            .map_or(REFERENCE_BODY_SIZE, crate::BodySize::get_size_cm3);
+        Self {
+            creature,
+            caste: String::from(caste),
+            body_size,
+        }
+    }
+    /// Set the body size of the target (e.g. for a child, or a creature changed by
+    /// `CHANGE_BODY_SIZE_PERC`).
+    ///
+    /// # Arguments
+    ///
+    /// * `body_size` - The body size in cm³.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The target.
+    #[must_use]
+    pub const fn with_body_size(mut self, body_size: u32) -> Self {
+        self.body_size = body_size;
+        self
+    }
+    /// Get the body size of the target.
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The body size in cm³.
+    #[must_use]
+    pub const fn get_body_size(&self) -> u32 {
+        self.body_size
+    }
+    /// Get the creature classes of the target (from its caste and from the `ALL` caste).
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&str>` - The creature classes.
+    #[must_use]
+    pub fn get_creature_classes(&self) -> Vec<&'a str> {
+        self.creature
+            .get_castes()
+            .iter()
+            .filter(|c| c.get_identifier() == self.caste || c.get_identifier() == "ALL")
+            .flat_map(Caste::get_creature_classes)
+            .map(String::as_str)
+            .collect()
+    }
+    /// Check whether a syndrome affects the target.
+    ///
+    /// A target is immune if it matches a `SYN_IMMUNE_CREATURE` or `SYN_IMMUNE_CLASS`. Otherwise it
+    /// is affected if it matches a `SYN_AFFECTED_CREATURE` or `SYN_AFFECTED_CLASS`, or if the
+    /// syndrome doesn't list any.
+    ///
+    /// # Arguments
+    ///
+    /// * `syndrome` - The syndrome.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the syndrome affects the target.
+    #[must_use]
+    pub fn is_affected_by(&self, syndrome: &Syndrome) -> bool {
+        let classes = self.get_creature_classes();
+        let matches_creature = |(creature, caste): &(String, String)| {
+            creature == self.creature.get_identifier() && (caste == "ALL" || *caste == self.caste)
+        };
+        let matches_class = |class: &String| classes.contains(&class.as_str());
+
+        if syndrome.get_immune_creatures().iter().any(matches_creature)
+            || syndrome.get_immune_classes().iter().any(matches_class)
+        {
+            return false;
+        }
+        if syndrome.get_affected_creatures().is_empty()
+            && syndrome.get_affected_classes().is_empty()
+        {
+            return true;
+        }
+        syndrome
+            .get_affected_creatures()
+            .iter()
+            .any(matches_creature)
+            || syndrome.get_affected_classes().iter().any(matches_class)
+    }
+}
+
+/// When one effect of a syndrome is active on a target, and how severe it is.
+#[derive(serde::Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectTimeline {
+    effect: CreatureEffectTag,
+    severity: u32,
+    probability: u8,
+    start: u32,
+    peak: Option<u32>,
+    end: Option<u32>,
+    abrupt_start: bool,
+    abrupt_end: bool,
+}
+
+impl EffectTimeline {
+    fn new(effect: &CreatureEffect, body_size: u32, dose: u32, fortress_mode: bool) -> Self {
+        // How much bigger the creature is compared to the dose, relative to the standard
+        let dilution = |value: u32| {
+            u64::from(value) * u64::from(dose) * u64::from(REFERENCE_BODY_SIZE)
+                / (u64::from(STANDARD_DOSE) * u64::from(body_size.max(1)))
+        };
+        let delay = |value: u32| {
+            u64::from(value) * u64::from(STANDARD_DOSE) * u64::from(body_size)
+                / (u64::from(dose.max(1)) * u64::from(REFERENCE_BODY_SIZE))
+        };
+        let stretch = u64::from(
+            effect
+                .get_dwf_stretch()
+                .filter(|_| fortress_mode)
+                .unwrap_or(1),
+        );
+        let size_delays = effect.has_tag(&CreatureEffectPropertyTag::SizeDelays);
+        let ticks = |value: u32| {
+            let value = if size_delays {
+                delay(value)
+            } else {
+                u64::from(value)
+            };
+            u32::try_from(value * stretch).unwrap_or(u32::MAX)
+        };
+
+        let (start, peak, end) = effect.get_timing();
+        let severity = if effect.has_tag(&CreatureEffectPropertyTag::SizeDilutes) {
+            u32::try_from(dilution(effect.get_severity())).unwrap_or(u32::MAX)
+        } else {
+            effect.get_severity()
+        };
+        let abrupt = effect.has_tag(&CreatureEffectPropertyTag::Abrupt);
+
+        Self {
+            effect: effect.get_effect().clone(),
+            severity,
+            probability: effect.get_probability(),
+            start: ticks(start),
+            peak: (peak > start).then(|| ticks(peak)),
+            end: (end > 0).then(|| ticks(end)),
+            abrupt_start: abrupt || effect.has_tag(&CreatureEffectPropertyTag::AbruptStart),
+            abrupt_end: abrupt || effect.has_tag(&CreatureEffectPropertyTag::AbruptEnd),
+        }
+    }
+    /// Get the effect.
+    ///
+    /// # Returns
+    ///
+    /// * `&CreatureEffectTag` - The effect.
+    #[must_use]
+    pub const fn get_effect(&self) -> &CreatureEffectTag {
+        &self.effect
+    }
+    /// Get the severity of the effect at its peak.
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The peak severity.
+    #[must_use]
+    pub const fn get_severity(&self) -> u32 {
+        self.severity
+    }
+    /// Get the chance of the effect happening at all, as a percentage.
+    ///
+    /// # Returns
+    ///
+    /// * `u8` - The probability.
+    #[must_use]
+    pub const fn get_probability(&self) -> u8 {
+        self.probability
+    }
+    /// Get the tick the effect starts at.
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The start tick.
+    #[must_use]
+    pub const fn get_start(&self) -> u32 {
+        self.start
+    }
+    /// Get the tick the effect is at its full severity.
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The peak tick (the start tick if the effect has no peak).
+    #[must_use]
+    pub fn get_peak(&self) -> u32 {
+        self.peak.unwrap_or(self.start)
+    }
+    /// Get the tick the effect ends at.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The end tick, or `None` if the effect is permanent.
+    #[must_use]
+    pub const fn get_end(&self) -> Option<u32> {
+        self.end
+    }
+    /// Get how long the effect is active for.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The number of ticks, or `None` if the effect is permanent.
+    #[must_use]
+    pub fn get_duration(&self) -> Option<u32> {
+        self.end.map(|end| end.saturating_sub(self.start))
+    }
+    /// Check whether the effect is active at a tick.
+    ///
+    /// # Arguments
+    ///
+    /// * `tick` - The number of ticks since exposure.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the effect is active.
+    #[must_use]
+    pub fn is_active_at(&self, tick: u32) -> bool {
+        tick >= self.start && self.end.is_none_or(|end| tick < end)
+    }
+    /// Get the severity of the effect at a tick.
+    ///
+    /// # Arguments
+    ///
+    /// * `tick` - The number of ticks since exposure.
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The severity (0 if the effect isn't active).
+    #[must_use]
+    pub fn severity_at(&self, tick: u32) -> u32 {
+        if !self.is_active_at(tick) {
+            return 0;
+        }
+        let peak = self.get_peak();
+        let scale = |elapsed: u32, length: u32| {
+            let severity = u64::from(self.severity) * u64::from(elapsed) / u64::from(length.max(1));
+            u32::try_from(severity).unwrap_or(u32::MAX)
+        };
+        if tick < peak && !self.abrupt_start {
+            return scale(tick - self.start, peak - self.start);
+        }
+        match self.end {
+            Some(end) if tick > peak && !self.abrupt_end => scale(end - tick, end - peak),
+            _ => self.severity,
+        }
+    }
+}
+
+/// The timeline of a syndrome's effects on a target.
+#[derive(serde::Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SyndromeTimeline {
+    affected: bool,
+    effects: Vec<EffectTimeline>,
+}
+
+impl SyndromeTimeline {
+    /// Work out the timeline of a syndrome on a target.
+    ///
+    /// # Arguments
+    ///
+    /// * `syndrome` - The syndrome.
+    /// * `target` - The creature the syndrome is applied to.
+    /// * `dose` - The size of the dose, where [`STANDARD_DOSE`] has the effects as written.
+    /// * `fortress_mode` - Whether to apply `DWF_STRETCH` to the timings.
+    ///
+    /// # Returns
+    ///
+    /// * `SyndromeTimeline` - The timeline (with no effects if the target is immune).
+    #[must_use]
+    pub fn simulate(
+        syndrome: &Syndrome,
+        target: &SyndromeTarget,
+        dose: u32,
+        fortress_mode: bool,
+    ) -> Self {
+        if !target.is_affected_by(syndrome) {
+            return Self::default();
+        }
+        Self::from_effects(syndrome.get_effects(), target, dose, fortress_mode)
+    }
+    /// Work out the timeline of some effects on a target (without checking whether the target is
+    /// immune).
+    ///
+    /// # Arguments
+    ///
+    /// * `effects` - The creature effects.
+    /// * `target` - The creature the effects are applied to.
+    /// * `dose` - The size of the dose, where [`STANDARD_DOSE`] has the effects as written.
+    /// * `fortress_mode` - Whether to apply `DWF_STRETCH` to the timings.
+    ///
+    /// # Returns
+    ///
+    /// * `SyndromeTimeline` - The timeline.
+    #[must_use]
+    pub fn from_effects(
+        effects: &[CreatureEffect],
+        target: &SyndromeTarget,
+        dose: u32,
+        fortress_mode: bool,
+    ) -> Self {
+        Self {
+            affected: dose > 0,
+            effects: if dose > 0 {
+                effects
+                    .iter()
+                    .map(|effect| {
+                        EffectTimeline::new(effect, target.body_size, dose, fortress_mode)
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            },
+        }
+    }
+    /// Check whether the target is affected at all.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the target is affected.
+    #[must_use]
+    pub const fn is_affected(&self) -> bool {
+        self.affected
+    }
+    /// Get the timelines of each effect, in the order the effects are listed in the syndrome.
+    ///
+    /// # Returns
+    ///
+    /// * `&[EffectTimeline]` - The effect timelines.
+    #[must_use]
+    pub fn get_effects(&self) -> &[EffectTimeline] {
+        &self.effects
+    }
+    /// Get how long the syndrome lasts, until its last effect ends.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The tick the last effect ends at, or `None` if an effect is permanent.
+    #[must_use]
+    pub fn get_duration(&self) -> Option<u32> {
+        self.effects
+            .iter()
+            .try_fold(0, |duration, effect| Some(duration.max(effect.get_end()?)))
+    }
+    /// Get the effects which are active at a tick, with their severity.
+    ///
+    /// # Arguments
+    ///
+    /// * `tick` - The number of ticks since exposure.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(&EffectTimeline, u32)>` - The active effects and their severities.
+    #[must_use]
+    pub fn active_at(&self, tick: u32) -> Vec<(&EffectTimeline, u32)> {
+        self.effects
+            .iter()
+            .filter(|effect| effect.is_active_at(tick))
+            .map(|effect| (effect, effect.severity_at(tick)))
+            .collect()
+    }
+    /// Sample the severity of every effect from exposure until the syndrome ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - The number of ticks between samples.
+    /// * `limit` - The last tick to sample, for syndromes with permanent effects.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(u32, Vec<u32>)>` - For each sampled tick, the severity of each effect (in the same
+    ///   order as `get_effects`).
+    #[must_use]
+    pub fn sample(&self, step: u32, limit: u32) -> Vec<(u32, Vec<u32>)> {
+        let last = self
+            .get_duration()
+            .map_or(limit, |duration| duration.min(limit));
+        (0..=last)
+            .step_by(usize::try_from(step.max(1)).unwrap_or(usize::MAX))
+            .map(|tick| {
+                (
+                    tick,
+                    self.effects
+                        .iter()
+                        .map(|effect| effect.severity_at(tick))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,12 @@
+use super::Span;
+
+/// A run of text outside of any token. Dwarf Fortress ignores this text, so it is a comment.
+///
+/// Comments never include leading or trailing whitespace and never span multiple lines.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Comment<'a> {
+    /// The text of the comment.
+    pub text: &'a str,
+    /// Where the comment is in the source.
+    pub span: Span,
+}
@@ -0,0 +1,23 @@
+use super::Span;
+
+/// Why a bracketed piece of text could not be read as a token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum MalformedReason {
+    /// The `[` was never closed before the end of the line, another `[`, or the end of the text.
+    UnclosedBracket,
+    /// The token has no key, e.g. `[]` or `[:VALUE]`.
+    EmptyKey,
+}
+
+/// Text that starts like a token but could not be read as one.
+///
+/// The parser skips over these, but tools can use them to report errors to the user.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct MalformedToken<'a> {
+    /// The text of the malformed token, including the opening bracket.
+    pub text: &'a str,
+    /// Why the text is malformed.
+    pub reason: MalformedReason,
+    /// Where the malformed token is in the source.
+    pub span: Span,
+}
@@ -0,0 +1,43 @@
+//! A lossless tokenizer for Dwarf Fortress raw text.
+//!
+//! The parser itself only cares about the `[KEY:VALUE]` tokens in a raw file, so it throws away
+//! everything else. Tools like formatters, syntax highlighters and language servers need the rest
+//! of the text too. The tokenizer here yields every piece of the source in order, so that joining
+//! the text of every node gives back the original input.
+//!
+//! Anything outside of brackets is treated as a comment by Dwarf Fortress, and that includes the
+//! raw file identifier on the first line of each file.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use dfraw_parser::tokenizer::{tokenize, Node};
+//!
+//! let text = "creature_example\n[CREATURE:EXAMPLE] comment\n[NAME:ex:exes:exy]";
+//! let tokens: Vec<_> = tokenize(text)
+//!     .filter_map(|node| match node {
+//!         Node::Token(token) => Some(token),
+//!         _ => None,
+//!     })
+//!     .collect();
+//!
+//! assert_eq!(tokens.len(), 2);
+//! assert_eq!(tokens[1].key, "NAME");
+//! assert_eq!(tokens[1].args, vec!["ex", "exes", "exy"]);
+//! ```
+
+mod comment;
+mod malformed;
+mod node;
+mod span;
+mod token;
+mod tokenize;
+mod whitespace;
+
+pub use comment::Comment;
+pub use malformed::{MalformedReason, MalformedToken};
+pub use node::Node;
+pub use span::Span;
+pub use token::Token;
+pub use tokenize::{tokenize, Tokenizer};
+pub use whitespace::Whitespace;
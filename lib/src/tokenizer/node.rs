@@ -0,0 +1,40 @@
+use super::{Comment, MalformedToken, Span, Token, Whitespace};
+
+/// A single piece of tokenized raw text.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub enum Node<'a> {
+    /// A complete `[KEY:ARGS]` token.
+    Token(Token<'a>),
+    /// Text outside of tokens.
+    Comment(Comment<'a>),
+    /// Whitespace outside of tokens.
+    Whitespace(Whitespace<'a>),
+    /// Something that looked like a token but is not valid.
+    Malformed(MalformedToken<'a>),
+}
+
+impl<'a> Node<'a> {
+    /// Get the span of the node.
+    #[must_use]
+    pub const fn span(&self) -> Span {
+        match self {
+            Self::Token(token) => token.span,
+            Self::Comment(comment) => comment.span,
+            Self::Whitespace(whitespace) => whitespace.span,
+            Self::Malformed(malformed) => malformed.span,
+        }
+    }
+    /// Get the node as a token, if it is one.
+    #[must_use]
+    pub const fn as_token(&self) -> Option<&Token<'a>> {
+        match self {
+            Self::Token(token) => Some(token),
+            _ => None,
+        }
+    }
+    /// Returns true if the node is a comment or whitespace, which Dwarf Fortress ignores.
+    #[must_use]
+    pub const fn is_trivia(&self) -> bool {
+        matches!(self, Self::Comment(_) | Self::Whitespace(_))
+    }
+}
@@ -0,0 +1,40 @@
+/// The location of a node within the tokenized text.
+///
+/// Offsets are byte offsets into the source string, so `&source[span.start..span.end]` is the
+/// text of the node. The line and column are zero-based and point at the start of the node. The
+/// column is counted in characters.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct Span {
+    /// The byte offset where the node starts.
+    pub start: usize,
+    /// The byte offset just after the node ends.
+    pub end: usize,
+    /// The line the node starts on (zero-based).
+    pub line: usize,
+    /// The column the node starts at (zero-based, in characters).
+    pub column: usize,
+}
+
+impl Span {
+    /// The length of the span in bytes.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.end - self.start
+    }
+    /// Returns true if the span covers no text.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.end == self.start
+    }
+    /// Returns true if the given byte offset is inside the span.
+    ///
+    /// Arguments:
+    ///
+    /// * `offset`: The byte offset to check.
+    #[must_use]
+    pub const fn contains(&self, offset: usize) -> bool {
+        offset >= self.start && offset < self.end
+    }
+}
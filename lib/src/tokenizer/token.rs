@@ -0,0 +1,82 @@
+use super::Span;
+
+/// A complete `[KEY:ARG:ARG]` token.
+///
+/// The `key` is the text before the first colon, and the `args` are the colon separated values
+/// after it. A token without a colon (e.g. `[FLIER]`) has no arguments, while a token with a
+/// trailing colon (e.g. `[NAME:]`) has a single empty argument.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Token<'a> {
+    /// The token key, e.g. `CREATURE` in `[CREATURE:DWARF]`.
+    pub key: &'a str,
+    /// The arguments of the token, split on `:`.
+    pub args: Vec<&'a str>,
+    /// Everything after the first colon, unsplit. This is the `value` the parser passes along to
+    /// `RawObject::parse_tag`.
+    pub value: &'a str,
+    /// Where the token is in the source, including the brackets.
+    pub span: Span,
+}
+
+impl<'a> Token<'a> {
+    /// Create a token from the text between the brackets.
+    ///
+    /// Arguments:
+    ///
+    /// * `inner`: The text between `[` and `]`.
+    /// * `span`: The span of the whole token, including the brackets.
+    ///
+    /// Returns:
+    ///
+    /// The token, or `None` if the key is empty.
+    #[must_use]
+    pub fn from_inner(inner: &'a str, span: Span) -> Option<Self> {
+        let (key, value, args) = match inner.split_once(':') {
+            Some((key, value)) => (key, value, value.split(':').collect()),
+            None => (inner, "", Vec::new()),
+        };
+        if key.is_empty() {
+            return None;
+        }
+        Some(Self {
+            key,
+            args,
+            value,
+            span,
+        })
+    }
+    /// Get the span of just the key (without the opening bracket).
+    #[must_use]
+    pub const fn key_span(&self) -> Span {
+        Span {
+            start: self.span.start + 1,
+            end: self.span.start + 1 + self.key.len(),
+            line: self.span.line,
+            column: self.span.column + 1,
+        }
+    }
+    /// Get the span of the argument at the given index, if there is one.
+    ///
+    /// Arguments:
+    ///
+    /// * `index`: The index of the argument in `args`.
+    #[must_use]
+    pub fn arg_span(&self, index: usize) -> Option<Span> {
+        if index >= self.args.len() {
+            return None;
+        }
+        // Skip the bracket, the key and its colon, then each earlier argument and its colon.
+        let mut start = self.span.start + 1 + self.key.len() + 1;
+        let mut column = self.span.column + 1 + self.key.chars().count() + 1;
+        for arg in self.args.iter().take(index) {
+            start += arg.len() + 1;
+            column += arg.chars().count() + 1;
+        }
+        Some(Span {
+            start,
+            end: start + self.args[index].len(),
+            line: self.span.line,
+            column,
+        })
+    }
+}
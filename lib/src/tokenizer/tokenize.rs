@@ -0,0 +1,122 @@
+use super::{Comment, MalformedReason, MalformedToken, Node, Span, Token, Whitespace};
+
+/// An iterator over the nodes in a piece of raw text. Created by `tokenize`.
+#[derive(Debug, Clone)]
+pub struct Tokenizer<'a> {
+    source: &'a str,
+    position: usize,
+    line: usize,
+    column: usize,
+}
+
+/// Tokenize raw text into an iterator of nodes.
+///
+/// Every byte of the source ends up in exactly one node, in order. Tokens cannot span lines, so an
+/// unclosed `[` ends at the end of its line (or at the next `[`) and is yielded as a
+/// `Node::Malformed`.
+///
+/// Arguments:
+///
+/// * `source`: The raw text to tokenize. This should already be decoded (raw files are Latin1).
+///
+/// Returns:
+///
+/// A `Tokenizer` which yields `Node`s.
+#[must_use]
+pub const fn tokenize(source: &str) -> Tokenizer<'_> {
+    Tokenizer {
+        source,
+        position: 0,
+        line: 0,
+        column: 0,
+    }
+}
+
+impl<'a> Tokenizer<'a> {
+    /// Build a span from the current position to `end`, and move the position to `end`.
+    fn advance_to(&mut self, end: usize) -> Span {
+        let span = Span {
+            start: self.position,
+            end,
+            line: self.line,
+            column: self.column,
+        };
+        for c in self.source[self.position..end].chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 0;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.position = end;
+        span
+    }
+    /// Read a token starting at the current position, which must be a `[`.
+    fn read_token(&mut self) -> Node<'a> {
+        let source = self.source;
+        let rest = &source[self.position + 1..];
+        match rest.find(['[', ']', '\n', '\r']) {
+            Some(offset) if rest[offset..].starts_with(']') => {
+                let inner = &rest[..offset];
+                let span = self.advance_to(self.position + offset + 2);
+                Token::from_inner(inner, span).map_or_else(
+                    || {
+                        Node::Malformed(MalformedToken {
+                            text: &source[span.start..span.end],
+                            reason: MalformedReason::EmptyKey,
+                            span,
+                        })
+                    },
+                    Node::Token,
+                )
+            }
+            found => {
+                let end = self.position + 1 + found.unwrap_or(rest.len());
+                let span = self.advance_to(end);
+                Node::Malformed(MalformedToken {
+                    text: &source[span.start..span.end],
+                    reason: MalformedReason::UnclosedBracket,
+                    span,
+                })
+            }
+        }
+    }
+    /// Read whitespace starting at the current position.
+    fn read_whitespace(&mut self) -> Node<'a> {
+        let rest = &self.source[self.position..];
+        let length = rest
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(rest.len());
+        let span = self.advance_to(self.position + length);
+        Node::Whitespace(Whitespace {
+            text: &self.source[span.start..span.end],
+            span,
+        })
+    }
+    /// Read a comment starting at the current position. The comment stops at the next token or
+    /// line break, and does not include any trailing whitespace.
+    fn read_comment(&mut self) -> Node<'a> {
+        let rest = &self.source[self.position..];
+        let length = rest.find(['[', '\n', '\r']).unwrap_or(rest.len());
+        let length = rest[..length].trim_end().len();
+        let span = self.advance_to(self.position + length);
+        Node::Comment(Comment {
+            text: &self.source[span.start..span.end],
+            span,
+        })
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Node<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next_char = self.source[self.position..].chars().next()?;
+        Some(match next_char {
+            '[' => self.read_token(),
+            c if c.is_whitespace() => self.read_whitespace(),
+            _ => self.read_comment(),
+        })
+    }
+}
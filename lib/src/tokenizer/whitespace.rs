@@ -0,0 +1,10 @@
+use super::Span;
+
+/// A run of whitespace (including newlines) outside of any token.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Whitespace<'a> {
+    /// The whitespace text.
+    pub text: &'a str,
+    /// Where the whitespace is in the source.
+    pub span: Span,
+}
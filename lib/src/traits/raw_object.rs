@@ -40,6 +40,15 @@ pub trait RawObject: RawObjectToAny + Send + Sync + Searchable {
     ///
     /// This also will remove the metadata if is_metadata_hidden is true.
     fn clean_self(&mut self);
+    /// Set whether the metadata of the raw (and of any raws it contains) is hidden.
+    ///
+    /// The `hidden` flag isn't serialized, so this is used to restore it when raws are read back in
+    /// (e.g. from the parse cache).
+    ///
+    /// Arguments:
+    ///
+    /// * `hidden`: Whether the metadata is hidden.
+    fn set_metadata_hidden(&mut self, hidden: bool);
 }
 
 /// The `RawObjectToAny` trait is implemented by all raw objects. This trait is
@@ -258,6 +258,7 @@ pub fn validate_options(options: &ParserOptions) -> Result<ParserOptions, Parser
         object_types_to_parse: options.object_types_to_parse.clone(),
         skip_apply_copy_tags_from: options.skip_apply_copy_tags_from,
         skip_apply_creature_variations: options.skip_apply_creature_variations,
+        cache_directory: options.cache_directory.clone(),
         ..Default::default()
     };
 
@@ -503,7 +504,8 @@ pub fn try_get_file<P: AsRef<Path>>(file_path: &P) -> Result<File, ParserError>
 
 /// Create a summary of the parsed raws.
 ///
-/// Summarizes the parsed raws by object type.
+/// Summarizes the parsed raws by object type. To also count them by module and location (or to
+/// count only some of them), use `raw_collection::RawCollection::facets`.
 ///
 /// Arguments:
 ///
@@ -1243,6 +1245,9 @@ pub fn singularly_apply_creature_variation(
 /// The function `build_search_string` takes a `raw_object` that implements the `Searchable` trait and
 /// returns a string representation of the object for searching purposes.
 ///
+/// For ranked searches (with typo tolerance and prefix matching) over many raws, build a
+/// `search_index::SearchIndex` instead.
+///
 /// Arguments:
 ///
 /// * `raw_object`: The `raw_object` parameter is a reference to an object that implements the
@@ -0,0 +1,41 @@
+//! Check raw text for the problems the parser would run into, with where they are in the text.
+//!
+//! The parser reads whatever it can and logs the rest, which is right for loading a whole game's
+//! raws but doesn't help someone editing one file. `validate_raw_text` reads the text the same way
+//! the parser does and reports:
+//!
+//! * malformed tokens (which the parser skips),
+//! * a missing, unknown or mismatched `[OBJECT]` token (which stop the parser reading the file),
+//! * tokens the parser doesn't know, or only knows for another type of raw (which it ignores),
+//! * and anything the parser warns about while parsing a token (like an unknown biome).
+//!
+//! Each token is parsed by the same raw objects (`Creature`, `Plant`, ...) the parser uses, so the
+//! problems are the parser's own rather than a separate set of rules.
+//!
+//! ```rust
+//! use dfraw_parser::validation::{validate_raw_text, RawProblemKind};
+//!
+//! let text = "creature_example\n[OBJECT:CREATURE]\n[CREATURE:EXAMPLE]\n[BIOME:NOT_A_BIOME]\n\
+//!     [TREE]\n[NOT_A_TOKEN]\n[FLIER";
+//! let kinds: Vec<RawProblemKind> = validate_raw_text(text)
+//!     .iter()
+//!     .map(|problem| problem.kind)
+//!     .collect();
+//!
+//! assert_eq!(
+//!     kinds,
+//!     vec![
+//!         RawProblemKind::ParseWarning,
+//!         RawProblemKind::WrongObjectType,
+//!         RawProblemKind::UnknownToken,
+//!         RawProblemKind::Malformed,
+//!     ]
+//! );
+//! ```
+
+mod problem;
+mod validate;
+mod warnings;
+
+pub use problem::{RawProblem, RawProblemKind};
+pub use validate::validate_raw_text;
@@ -0,0 +1,74 @@
+//! The problems found by `validate_raw_text`.
+
+use crate::tokenizer::Span;
+
+/// The kinds of problems in raw text.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RawProblemKind {
+    /// Text that starts like a token but isn't one (the parser skips it)
+    Malformed,
+    /// There is no `[OBJECT]` token (the parser can't read the file)
+    MissingObject,
+    /// The `[OBJECT]` token names an object type the parser doesn't know
+    UnknownObjectType,
+    /// The `[OBJECT]` token doesn't match the first `[OBJECT]` token of the file
+    ObjectTypeMismatch,
+    /// A token the parser doesn't know (it is ignored)
+    UnknownToken,
+    /// A token the parser only knows for other types of raws (it is ignored)
+    WrongObjectType,
+    /// Something the parser warned about while parsing a token
+    ParseWarning,
+}
+
+impl RawProblemKind {
+    /// Whether the problem is an error (the text can't be read as it was meant to be), rather than a
+    /// warning (the text can be read, but some of it is ignored or doesn't make sense).
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the problem is an error.
+    #[must_use]
+    pub const fn is_error(self) -> bool {
+        matches!(
+            self,
+            Self::Malformed
+                | Self::MissingObject
+                | Self::UnknownObjectType
+                | Self::ObjectTypeMismatch
+        )
+    }
+}
+
+/// A problem in raw text, and where it is.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RawProblem {
+    /// What kind of problem it is
+    pub kind: RawProblemKind,
+    /// Where the problem is (the start of the text, for a missing `[OBJECT]` token)
+    pub span: Span,
+    /// A description of the problem
+    pub message: String,
+}
+
+impl RawProblem {
+    /// Create a problem.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of problem.
+    /// * `span` - Where the problem is.
+    /// * `message` - A description of the problem.
+    ///
+    /// # Returns
+    ///
+    /// * `RawProblem` - The problem.
+    #[must_use]
+    pub fn new(kind: RawProblemKind, span: Span, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            span,
+            message: message.into(),
+        }
+    }
+}
@@ -0,0 +1,372 @@
+//! Reading raw text the way the parser does, keeping track of the problems found.
+
+use crate::{
+    creature::Creature,
+    creature_variation::CreatureVariation,
+    entity::Entity,
+    graphic::Graphic,
+    inorganic::Inorganic,
+    material_template::MaterialTemplate,
+    metadata::{ObjectType, RawMetadata, OBJECT_TOKEN_MAP},
+    plant::Plant,
+    raw_definitions::{
+        CASTE_TOKENS, CREATURE_EFFECT_TOKENS, CREATURE_TOKENS, CREATURE_VARIATION_TOKENS,
+        ENTITY_TOKENS, GRAPHIC_TYPE_TOKENS, INORGANIC_TOKENS, MATERIAL_PROPERTY_TOKENS,
+        MATERIAL_USAGE_TOKENS, PLANT_GROWTH_TOKENS, PLANT_TOKENS, POSITION_TOKENS, SHRUB_TOKENS,
+        SYNDROME_TOKENS, TILE_PAGE_TOKENS, TREE_TOKENS,
+    },
+    reader::PARSABLE_OBJECT_TYPES,
+    tags::GraphicTypeTag,
+    tile_page::TilePage,
+    tokenizer::{tokenize, MalformedReason, Node, Span, Token},
+    traits::RawObject,
+    ParserError,
+};
+
+use super::{
+    problem::{RawProblem, RawProblemKind},
+    warnings::WarningCollector,
+};
+
+/// The object types which can be checked for tokens of another object type, in the order they are
+/// named in problems.
+const TOKEN_OWNERS: [ObjectType; 7] = [
+    ObjectType::Creature,
+    ObjectType::CreatureVariation,
+    ObjectType::Entity,
+    ObjectType::Inorganic,
+    ObjectType::MaterialTemplate,
+    ObjectType::Plant,
+    ObjectType::TilePage,
+];
+
+/// Check raw text for the problems the parser would run into.
+///
+/// The text is read like the parser reads a raw file: the `[OBJECT]` token decides what the file
+/// holds, the object tokens (like `[CREATURE:...]`) start a new raw, and every other token is
+/// parsed by the raw it belongs to. Anything the parser would skip, ignore or warn about is
+/// reported, along with where it is in the text.
+///
+/// The tokens of object types the parser doesn't parse (like reactions) aren't checked.
+///
+/// # Arguments
+///
+/// * `text` - The raw text to check.
+///
+/// # Returns
+///
+/// * `Vec<RawProblem>` - The problems found, in the order they appear in the text.
+#[must_use]
+pub fn validate_raw_text(text: &str) -> Vec<RawProblem> {
+    let warnings = WarningCollector::default();
+    let dispatch = tracing::Dispatch::new(warnings.clone());
+
+    tracing::dispatcher::with_default(&dispatch, || {
+        let mut validator = Validator::new(warnings);
+        for node in tokenize(text) {
+            match node {
+                Node::Malformed(malformed) => {
+                    let message = match malformed.reason {
+                        MalformedReason::UnclosedBracket => {
+                            format!("Unclosed token '{}' is ignored", malformed.text)
+                        }
+                        MalformedReason::EmptyKey => {
+                            format!("Token '{}' has no key and is ignored", malformed.text)
+                        }
+                    };
+                    validator.report(RawProblemKind::Malformed, malformed.span, message);
+                }
+                Node::Token(token) => validator.check_token(&token),
+                Node::Comment(_) | Node::Whitespace(_) => {}
+            }
+        }
+        validator.finish()
+    })
+}
+
+/// The raw which tokens are currently being added to.
+enum CurrentRaw {
+    None,
+    Creature(Box<Creature>),
+    CreatureVariation(Box<CreatureVariation>),
+    Entity(Box<Entity>),
+    /// The graphic, and the graphic type its sprites are parsed as
+    Graphic(Box<Graphic>, GraphicTypeTag),
+    Inorganic(Box<Inorganic>),
+    MaterialTemplate(Box<MaterialTemplate>),
+    Plant(Box<Plant>),
+    TilePage(Box<TilePage>),
+}
+
+impl CurrentRaw {
+    /// The object type of the raw, if there is one.
+    const fn object_type(&self) -> Option<ObjectType> {
+        match self {
+            Self::None => None,
+            Self::Creature(_) => Some(ObjectType::Creature),
+            Self::CreatureVariation(_) => Some(ObjectType::CreatureVariation),
+            Self::Entity(_) => Some(ObjectType::Entity),
+            Self::Graphic(..) => Some(ObjectType::Graphics),
+            Self::Inorganic(_) => Some(ObjectType::Inorganic),
+            Self::MaterialTemplate(_) => Some(ObjectType::MaterialTemplate),
+            Self::Plant(_) => Some(ObjectType::Plant),
+            Self::TilePage(_) => Some(ObjectType::TilePage),
+        }
+    }
+    /// Have the raw parse a token, the way the parser would.
+    fn parse_tag(&mut self, key: &str, value: &str) {
+        match self {
+            Self::None => {}
+            Self::Creature(creature) => match key {
+                "CASTE" | "SELECT_CASTE" => creature.select_caste(value),
+                _ => creature.parse_tag(key, value),
+            },
+            Self::CreatureVariation(creature_variation) => creature_variation.parse_tag(key, value),
+            Self::Entity(entity) => entity.parse_tag(key, value),
+            Self::Graphic(graphic, graphic_type) => {
+                if graphic.get_graphic_type() == GraphicTypeTag::Tile {
+                    // Every line of tile graphics names its own graphic type
+                    *graphic_type = *GRAPHIC_TYPE_TOKENS
+                        .get(key)
+                        .unwrap_or(&GraphicTypeTag::Unknown);
+                }
+                graphic.parse_sprite_from_tag(key, value, *graphic_type);
+            }
+            Self::Inorganic(inorganic) => inorganic.parse_tag(key, value),
+            Self::MaterialTemplate(material_template) => material_template.parse_tag(key, value),
+            Self::Plant(plant) => plant.parse_tag(key, value),
+            Self::TilePage(tile_page) => tile_page.parse_tag(key, value),
+        }
+    }
+}
+
+/// Whether the parser knows a token for raws of the given object type.
+fn is_known_token(object_type: &ObjectType, key: &str) -> bool {
+    match object_type {
+        ObjectType::Creature => {
+            CREATURE_TOKENS.contains_key(key)
+                || CASTE_TOKENS.contains_key(key)
+                || matches!(key, "CASTE" | "SELECT_CASTE")
+        }
+        ObjectType::CreatureVariation => CREATURE_VARIATION_TOKENS.contains_key(key),
+        ObjectType::Entity => {
+            ENTITY_TOKENS.contains_key(key)
+                || POSITION_TOKENS.contains_key(key)
+                || matches!(key, "SELECT_CREATURE" | "CASTE" | "SELECT_CASTE")
+        }
+        ObjectType::Inorganic => INORGANIC_TOKENS.contains_key(key) || is_material_token(key),
+        ObjectType::MaterialTemplate => is_material_token(key),
+        ObjectType::Plant => {
+            PLANT_TOKENS.contains_key(key)
+                || TREE_TOKENS.contains_key(key)
+                || PLANT_GROWTH_TOKENS.contains_key(key)
+                || SHRUB_TOKENS.contains_key(key)
+                || MATERIAL_PROPERTY_TOKENS.contains_key(key)
+                || MATERIAL_USAGE_TOKENS.contains_key(key)
+        }
+        ObjectType::TilePage => TILE_PAGE_TOKENS.contains_key(key),
+        // Graphics take any token as a sprite (and warn if it doesn't parse as one)
+        _ => true,
+    }
+}
+
+/// Whether the parser knows a token for materials.
+fn is_material_token(key: &str) -> bool {
+    MATERIAL_PROPERTY_TOKENS.contains_key(key)
+        || MATERIAL_USAGE_TOKENS.contains_key(key)
+        || SYNDROME_TOKENS.contains_key(key)
+        || CREATURE_EFFECT_TOKENS.contains_key(key)
+        || key == "CE"
+}
+
+/// Reads the tokens of raw text, keeping track of the problems found.
+struct Validator {
+    warnings: WarningCollector,
+    metadata: RawMetadata,
+    problems: Vec<RawProblem>,
+    /// The object type from the first `[OBJECT]` token
+    object_type: Option<ObjectType>,
+    current: CurrentRaw,
+}
+
+impl Validator {
+    fn new(warnings: WarningCollector) -> Self {
+        Self {
+            warnings,
+            metadata: RawMetadata::default(),
+            problems: Vec::new(),
+            object_type: None,
+            current: CurrentRaw::None,
+        }
+    }
+
+    fn report(&mut self, kind: RawProblemKind, span: Span, message: impl Into<String>) {
+        self.problems.push(RawProblem::new(kind, span, message));
+    }
+
+    /// Finish reading the text, returning the problems found.
+    fn finish(mut self) -> Vec<RawProblem> {
+        if self.object_type.is_none() {
+            self.report(
+                RawProblemKind::MissingObject,
+                Span::default(),
+                ParserError::InvalidRawFile("No [OBJECT] tag found".to_string()).to_string(),
+            );
+        }
+        self.problems
+    }
+
+    fn check_token(&mut self, token: &Token) {
+        if token.key == "OBJECT" {
+            self.check_object_token(token);
+            return;
+        }
+        // Like the parser, only read the tokens of object types it can parse
+        let Some(object_type) = self.object_type.clone() else {
+            return;
+        };
+        if !PARSABLE_OBJECT_TYPES.contains(&&object_type) {
+            return;
+        }
+
+        if self.start_raw(token) {
+            return;
+        }
+        match token.key {
+            // Modifications are applied to creatures after they are all read
+            "GO_TO_END"
+            | "GO_TO_START"
+            | "GO_TO_TAG"
+            | "COPY_TAGS_FROM"
+            | "APPLY_CREATURE_VARIATION" => return,
+            // Castes are only read in creatures, entities and graphics
+            "CASTE" | "SELECT_CASTE"
+                if !matches!(
+                    object_type,
+                    ObjectType::Creature | ObjectType::Entity | ObjectType::Graphics
+                ) =>
+            {
+                return
+            }
+            _ => {}
+        }
+        // Tokens before the first raw are ignored by the parser
+        let Some(raw_type) = self.current.object_type() else {
+            return;
+        };
+
+        if !is_known_token(&raw_type, token.key) {
+            self.report_unknown_token(token, &raw_type);
+            return;
+        }
+
+        self.current.parse_tag(token.key, token.value);
+        for message in self.warnings.take() {
+            self.report(RawProblemKind::ParseWarning, token.span, message);
+        }
+    }
+
+    fn check_object_token(&mut self, token: &Token) {
+        let span = token.arg_span(0).unwrap_or(token.span);
+        let Some(declared) = OBJECT_TOKEN_MAP.get(token.value) else {
+            self.report(
+                RawProblemKind::UnknownObjectType,
+                span,
+                ParserError::InvalidRawFile(format!(
+                    "Unknown object type: {}",
+                    token.value.to_uppercase()
+                ))
+                .to_string(),
+            );
+            return;
+        };
+        match &self.object_type {
+            Some(existing) if existing != declared => {
+                let message = ParserError::InvalidRawFile(format!(
+                    "Object type mismatch: {existing} != {}",
+                    token.value.to_uppercase()
+                ))
+                .to_string();
+                self.report(RawProblemKind::ObjectTypeMismatch, span, message);
+            }
+            Some(_) => {}
+            None => self.object_type = Some(declared.clone()),
+        }
+    }
+
+    /// Start a new raw if the token is an object token (like `[CREATURE:...]`).
+    ///
+    /// Returns true if the token was an object token.
+    fn start_raw(&mut self, token: &Token) -> bool {
+        let (key, value) = (token.key, token.value);
+        self.current = match key {
+            // Entities have their own creature and caste tokens
+            "CREATURE" | "SELECT_CREATURE" | "CASTE" | "SELECT_CASTE"
+                if matches!(self.current, CurrentRaw::Entity(_)) =>
+            {
+                return false
+            }
+            "CREATURE" | "SELECT_CREATURE" => {
+                CurrentRaw::Creature(Box::new(Creature::new(value, &self.metadata)))
+            }
+            "CREATURE_VARIATION" => CurrentRaw::CreatureVariation(Box::new(
+                CreatureVariation::new(value, &self.metadata),
+            )),
+            "PLANT" => CurrentRaw::Plant(Box::new(Plant::new(value, &self.metadata))),
+            "INORGANIC" | "SELECT_INORGANIC" => {
+                CurrentRaw::Inorganic(Box::new(Inorganic::new(value, &self.metadata)))
+            }
+            "MATERIAL_TEMPLATE" => {
+                CurrentRaw::MaterialTemplate(Box::new(MaterialTemplate::new(value, &self.metadata)))
+            }
+            "CREATURE_GRAPHICS"
+            | "CREATURE_CASTE_GRAPHICS"
+            | "STATUE_CREATURE_GRAPHICS"
+            | "STATUE_CREATURE_CASTE_GRAPHICS"
+            | "TILE_GRAPHICS"
+            | "PLANT_GRAPHICS" => {
+                let graphic_type = *GRAPHIC_TYPE_TOKENS
+                    .get(key)
+                    .unwrap_or(&GraphicTypeTag::Unknown);
+                CurrentRaw::Graphic(
+                    Box::new(Graphic::new(value, &self.metadata, graphic_type)),
+                    graphic_type,
+                )
+            }
+            "TILE_PAGE" => CurrentRaw::TilePage(Box::new(TilePage::new(value, &self.metadata))),
+            "ENTITY" => CurrentRaw::Entity(Box::new(Entity::new(value, &self.metadata))),
+            _ => return false,
+        };
+        true
+    }
+
+    fn report_unknown_token(&mut self, token: &Token, raw_type: &ObjectType) {
+        let owners: Vec<String> = TOKEN_OWNERS
+            .iter()
+            .filter(|owner| *owner != raw_type && is_known_token(owner, token.key))
+            .map(|owner| owner.to_string().to_lowercase())
+            .collect();
+        let raw_type = raw_type.to_string().to_lowercase();
+
+        if owners.is_empty() {
+            self.report(
+                RawProblemKind::UnknownToken,
+                token.key_span(),
+                format!(
+                    "Unknown {raw_type} token '{}' is ignored by the parser",
+                    token.key
+                ),
+            );
+        } else {
+            self.report(
+                RawProblemKind::WrongObjectType,
+                token.key_span(),
+                format!(
+                    "'{}' is only read in {} raws, so it is ignored by the parser in {raw_type} raws",
+                    token.key,
+                    owners.join(", ")
+                ),
+            );
+        }
+    }
+}
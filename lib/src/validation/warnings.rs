@@ -0,0 +1,65 @@
+//! Collecting the warnings the parser logs, so they can be reported for the token which caused
+//! them.
+
+use std::sync::{Arc, Mutex};
+
+use tracing::{
+    field::{Field, Visit},
+    level_filters::LevelFilter,
+    span, Event, Level, Metadata, Subscriber,
+};
+
+/// A `tracing` subscriber which keeps the messages of warnings (and errors) instead of logging
+/// them.
+#[derive(Debug, Clone, Default)]
+pub struct WarningCollector {
+    messages: Arc<Mutex<Vec<String>>>,
+}
+
+impl WarningCollector {
+    /// Take the messages collected since the last call.
+    pub fn take(&self) -> Vec<String> {
+        self.messages
+            .lock()
+            .map(|mut messages| std::mem::take(&mut *messages))
+            .unwrap_or_default()
+    }
+}
+
+impl Subscriber for WarningCollector {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= Level::WARN
+    }
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(LevelFilter::WARN)
+    }
+    fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+        // Spans aren't kept, so they can all share an id
+        span::Id::from_u64(1)
+    }
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        if let Ok(mut messages) = self.messages.lock() {
+            messages.push(visitor.message);
+        }
+    }
+    fn enter(&self, _span: &span::Id) {}
+    fn exit(&self, _span: &span::Id) {}
+}
+
+/// Reads the message of an event.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use dfraw_parser::{cache::ParseCache, metadata::ParserOptions, FileParseResult};
+
+/// A module with an `info.txt` and a plant raw file, and an empty cache directory next to it.
+fn write_module(name: &str, plant_name: &str) -> (PathBuf, ParseCache) {
+    let dir =
+        std::env::temp_dir().join(format!("dfraw_parser_cache_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("module/objects")).expect("Failed to create the fixture directory");
+    write_info_file(&dir, "1");
+    let raw_file = dir.join("module/objects/plant_cache.txt");
+    write_plant(&raw_file, plant_name);
+    (raw_file, ParseCache::new(dir.join("cache")))
+}
+
+fn write_info_file(dir: &Path, version: &str) {
+    fs::write(
+        dir.join("module/info.txt"),
+        format!("[ID:cache_test][NUMERIC_VERSION:{version}][DISPLAYED_VERSION:{version}][NAME:Cache test]"),
+    )
+    .expect("Failed to write the info file");
+}
+
+fn write_plant(raw_file: &Path, plant_name: &str) {
+    fs::write(
+        raw_file,
+        format!("plant_cache\n[OBJECT:PLANT]\n[PLANT:REED][NAME:{plant_name}]\n"),
+    )
+    .expect("Failed to write the raw file");
+}
+
+fn plant_name(result: &FileParseResult) -> &str {
+    result
+        .parsed_raws
+        .first()
+        .map(|raw| raw.get_name())
+        .expect("Expected a plant")
+}
+
+#[test]
+fn unchanged_files_are_read_from_the_cache() {
+    let (raw_file, cache) = write_module("hit", "reed");
+    let mut options = ParserOptions::default();
+
+    let parsed = cache
+        .parse_raw_file(&raw_file, &options)
+        .expect("Failed to parse the raw file");
+    assert_eq!(plant_name(&parsed), "reed");
+
+    // Same size and modified time: the cache is trusted without reading the file
+    let modified = fs::metadata(&raw_file)
+        .and_then(|metadata| metadata.modified())
+        .expect("Failed to read the modified time");
+    write_plant(&raw_file, "rush");
+    fs::File::options()
+        .write(true)
+        .open(&raw_file)
+        .and_then(|file| file.set_modified(modified))
+        .expect("Failed to set the modified time");
+    let cached = cache
+        .parse_raw_file(&raw_file, &options)
+        .expect("Failed to read the cached raw file");
+    assert_eq!(plant_name(&cached), "reed");
+    // The `hidden` flag isn't stored, but comes back from the options
+    assert!(cached.parsed_raws[0].get_metadata().is_hidden());
+
+    // Attaching the metadata changes the options, so the file is parsed again
+    options.attach_metadata_to_raws();
+    let parsed = cache
+        .parse_raw_file(&raw_file, &options)
+        .expect("Failed to parse the raw file");
+    assert_eq!(plant_name(&parsed), "rush");
+    let cached = cache
+        .parse_raw_file(&raw_file, &options)
+        .expect("Failed to read the cached raw file");
+    assert!(!cached.parsed_raws[0].get_metadata().is_hidden());
+}
+
+#[test]
+fn changed_raw_files_are_parsed_again() {
+    let (raw_file, cache) = write_module("raw_changed", "reed");
+    let options = ParserOptions::default();
+
+    cache
+        .parse_raw_file(&raw_file, &options)
+        .expect("Failed to parse the raw file");
+    write_plant(&raw_file, "bulrush");
+    let parsed = cache
+        .parse_raw_file(&raw_file, &options)
+        .expect("Failed to parse the raw file");
+    assert_eq!(plant_name(&parsed), "bulrush");
+}
+
+#[test]
+fn changed_info_files_invalidate_the_cache() {
+    let (raw_file, cache) = write_module("info_changed", "reed");
+    let dir = raw_file
+        .ancestors()
+        .nth(3)
+        .expect("Expected the fixture directory");
+    let options = ParserOptions::default();
+
+    let parsed = cache
+        .parse_raw_file(&raw_file, &options)
+        .expect("Failed to parse the raw file");
+    assert_eq!(
+        parsed.parsed_raws[0].get_metadata().get_module_version(),
+        "1"
+    );
+
+    write_info_file(dir, "2");
+    let parsed = cache
+        .parse_raw_file(&raw_file, &options)
+        .expect("Failed to parse the raw file");
+    assert_eq!(
+        parsed.parsed_raws[0].get_metadata().get_module_version(),
+        "2"
+    );
+}
@@ -0,0 +1,54 @@
+use dfraw_parser::{
+    tags::{CreatureEffectPropertyTag, CreatureEffectTag},
+    CreatureEffect,
+};
+use serde_json::json;
+
+#[test]
+fn properties_and_body_parts_are_parsed() {
+    let effect = CreatureEffect::from_value(
+        CreatureEffectTag::Pain,
+        "SEV:50:PROB:25:RESISTABLE:BP:BY_CATEGORY:ALL:SKIN:BP:BY_TOKEN:HEAD:MUSCLE:START:50:PEAK:500:END:1000",
+    );
+
+    assert_eq!(effect.get_effect(), &CreatureEffectTag::Pain);
+    assert!(effect.get_arguments().is_empty());
+    assert_eq!(effect.get_severity(), 50);
+    assert_eq!(effect.get_probability(), 25);
+    assert_eq!(effect.get_timing(), (50, 500, 1000));
+    assert!(effect.has_tag(&CreatureEffectPropertyTag::Resistible));
+    assert_eq!(
+        effect.get_affected_body_parts_by_category(),
+        [(String::from("ALL"), String::from("SKIN"))]
+    );
+    assert_eq!(
+        effect.get_affected_body_parts_by_token(),
+        [(String::from("HEAD"), String::from("MUSCLE"))]
+    );
+    assert!(effect.get_affected_body_parts_by_type().is_empty());
+
+    // Body parts are serialized as `[body part, tissue]` pairs
+    let serialized =
+        serde_json::to_value(effect.cleaned()).expect("Failed to serialize the effect");
+    assert_eq!(
+        serialized["affectedBodyPartsByCategory"],
+        json!([["ALL", "SKIN"]])
+    );
+    assert_eq!(serialized["affectedBodyPartsByType"], json!(null));
+}
+
+#[test]
+fn effect_arguments_are_kept_before_the_properties() {
+    let effect = CreatureEffect::from_value(
+        CreatureEffectTag::PhysAttChange,
+        "STRENGTH:150:0:START:0:END:1000:DWF_STRETCH:4",
+    );
+
+    assert_eq!(effect.get_arguments(), ["STRENGTH", "150", "0"]);
+    // Effects without a `PROB` always happen
+    assert_eq!(effect.get_probability(), 100);
+    assert_eq!(effect.get_severity(), 0);
+    assert_eq!(effect.get_timing(), (0, 0, 1000));
+    assert_eq!(effect.get_dwf_stretch(), Some(4));
+    assert!(effect.get_tags().is_empty());
+}
@@ -0,0 +1,61 @@
+use dfraw_parser::{
+    graphic::Graphic, graphics_index::GraphicsIndex, metadata::RawMetadata, tags::GraphicTypeTag,
+    traits::RawObject,
+};
+
+fn graphic(identifier: &str, module: &str, kind: GraphicTypeTag) -> Box<dyn RawObject> {
+    let metadata = RawMetadata::default().with_module_object_id(String::from(module));
+    Box::new(Graphic::new(identifier, &metadata, kind))
+}
+
+fn modules_of(graphics: &[&Graphic]) -> Vec<(GraphicTypeTag, String)> {
+    graphics
+        .iter()
+        .map(|graphic| {
+            (
+                graphic.get_graphic_type(),
+                graphic.get_metadata().get_module_object_id().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn later_modules_only_replace_graphics_of_the_same_kind() {
+    let raws = vec![
+        graphic("DWARF", "vanilla", GraphicTypeTag::Creature),
+        graphic("DWARF", "vanilla", GraphicTypeTag::StatueCreature),
+        graphic("DWARF", "statue_mod", GraphicTypeTag::StatueCreature),
+    ];
+    let index = GraphicsIndex::new(&raws);
+
+    assert_eq!(
+        modules_of(&index.graphics_for_creature("DWARF")),
+        vec![
+            (GraphicTypeTag::Creature, String::from("vanilla")),
+            (GraphicTypeTag::StatueCreature, String::from("statue_mod")),
+        ]
+    );
+}
+
+#[test]
+fn creature_graphics_do_not_replace_caste_graphics() {
+    let raws = vec![
+        graphic("DWARF", "vanilla", GraphicTypeTag::Creature),
+        graphic("DWARF:FEMALE", "vanilla", GraphicTypeTag::CreatureCaste),
+        graphic("DWARF", "dwarf_mod", GraphicTypeTag::Creature),
+    ];
+    let index = GraphicsIndex::new(&raws);
+
+    assert_eq!(
+        modules_of(&index.graphics_for_caste("DWARF", "FEMALE")),
+        vec![
+            (GraphicTypeTag::Creature, String::from("dwarf_mod")),
+            (GraphicTypeTag::CreatureCaste, String::from("vanilla")),
+        ]
+    );
+    assert_eq!(
+        modules_of(&index.graphics_for_caste("DWARF", "MALE")),
+        vec![(GraphicTypeTag::Creature, String::from("dwarf_mod"))]
+    );
+}
@@ -0,0 +1,216 @@
+use dfraw_parser::{
+    legends_export::{
+        parse, parse_with_progress, parse_world, parse_world_files,
+        parse_world_files_with_progress, LegendsProgress,
+    },
+    metadata::{ObjectType, ParserOptions, RawMetadata},
+};
+
+/// A `legends.xml` as Dwarf Fortress writes it: the declaration says UTF-8, but the text is in
+/// code page 437 (`0x82` is `é`, `0x94` is `ö` and `0x81` is `ü`).
+const LEGENDS_XML: &[u8] = b"<?xml version=\"1.0\" encoding='UTF-8'?>\n<df_world>\n\
+<name>Th\x82r \x94m</name>\n<altname>The Realm of Legends</altname>\n\
+<historical_figures>\n<historical_figure>\n<id>7</id>\n<name>\x81rist \x82b\x94n</name>\n\
+<race>DWARF</race>\n</historical_figure>\n</historical_figures>\n</df_world>\n";
+
+/// The same world as `DFHack` writes it in `legends_plus.xml`, in UTF-8.
+const LEGENDS_PLUS_XML: &str = "<?xml version=\"1.0\" encoding='UTF-8'?>\n<df_world>\n\
+<historical_figures>\n<historical_figure>\n<id>8</id>\n<name>\u{e9}rith \u{fc}l</name>\n\
+</historical_figure>\n</historical_figures>\n</df_world>\n";
+
+/// A generated creature in `legends_plus.xml`, with a body, a material and a caste.
+const GENERATED_CREATURE_XML: &str = "<?xml version=\"1.0\" encoding='UTF-8'?>\n<df_world>\n\
+<creature_raw>\n<creature>\n<creature_id>FORGOTTEN_BEAST_1</creature_id>\n\
+<name_singular>forgotten beast</name_singular>\n<name_plural>forgotten beasts</name_plural>\n\
+<generated/>\n<description>A huge spider.</description>\n\
+<body>\n<body_part>BODY_WITH_HEAD_FLAG</body_part>\n<body_part>8LEGS</body_part>\n</body>\n\
+<material>\n<id>VENOM</id>\n<state_name>venom</state_name>\n</material>\n\
+<caste>\n<caste_id>FEMALE</caste_id>\n<body_size>0:0:500000</body_size>\n</caste>\n\
+</creature>\n</creature_raw>\n</df_world>\n";
+
+/// Creatures and entities as `legends_plus.xml` lists them.
+const CREATURES_AND_ENTITIES_XML: &str = "<?xml version=\"1.0\" encoding='UTF-8'?>\n<df_world>\n\
+<creature_raw>\n<creature>\n<creature_id>TOAD</creature_id>\n<name_singular>toad</name_singular>\n\
+<name_plural>toads</name_plural>\n<amphibious/>\n<large_roaming/>\n</creature>\n\
+<creature>\n<creature_id>DWARF</creature_id>\n<name_singular>dwarf</name_singular>\n\
+<name_plural>dwarves</name_plural>\n<intelligent/>\n</creature>\n</creature_raw>\n\
+<entities>\n<entity>\n<id>1</id>\n<race>dwarf</race>\n<type>civilization</type>\n<child>3</child>\n\
+</entity>\n<entity>\n<id>3</id>\n<race>dwarf</race>\n<type>sitegovernment</type>\n</entity>\n\
+</entities>\n</df_world>\n";
+
+fn write_export(name: &str, contents: &[u8]) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("dfraw_parser_legends_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("Failed to create the fixture directory");
+    let path = dir.join(name);
+    std::fs::write(&path, contents).expect("Failed to write the fixture");
+    path
+}
+
+#[test]
+fn legends_xml_is_read_as_cp437() {
+    let path = write_export("region1-00250-01-01-legends.xml", LEGENDS_XML);
+    let world = parse_world(&path).expect("Failed to parse the fixture");
+
+    assert_eq!(world.get_name(), Some("Th\u{e9}r \u{f6}m"));
+    assert_eq!(
+        world
+            .get_historical_figure(7)
+            .and_then(|figure| figure.get_name()),
+        Some("\u{fc}rist \u{e9}b\u{f6}n")
+    );
+}
+
+#[test]
+fn legends_plus_xml_is_read_as_utf8() {
+    let legends = write_export("region2-00250-01-01-legends.xml", LEGENDS_XML);
+    let legends_plus = write_export(
+        "region2-00250-01-01-legends_plus.xml",
+        LEGENDS_PLUS_XML.as_bytes(),
+    );
+    let world = parse_world_files(&[legends, legends_plus]).expect("Failed to parse the fixtures");
+
+    assert_eq!(
+        world
+            .get_historical_figure(8)
+            .and_then(|figure| figure.get_name()),
+        Some("\u{e9}rith \u{fc}l")
+    );
+    assert_eq!(
+        world
+            .get_historical_figure(7)
+            .and_then(|figure| figure.get_name()),
+        Some("\u{fc}rist \u{e9}b\u{f6}n")
+    );
+}
+
+#[test]
+fn generated_creatures_keep_their_body_and_materials() {
+    let path = write_export(
+        "region3-00250-01-01-legends_plus.xml",
+        GENERATED_CREATURE_XML.as_bytes(),
+    );
+    let world = parse_world(&path).expect("Failed to parse the fixture");
+    let exported = world
+        .get_creature("FORGOTTEN_BEAST_1")
+        .expect("The creature wasn't read");
+
+    assert!(exported.is_generated());
+    assert_eq!(
+        exported.get_body_parts(),
+        vec!["BODY_WITH_HEAD_FLAG", "8LEGS"]
+    );
+    assert_eq!(
+        exported.get_fields_of("material").collect::<Vec<_>>(),
+        vec![("id", "VENOM"), ("state_name", "venom")]
+    );
+
+    let creature = exported.clone().into_creature(&RawMetadata::default());
+    let caste = |identifier: &str| {
+        creature
+            .get_castes()
+            .iter()
+            .find(|caste| caste.get_identifier() == identifier)
+            .expect("The caste wasn't added")
+    };
+    assert_eq!(caste("ALL").get_description(), Some("A huge spider."));
+    assert_eq!(caste("FEMALE").get_body_sizes().len(), 1);
+}
+
+#[test]
+fn streamed_export_has_the_same_creatures_and_entities() {
+    let path = write_export(
+        "region4-00250-01-01-legends_plus.xml",
+        CREATURES_AND_ENTITIES_XML.as_bytes(),
+    );
+    let mut reports = Vec::new();
+    let raws = parse_with_progress(&path, &ParserOptions::default(), |progress| {
+        reports.push(progress);
+    })
+    .expect("Failed to parse the fixture");
+
+    // What the reader returned before it streamed the export
+    let summary: Vec<_> = raws
+        .iter()
+        .map(|raw| {
+            (
+                raw.get_type().clone(),
+                raw.get_identifier().to_string(),
+                raw.get_search_vec(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                ObjectType::Creature,
+                String::from("TOAD"),
+                vec![
+                    String::from("largeroaming"),
+                    String::from("toad"),
+                    String::from("toads")
+                ]
+            ),
+            (
+                ObjectType::Creature,
+                String::from("DWARF"),
+                vec![String::from("dwarf"), String::from("dwarves")]
+            ),
+            (
+                ObjectType::Entity,
+                String::from("dwarf-civilization1"),
+                vec![String::from("dwarf-civilization1")]
+            ),
+            (
+                ObjectType::Entity,
+                String::from("dwarf-sitegovernment3"),
+                vec![String::from("dwarf-sitegovernment3")]
+            ),
+        ]
+    );
+    let as_json = |raws| serde_json::to_string(raws).expect("Failed to serialize the raws");
+    assert_eq!(
+        as_json(&raws),
+        as_json(&parse(&path, &ParserOptions::default()).expect("Failed to parse the fixture"))
+    );
+
+    let file_size = CREATURES_AND_ENTITIES_XML.len() as u64;
+    assert_eq!(
+        reports.last(),
+        Some(&LegendsProgress {
+            bytes_read: file_size,
+            total_bytes: file_size,
+        })
+    );
+}
+
+#[test]
+fn world_files_report_progress_across_the_files() {
+    let legends = write_export("region5-00250-01-01-legends.xml", LEGENDS_XML);
+    let legends_plus = write_export(
+        "region5-00250-01-01-legends_plus.xml",
+        LEGENDS_PLUS_XML.as_bytes(),
+    );
+    let mut reports = Vec::new();
+    let world = parse_world_files_with_progress(&[legends_plus, legends], |progress| {
+        reports.push(progress);
+    })
+    .expect("Failed to parse the fixtures");
+
+    assert_eq!(world.get_historical_figures().len(), 2);
+    // `legends.xml` is read first, then `legends_plus.xml` carries on from its size
+    let total_bytes = (LEGENDS_XML.len() + LEGENDS_PLUS_XML.len()) as u64;
+    assert_eq!(
+        reports,
+        vec![
+            LegendsProgress {
+                bytes_read: LEGENDS_XML.len() as u64,
+                total_bytes,
+            },
+            LegendsProgress {
+                bytes_read: total_bytes,
+                total_bytes,
+            },
+        ]
+    );
+}
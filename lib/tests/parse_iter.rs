@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+
+use dfraw_parser::{metadata::ParserOptions, parse, parse_iter, traits::RawObject};
+
+const CREATURE_RAWS: &str = "creature_iter\n[OBJECT:CREATURE]\n\
+[CREATURE:TOAD][NAME:toad:toads:toad][BODY_SIZE:0:0:100][AMPHIBIOUS]\n\
+[CREATURE:GIANT_TOAD][COPY_TAGS_FROM:TOAD][NAME:giant toad:giant toads:giant toad]\n";
+
+const PLANT_RAWS: &str = "plant_iter\n[OBJECT:PLANT]\n\
+[PLANT:REED][NAME:reed:reeds:reed]\n[PLANT:BERRY][NAME:berry:berries:berry]\n";
+
+fn write_fixture(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dfraw_parser_iter_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("Failed to create the fixture directory");
+    let path = dir.join(name);
+    std::fs::write(&path, contents).expect("Failed to write the fixture");
+    path
+}
+
+fn options() -> ParserOptions {
+    let mut options = ParserOptions::default();
+    options.set_raw_files_to_parse(vec![
+        write_fixture("creature_iter.txt", CREATURE_RAWS),
+        write_fixture("plant_iter.txt", PLANT_RAWS),
+    ]);
+    options
+}
+
+#[test]
+fn parse_iter_yields_the_same_raws_as_parse() {
+    let options = options();
+    let parsed = parse(&options).expect("Failed to parse the fixtures");
+    let iterated = parse_iter(&options)
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Failed to parse the fixtures");
+
+    let object_ids = |raws: &[Box<dyn RawObject>]| {
+        raws.iter()
+            .map(|raw| raw.get_object_id().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(parsed.raws.len(), 4);
+    assert_eq!(object_ids(&iterated), object_ids(&parsed.raws));
+    // The creatures are resolved the same way (the giant toad copies the toad's tags)
+    let as_json = |raws: &[Box<dyn RawObject>]| {
+        serde_json::to_string(raws).expect("Failed to serialize the raws")
+    };
+    assert_eq!(as_json(&iterated), as_json(&parsed.raws));
+}
+
+#[test]
+fn parse_iter_reports_errors_inline_and_carries_on() {
+    let mut options = ParserOptions::default();
+    options.set_raw_files_to_parse(vec![
+        write_fixture("creature_iter.txt", CREATURE_RAWS),
+        // The parser stops reading a file whose object types don't match
+        write_fixture(
+            "plant_mismatch_iter.txt",
+            "plant_mismatch_iter\n[OBJECT:PLANT]\n[OBJECT:CREATURE]\n",
+        ),
+        write_fixture("plant_iter.txt", PLANT_RAWS),
+    ]);
+
+    assert!(parse(&options).is_err());
+
+    let items: Vec<_> = parse_iter(&options)
+        .map(|item| {
+            item.map_or_else(
+                |_| String::from("error"),
+                |raw| raw.get_identifier().to_string(),
+            )
+        })
+        .collect();
+    // The error comes in place of the broken file, the plants after it are still parsed, and the
+    // creatures are still resolved
+    assert_eq!(items, vec!["error", "REED", "BERRY", "TOAD", "GIANT_TOAD"]);
+}
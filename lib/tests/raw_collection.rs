@@ -0,0 +1,95 @@
+use dfraw_parser::{
+    creature::Creature,
+    metadata::RawMetadata,
+    query::QueryField,
+    raw_collection::{PageRequest, RawCollection, SortKey, SortOrder, DEFAULT_PAGE_SIZE},
+    traits::RawObject,
+    ParserError,
+};
+
+const CREATURES: [(&str, &str); 5] = [
+    ("CAT", "5000"),
+    ("DOG", "30000"),
+    ("DWARF", "60000"),
+    ("ELEPHANT", "5000000"),
+    ("TOAD", "100"),
+];
+
+fn collection(skip: &[&str]) -> RawCollection {
+    let mut raws: Vec<Box<dyn RawObject>> = Vec::new();
+    for (identifier, size) in CREATURES {
+        if skip.contains(&identifier) {
+            continue;
+        }
+        let mut creature = Creature::new(identifier, &RawMetadata::default());
+        creature.parse_tag("BODY_SIZE", &format!("0:0:{size}"));
+        raws.push(Box::new(creature));
+    }
+    RawCollection::new(raws)
+}
+
+fn identifiers(collection: &RawCollection, request: &PageRequest) -> (Vec<String>, Option<String>) {
+    let page = collection.page(request).expect("Failed to get the page");
+    (
+        page.raws
+            .iter()
+            .map(|raw| raw.get_identifier().to_string())
+            .collect(),
+        page.next_cursor,
+    )
+}
+
+#[test]
+fn a_limit_of_zero_uses_the_default_page_size() {
+    let request = PageRequest::new().with_limit(0);
+    assert_eq!(request.get_limit(), DEFAULT_PAGE_SIZE);
+
+    let (raws, next_cursor) = identifiers(&collection(&[]), &request);
+    assert_eq!(raws.len(), CREATURES.len());
+    assert!(next_cursor.is_none());
+}
+
+#[test]
+fn cursors_carry_on_after_raws_are_removed() {
+    let request = PageRequest::new()
+        .sorted_by(
+            SortKey::Numeric(QueryField::BodySize),
+            SortOrder::Descending,
+        )
+        .with_limit(2);
+    let (raws, next_cursor) = identifiers(&collection(&[]), &request);
+    assert_eq!(raws, vec!["ELEPHANT", "DWARF"]);
+    let cursor = next_cursor.expect("Expected a next page");
+
+    // The last raw of the page is removed, and one from the next page too
+    let collection = collection(&["DWARF", "CAT"]);
+    let (raws, next_cursor) = identifiers(&collection, &request.after(&cursor));
+    assert_eq!(raws, vec!["DOG", "TOAD"]);
+    assert!(next_cursor.is_none());
+}
+
+#[test]
+fn cursors_only_work_with_the_same_sorting() {
+    let collection = collection(&[]);
+    let request = PageRequest::new().with_limit(2);
+    let (_, next_cursor) = identifiers(&collection, &request);
+    let cursor = next_cursor.expect("Expected a next page");
+
+    for request in [
+        request
+            .clone()
+            .sorted_by(SortKey::default(), SortOrder::Descending),
+        request
+            .clone()
+            .sorted_by(SortKey::Numeric(QueryField::BodySize), SortOrder::default()),
+    ] {
+        assert!(matches!(
+            collection.page(&request.after(&cursor)),
+            Err(ParserError::InvalidCursor(_))
+        ));
+    }
+    assert!(matches!(
+        collection.page(&request.after("not a cursor")),
+        Err(ParserError::InvalidCursor(_))
+    ));
+}
@@ -0,0 +1,112 @@
+use dfraw_parser::{
+    metadata::RawMetadata,
+    syndrome_timeline::{SyndromeTarget, SyndromeTimeline, REFERENCE_BODY_SIZE, STANDARD_DOSE},
+    traits::RawObject,
+    Creature, Syndrome,
+};
+
+/// A dwarf whose castes are all mammals, with a female caste twice the reference body size.
+fn dwarf() -> Creature {
+    let mut dwarf = Creature::new("DWARF", &RawMetadata::default());
+    dwarf.parse_tag("CREATURE_CLASS", "MAMMAL");
+    dwarf.parse_tag("CASTE", "FEMALE");
+    dwarf.parse_tag("BODY_SIZE", &format!("0:0:{}", REFERENCE_BODY_SIZE * 2));
+    dwarf
+}
+
+fn syndrome(tokens: &[(&str, &str)]) -> Syndrome {
+    let mut syndrome = Syndrome::new();
+    for (key, value) in tokens {
+        syndrome.parse_tag(key, value);
+    }
+    syndrome
+}
+
+#[test]
+fn immune_classes_are_not_affected() {
+    let dwarf = dwarf();
+    let target = SyndromeTarget::new(&dwarf, "FEMALE");
+    // The class comes from the `ALL` caste
+    assert_eq!(target.get_creature_classes(), vec!["MAMMAL"]);
+
+    let syndrome = syndrome(&[
+        ("SYN_AFFECTED_CREATURE", "DWARF:FEMALE"),
+        ("SYN_IMMUNE_CLASS", "MAMMAL"),
+        ("CE_PAIN", "SEV:50:START:0:END:100"),
+    ]);
+    // Immunity wins over being affected
+    assert!(!target.is_affected_by(&syndrome));
+    let timeline = SyndromeTimeline::simulate(&syndrome, &target, STANDARD_DOSE, false);
+    assert!(!timeline.is_affected());
+    assert!(timeline.get_effects().is_empty());
+}
+
+#[test]
+fn affected_creatures_with_caste_all_affect_every_caste() {
+    let dwarf = dwarf();
+    let syndrome = syndrome(&[
+        ("SYN_AFFECTED_CREATURE", "DWARF:ALL"),
+        ("CE_PAIN", "SEV:50:START:0:END:100"),
+    ]);
+
+    for caste in ["FEMALE", "MALE"] {
+        let target = SyndromeTarget::new(&dwarf, caste);
+        assert!(target.is_affected_by(&syndrome), "{caste} isn't affected");
+        let timeline = SyndromeTimeline::simulate(&syndrome, &target, STANDARD_DOSE, false);
+        assert!(timeline.is_affected());
+        assert_eq!(timeline.get_duration(), Some(100));
+    }
+
+    let toad = Creature::new("TOAD", &RawMetadata::default());
+    assert!(!SyndromeTarget::new(&toad, "FEMALE").is_affected_by(&syndrome));
+}
+
+#[test]
+fn bigger_creatures_delay_and_dilute_the_effects() {
+    let dwarf = dwarf();
+    let target = SyndromeTarget::new(&dwarf, "FEMALE");
+    assert_eq!(target.get_body_size(), REFERENCE_BODY_SIZE * 2);
+
+    let syndrome = syndrome(&[
+        (
+            "CE_PAIN",
+            "SEV:100:SIZE_DELAYS:SIZE_DILUTES:START:10:PEAK:110:END:1000",
+        ),
+        ("CE_NAUSEA", "SEV:100:START:10:PEAK:110:END:1000"),
+    ]);
+    let timeline = SyndromeTimeline::simulate(&syndrome, &target, STANDARD_DOSE, false);
+
+    let [pain, nausea] = timeline.get_effects() else {
+        panic!("Expected two effects");
+    };
+    // Twice the body size takes twice as long, at half the severity
+    assert_eq!(
+        (pain.get_start(), pain.get_peak(), pain.get_end()),
+        (20, 220, Some(2000))
+    );
+    assert_eq!(pain.get_severity(), 50);
+    // Effects without the tags are as written
+    assert_eq!(
+        (nausea.get_start(), nausea.get_peak(), nausea.get_end()),
+        (10, 110, Some(1000))
+    );
+    assert_eq!(nausea.get_severity(), 100);
+
+    // A double dose on the double size is as written again
+    let timeline = SyndromeTimeline::simulate(&syndrome, &target, STANDARD_DOSE * 2, false);
+    let pain = &timeline.get_effects()[0];
+    assert_eq!((pain.get_start(), pain.get_end()), (10, Some(1000)));
+    assert_eq!(pain.get_severity(), 100);
+
+    // Samples run until the last effect ends
+    let timeline = SyndromeTimeline::simulate(&syndrome, &target, STANDARD_DOSE, false);
+    assert_eq!(timeline.get_duration(), Some(2000));
+    let samples = timeline.sample(500, 10_000);
+    assert_eq!(
+        samples.iter().map(|(tick, _)| *tick).collect::<Vec<_>>(),
+        vec![0, 500, 1000, 1500, 2000]
+    );
+    assert_eq!(samples[0].1, vec![0, 0]);
+    // The pain wears off from its peak at 220 until 2000, and the nausea has ended
+    assert_eq!(samples[2].1, vec![50 * 1000 / 1780, 0]);
+}
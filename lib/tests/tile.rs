@@ -0,0 +1,41 @@
+use dfraw_parser::{Color, Tile};
+use serde_json::json;
+
+#[test]
+fn colors_always_serialize_their_rgb_colors() {
+    assert_eq!(
+        serde_json::to_value(Color::default()).expect("Failed to serialize the color"),
+        json!({
+            "foreground": 0,
+            "background": 0,
+            "brightness": 0,
+            "foregroundRgb": [0, 0, 0],
+            "backgroundRgb": [0, 0, 0],
+        })
+    );
+
+    let color: Color =
+        serde_json::from_value(json!({ "foreground": 7, "background": 0, "brightness": 1 }))
+            .expect("Failed to deserialize the color");
+    let serialized = serde_json::to_value(color).expect("Failed to serialize the color");
+    assert_eq!(serialized["foregroundRgb"], json!([255, 255, 255]));
+    assert_eq!(serialized["backgroundRgb"], json!([0, 0, 0]));
+}
+
+#[test]
+fn every_tile_character_is_resolved_to_unicode() {
+    let tile = Tile::default()
+        .with_character("1")
+        .with_alt_character("'X'")
+        .with_glow_character("219");
+    let serialized = serde_json::to_value(&tile).expect("Failed to serialize the tile");
+    assert_eq!(serialized["unicodeCharacter"], json!("☺"));
+    assert_eq!(serialized["unicodeAltCharacter"], json!("X"));
+    assert_eq!(serialized["unicodeGlowCharacter"], json!("█"));
+
+    let tile: Tile = serde_json::from_value(json!({ "character": "1", "altCharacter": "2" }))
+        .expect("Failed to deserialize the tile");
+    let serialized = serde_json::to_value(&tile).expect("Failed to serialize the tile");
+    assert_eq!(serialized["unicodeCharacter"], json!("☺"));
+    assert_eq!(serialized["unicodeAltCharacter"], json!("☻"));
+}
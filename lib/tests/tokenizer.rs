@@ -0,0 +1,106 @@
+use dfraw_parser::tokenizer::{tokenize, MalformedReason, Node, Span};
+
+const fn span_of(node: &Node) -> Span {
+    match node {
+        Node::Token(token) => token.span,
+        Node::Comment(comment) => comment.span,
+        Node::Whitespace(whitespace) => whitespace.span,
+        Node::Malformed(malformed) => malformed.span,
+    }
+}
+
+#[test]
+fn spans_cover_the_text_in_order() {
+    let text = "creature_example\r\n[CREATURE:EXAMPLE] comment\n\t[NAME:é:ées:éy][FLIER]";
+    let nodes: Vec<_> = tokenize(text).collect();
+
+    let mut end = 0;
+    for node in &nodes {
+        let span = span_of(node);
+        assert_eq!(
+            span.start, end,
+            "{node:?} doesn't start where the last node ended"
+        );
+        end = span.end;
+    }
+    assert_eq!(end, text.len());
+
+    let spans: Vec<_> = nodes
+        .iter()
+        .map(|node| {
+            let span = span_of(node);
+            (&text[span.start..span.end], span.line, span.column)
+        })
+        .collect();
+    assert_eq!(
+        spans,
+        vec![
+            ("creature_example", 0, 0),
+            ("\r\n", 0, 16),
+            ("[CREATURE:EXAMPLE]", 1, 0),
+            (" ", 1, 18),
+            ("comment", 1, 19),
+            ("\n\t", 1, 26),
+            ("[NAME:é:ées:éy]", 2, 1),
+            // Columns count characters, not bytes
+            ("[FLIER]", 2, 16),
+        ]
+    );
+}
+
+#[test]
+fn argument_spans_point_at_the_arguments() {
+    let text = "\n  [NAME:é:ées:]";
+    let Some(Node::Token(token)) = tokenize(text).nth(1) else {
+        panic!("Expected a token");
+    };
+
+    assert_eq!(token.args, vec!["é", "ées", ""]);
+    assert_eq!(token.value, "é:ées:");
+    let key_span = token.key_span();
+    assert_eq!(
+        (&text[key_span.start..key_span.end], key_span.column),
+        ("NAME", 3)
+    );
+    let arg_spans: Vec<_> = (0..token.args.len())
+        .filter_map(|index| token.arg_span(index))
+        .map(|span| (&text[span.start..span.end], span.line, span.column))
+        .collect();
+    assert_eq!(arg_spans, vec![("é", 1, 8), ("ées", 1, 10), ("", 1, 14)]);
+    assert_eq!(token.arg_span(3), None);
+}
+
+#[test]
+fn malformed_tokens_are_kept_with_their_reason() {
+    let text = "[NAME:unclosed\n[:VALUE][][OPEN[FLIER]";
+    let malformed: Vec<_> = tokenize(text)
+        .filter_map(|node| match node {
+            Node::Malformed(malformed) => Some((malformed.text, malformed.reason)),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        malformed,
+        vec![
+            ("[NAME:unclosed", MalformedReason::UnclosedBracket),
+            ("[:VALUE]", MalformedReason::EmptyKey),
+            ("[]", MalformedReason::EmptyKey),
+            // A token can't contain another `[`, so this one stops before it
+            ("[OPEN", MalformedReason::UnclosedBracket),
+        ]
+    );
+    // Nothing is lost, and the token after the malformed ones is still read
+    assert_eq!(
+        tokenize(text)
+            .map(|node| &text[span_of(&node).start..span_of(&node).end])
+            .collect::<String>(),
+        text
+    );
+    assert!(matches!(tokenize(text).last(), Some(Node::Token(token)) if token.key == "FLIER"));
+    // An unclosed bracket at the end of the text
+    assert!(matches!(
+        tokenize("[FLIER").next(),
+        Some(Node::Malformed(malformed)) if malformed.reason == MalformedReason::UnclosedBracket
+    ));
+}
@@ -0,0 +1,56 @@
+use dfraw_parser::validation::{validate_raw_text, RawProblem, RawProblemKind};
+
+fn problems_in(text: &str) -> Vec<(RawProblemKind, String)> {
+    validate_raw_text(text)
+        .into_iter()
+        .map(|RawProblem { kind, span, .. }| (kind, text[span.start..span.end].to_string()))
+        .collect()
+}
+
+#[test]
+fn tokens_are_checked_against_the_raw_they_belong_to() {
+    let text = "plant_example\n[OBJECT:PLANT]\n[PLANT:EXAMPLE]\n[TREE:LOG]\n[FLIER]\n\
+        [NOT_A_TOKEN:NOW]\n[SHRUB_TILE:5]";
+
+    assert_eq!(
+        problems_in(text),
+        vec![
+            (RawProblemKind::WrongObjectType, String::from("FLIER")),
+            (RawProblemKind::UnknownToken, String::from("NOT_A_TOKEN")),
+        ]
+    );
+}
+
+#[test]
+fn parse_warnings_are_reported_for_the_token_which_caused_them() {
+    let text = "creature_example\n[OBJECT:CREATURE]\n[CREATURE:EXAMPLE]\n[BIOME:ANY_LAND]\n\
+        [BIOME:NOWHERE]\n[CASTE:FEMALE]\n[FEMALE]";
+
+    assert_eq!(
+        problems_in(text),
+        vec![(
+            RawProblemKind::ParseWarning,
+            String::from("[BIOME:NOWHERE]")
+        )]
+    );
+}
+
+#[test]
+fn object_tokens_are_checked_like_the_parser_checks_them() {
+    assert_eq!(
+        problems_in("[CREATURE:EXAMPLE]\n[FLIER]"),
+        vec![(RawProblemKind::MissingObject, String::new())]
+    );
+    assert_eq!(
+        problems_in("[OBJECT:NOT_AN_OBJECT]\n[OBJECT:CREATURE]\n[OBJECT:PLANT]"),
+        vec![
+            (
+                RawProblemKind::UnknownObjectType,
+                String::from("NOT_AN_OBJECT")
+            ),
+            (RawProblemKind::ObjectTypeMismatch, String::from("PLANT")),
+        ]
+    );
+    // Reactions aren't parsed, so their tokens aren't checked
+    assert!(problems_in("[OBJECT:REACTION]\n[REACTION:EXAMPLE]\n[NOT_A_TOKEN]").is_empty());
+}
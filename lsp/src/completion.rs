@@ -0,0 +1,60 @@
+//! Completion of token names.
+
+use std::collections::BTreeMap;
+
+use lsp_types::{CompletionItem, CompletionItemKind, Documentation, MarkupContent, MarkupKind};
+
+use crate::{
+    document::Document,
+    hover::{token_docs, TagSource},
+};
+
+/// Build the completion items for the given offset in a document.
+///
+/// Token names are only completed when the cursor is in the key of a token, i.e. after a `[` and
+/// before any `:` or `]`.
+///
+/// Arguments:
+///
+/// * `document`: The document being edited.
+/// * `offset`: The byte offset of the cursor.
+///
+/// Returns:
+///
+/// The completion items, which is empty if the cursor is not in a token key.
+pub fn completions(document: &Document, offset: usize) -> Vec<CompletionItem> {
+    let before = &document.text[..offset.min(document.text.len())];
+    let line = &before[before.rfind('\n').map_or(0, |newline| newline + 1)..];
+
+    let Some(open) = line.rfind('[') else {
+        return Vec::new();
+    };
+    if line[open..].contains([']', ':']) {
+        return Vec::new();
+    }
+
+    // Keyed by label so tokens shared between sources only show up once, sorted by name.
+    let mut items: BTreeMap<&str, CompletionItem> = BTreeMap::new();
+    for source in TagSource::for_object_type(&document.object_type) {
+        for key in source.keys() {
+            items.entry(key).or_insert_with(|| {
+                let docs = token_docs(key, &document.object_type);
+                CompletionItem {
+                    label: key.to_string(),
+                    kind: Some(CompletionItemKind::KEYWORD),
+                    detail: docs
+                        .as_ref()
+                        .map(|docs| format!("{}::{}", docs.source.name(), docs.variant)),
+                    documentation: docs.map(|docs| {
+                        Documentation::MarkupContent(MarkupContent {
+                            kind: MarkupKind::Markdown,
+                            value: docs.docs,
+                        })
+                    }),
+                    ..CompletionItem::default()
+                }
+            });
+        }
+    }
+    items.into_values().collect()
+}
@@ -0,0 +1,47 @@
+//! Diagnostics for raw files.
+//!
+//! The problems come from the library's validation, which reads the document with the parser's
+//! own raw objects, so the editor shows what the parser would skip, ignore or warn about.
+
+use dfraw_parser::validation::{validate_raw_text, RawProblem, RawProblemKind};
+use lsp_types::{Diagnostic, DiagnosticSeverity};
+
+use crate::document::Document;
+
+/// The source name shown alongside each diagnostic in the editor.
+const SOURCE: &str = "dfraw";
+
+/// Build the diagnostics for a document.
+///
+/// Arguments:
+///
+/// * `document`: The document to check.
+/// * `is_raw_file`: Whether the document is expected to be a raw file (i.e. it is not an
+///   `info.txt` file). Only raw files are required to have an `[OBJECT]` token.
+///
+/// Returns:
+///
+/// The diagnostics found in the document.
+pub fn diagnostics(document: &Document, is_raw_file: bool) -> Vec<Diagnostic> {
+    validate_raw_text(&document.text)
+        .into_iter()
+        .filter(|problem| is_raw_file || problem.kind != RawProblemKind::MissingObject)
+        .map(|problem| diagnostic(document, problem))
+        .collect()
+}
+
+/// Turn a problem into a diagnostic with our source name attached.
+fn diagnostic(document: &Document, problem: RawProblem) -> Diagnostic {
+    let severity = if problem.kind.is_error() {
+        DiagnosticSeverity::ERROR
+    } else {
+        DiagnosticSeverity::WARNING
+    };
+    Diagnostic {
+        range: document.range_of(problem.span),
+        severity: Some(severity),
+        source: Some(SOURCE.to_string()),
+        message: problem.message,
+        ..Diagnostic::default()
+    }
+}
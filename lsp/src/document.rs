@@ -0,0 +1,91 @@
+//! Open text documents and conversions between byte offsets and LSP positions.
+
+use dfraw_parser::{
+    metadata::{ObjectType, OBJECT_TOKEN_MAP},
+    tokenizer::{tokenize, Node, Span, Token},
+};
+use lsp_types::{Position, Range};
+
+/// A raw file's text, along with the object type declared by its `[OBJECT]` token.
+#[derive(Debug, Clone)]
+pub struct Document {
+    /// The full text of the document.
+    pub text: String,
+    /// The object type of the document, from its first `[OBJECT]` token.
+    pub object_type: ObjectType,
+}
+
+impl Document {
+    /// Create a new document from its text.
+    pub fn new(text: String) -> Self {
+        let object_type = object_type_of(&text);
+        Self { text, object_type }
+    }
+    /// Convert an LSP position (zero-based line, UTF-16 character) into a byte offset.
+    ///
+    /// Positions past the end of a line are clamped to the end of that line, and positions past the
+    /// end of the document are clamped to the end of the document.
+    pub fn offset_at(&self, position: Position) -> usize {
+        let mut line_start = 0;
+        for _ in 0..position.line {
+            match self.text[line_start..].find('\n') {
+                Some(newline) => line_start += newline + 1,
+                None => return self.text.len(),
+            }
+        }
+        let line = &self.text[line_start..];
+        let line = &line[..line.find('\n').unwrap_or(line.len())];
+
+        let mut utf16_count = 0;
+        for (offset, c) in line.char_indices() {
+            if utf16_count >= position.character as usize {
+                return line_start + offset;
+            }
+            utf16_count += c.len_utf16();
+        }
+        line_start + line.len()
+    }
+    /// Convert a byte offset into an LSP position (zero-based line, UTF-16 character).
+    pub fn position_at(&self, offset: usize) -> Position {
+        let offset = offset.min(self.text.len());
+        let before = &self.text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        let line = before.matches('\n').count();
+        let character: usize = before[line_start..].chars().map(char::len_utf16).sum();
+
+        Position {
+            line: u32::try_from(line).unwrap_or(u32::MAX),
+            character: u32::try_from(character).unwrap_or(u32::MAX),
+        }
+    }
+    /// Convert a tokenizer span into an LSP range.
+    pub fn range_of(&self, span: Span) -> Range {
+        Range {
+            start: self.position_at(span.start),
+            end: self.position_at(span.end),
+        }
+    }
+    /// Get the token which contains the given byte offset, if any.
+    pub fn token_at(&self, offset: usize) -> Option<Token<'_>> {
+        tokenize(&self.text)
+            .skip_while(|node| node.span().end <= offset)
+            .take(1)
+            .find_map(|node| match node {
+                Node::Token(token) if token.span.contains(offset) => Some(token),
+                _ => None,
+            })
+    }
+}
+
+/// Find the object type of some raw text from its first `[OBJECT]` token.
+///
+/// Returns `ObjectType::Unknown` if there is no `[OBJECT]` token or the type is not recognized.
+pub fn object_type_of(text: &str) -> ObjectType {
+    tokenize(text)
+        .find_map(|node| match node {
+            Node::Token(token) if token.key == "OBJECT" => Some(token.value),
+            _ => None,
+        })
+        .and_then(|value| OBJECT_TOKEN_MAP.get(value).cloned())
+        .unwrap_or_default()
+}
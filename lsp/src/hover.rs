@@ -0,0 +1,153 @@
+//! Token documentation, drawn from the doc comments on the parser's tag enums.
+//!
+//! The tag enums derive `specta::Type`, which records the doc comment of every variant. We read
+//! those at runtime and look them up by the variant a raw token maps to in the token maps.
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use dfraw_parser::{
+    metadata::ObjectType,
+    raw_definitions::{CASTE_TOKENS, CREATURE_TOKENS, ENTITY_TOKENS},
+    tags::{CasteTag, CreatureTag, EntityTag},
+};
+use specta::{DataType, Generics, Type, TypeMap};
+
+/// Which tag enum a token belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagSource {
+    /// A creature-level token (`CreatureTag`)
+    Creature,
+    /// A caste-level token (`CasteTag`)
+    Caste,
+    /// An entity token (`EntityTag`)
+    Entity,
+}
+
+impl TagSource {
+    /// The tag sources which apply to a file of the given object type, in lookup order.
+    pub const fn for_object_type(object_type: &ObjectType) -> &'static [Self] {
+        match object_type {
+            ObjectType::Creature | ObjectType::CreatureVariation => &[Self::Creature, Self::Caste],
+            ObjectType::Entity => &[Self::Entity],
+            _ => &[Self::Creature, Self::Caste, Self::Entity],
+        }
+    }
+    /// The name of the enum, for display.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Creature => "CreatureTag",
+            Self::Caste => "CasteTag",
+            Self::Entity => "EntityTag",
+        }
+    }
+    /// All of the token keys in this source's token map.
+    pub fn keys(self) -> Vec<&'static str> {
+        match self {
+            Self::Creature => CREATURE_TOKENS.keys().copied().collect(),
+            Self::Caste => CASTE_TOKENS.keys().copied().collect(),
+            Self::Entity => ENTITY_TOKENS.keys().copied().collect(),
+        }
+    }
+    /// Get the enum variant name for a token key, if this source knows the key.
+    pub fn variant_name(self, key: &str) -> Option<String> {
+        let value = match self {
+            Self::Creature => serde_json::to_value(CREATURE_TOKENS.get(key)?),
+            Self::Caste => serde_json::to_value(CASTE_TOKENS.get(key)?),
+            Self::Entity => serde_json::to_value(ENTITY_TOKENS.get(key)?),
+        }
+        .ok()?;
+
+        // Unit variants serialize as a string, and the rest as a single-key object.
+        match value {
+            serde_json::Value::String(name) => Some(name),
+            serde_json::Value::Object(map) => map.keys().next().cloned(),
+            _ => None,
+        }
+    }
+    /// The doc comments for each variant of this source's enum.
+    fn variant_docs(self) -> &'static HashMap<String, String> {
+        static CREATURE_DOCS: OnceLock<HashMap<String, String>> = OnceLock::new();
+        static CASTE_DOCS: OnceLock<HashMap<String, String>> = OnceLock::new();
+        static ENTITY_DOCS: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+        match self {
+            Self::Creature => CREATURE_DOCS.get_or_init(variant_docs::<CreatureTag>),
+            Self::Caste => CASTE_DOCS.get_or_init(variant_docs::<CasteTag>),
+            Self::Entity => ENTITY_DOCS.get_or_init(variant_docs::<EntityTag>),
+        }
+    }
+}
+
+/// Documentation for a single token.
+#[derive(Debug, Clone)]
+pub struct TokenDocs {
+    /// Where the documentation came from.
+    pub source: TagSource,
+    /// The name of the enum variant the token maps to.
+    pub variant: String,
+    /// The doc comment of the variant (may be empty).
+    pub docs: String,
+}
+
+impl TokenDocs {
+    /// Format the documentation as markdown for display in the editor.
+    pub fn to_markdown(&self, key: &str) -> String {
+        let mut markdown = format!("**{key}** (`{}::{}`)", self.source.name(), self.variant);
+        if !self.docs.is_empty() {
+            markdown.push_str("\n\n");
+            markdown.push_str(&self.docs);
+        }
+        markdown
+    }
+}
+
+/// Look up the documentation for a token key.
+///
+/// Arguments:
+///
+/// * `key`: The token key, e.g. `FLIER`.
+/// * `object_type`: The object type of the file the token is in. This decides which tag enums are
+///   searched (and in what order).
+///
+/// Returns:
+///
+/// The documentation for the token, or `None` if the key is not in any of the token maps.
+pub fn token_docs(key: &str, object_type: &ObjectType) -> Option<TokenDocs> {
+    TagSource::for_object_type(object_type)
+        .iter()
+        .find_map(|&source| {
+            let variant = source.variant_name(key)?;
+            let docs = source
+                .variant_docs()
+                .get(&variant)
+                .cloned()
+                .unwrap_or_default();
+            Some(TokenDocs {
+                source,
+                variant,
+                docs,
+            })
+        })
+}
+
+/// Collect the doc comment of each variant of an enum which derives `specta::Type`.
+fn variant_docs<T: Type>() -> HashMap<String, String> {
+    let mut type_map = TypeMap::default();
+    match T::inline(&mut type_map, Generics::Definition) {
+        DataType::Enum(enum_type) => enum_type
+            .variants()
+            .iter()
+            .map(|(name, variant)| {
+                // Each line of the doc comment keeps the space that followed the `///`.
+                let docs = variant
+                    .docs()
+                    .lines()
+                    .map(|line| line.strip_prefix(' ').unwrap_or(line))
+                    .collect::<Vec<&str>>()
+                    .join("\n");
+                (name.to_string(), docs.trim().to_string())
+            })
+            .collect(),
+        _ => HashMap::new(),
+    }
+}
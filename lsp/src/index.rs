@@ -0,0 +1,174 @@
+//! An index of the objects defined across the workspace, used for go-to-definition.
+
+use std::{collections::HashMap, path::Path};
+
+use dfraw_parser::{
+    constants::DF_ENCODING,
+    metadata::{ObjectType, OBJECT_TOKEN_MAP},
+    tokenizer::{tokenize, Node, Token},
+};
+use lsp_types::{Location, Url};
+use tracing::{debug, info, warn};
+use walkdir::WalkDir;
+
+use crate::document::Document;
+
+/// An object definition, e.g. `[CREATURE:DWARF]` or `[TILE_PAGE:DWARVES]`.
+#[derive(Debug, Clone)]
+pub struct Definition {
+    /// The identifier of the object.
+    pub identifier: String,
+    /// The type of object that is defined.
+    pub object_type: ObjectType,
+    /// Where the identifier is.
+    pub location: Location,
+}
+
+/// The objects defined in each file in the workspace.
+#[derive(Debug, Default)]
+pub struct Index {
+    files: HashMap<Url, Vec<Definition>>,
+}
+
+impl Index {
+    /// Index all of the `.txt` files under a directory.
+    ///
+    /// Arguments:
+    ///
+    /// * `root`: The directory to index.
+    pub fn index_directory<P: AsRef<Path>>(&mut self, root: &P) {
+        let mut indexed_files = 0_usize;
+        for entry in WalkDir::new(root)
+            .into_iter()
+            .filter_map(std::result::Result::ok)
+        {
+            let path = entry.path();
+            if !entry.file_type().is_file()
+                || !path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("txt"))
+            {
+                continue;
+            }
+            let Ok(uri) = Url::from_file_path(path) else {
+                warn!("Unable to build a URI for {}", path.display());
+                continue;
+            };
+            match std::fs::read(path) {
+                Ok(bytes) => {
+                    // Raw files are Latin1 on disk.
+                    let (text, _, _) = DF_ENCODING.decode(&bytes);
+                    self.update(uri, &Document::new(text.into_owned()));
+                    indexed_files += 1;
+                }
+                Err(e) => debug!("Skipping {}: {e:?}", path.display()),
+            }
+        }
+        info!(
+            "Indexed {indexed_files} files under {}",
+            root.as_ref().display()
+        );
+    }
+    /// Replace the definitions for a file with the ones in the given document.
+    pub fn update(&mut self, uri: Url, document: &Document) {
+        let definitions: Vec<Definition> = tokenize(&document.text)
+            .filter_map(|node| match node {
+                Node::Token(token) => {
+                    let object_type = defined_object_type(&document.object_type, token.key)?;
+                    let span = token.arg_span(0)?;
+                    Some(Definition {
+                        identifier: token.args[0].to_string(),
+                        object_type,
+                        location: Location {
+                            uri: uri.clone(),
+                            range: document.range_of(span),
+                        },
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+
+        if definitions.is_empty() {
+            self.files.remove(&uri);
+        } else {
+            self.files.insert(uri, definitions);
+        }
+    }
+    /// Find the definitions for an argument of a token.
+    ///
+    /// If the token is known to reference a specific object type (e.g. `COPY_TAGS_FROM` references
+    /// a creature), only definitions of that type are returned. Otherwise any definition with a
+    /// matching identifier is returned, which covers things like tile page IDs in graphics tokens.
+    ///
+    /// Arguments:
+    ///
+    /// * `token`: The token the cursor is in.
+    /// * `arg_index`: The index of the argument the cursor is on.
+    /// * `object_type`: The object type of the file the token is in.
+    pub fn find_definitions(
+        &self,
+        token: &Token<'_>,
+        arg_index: usize,
+        object_type: &ObjectType,
+    ) -> Vec<Location> {
+        let Some(identifier) = token.args.get(arg_index) else {
+            return Vec::new();
+        };
+        let expected = referenced_object_type(object_type, token.key, arg_index);
+
+        self.files
+            .values()
+            .flatten()
+            .filter(|definition| definition.identifier == *identifier)
+            .filter(|definition| {
+                expected
+                    .as_ref()
+                    .is_none_or(|expected| definition.object_type == *expected)
+            })
+            .map(|definition| definition.location.clone())
+            .collect()
+    }
+}
+
+/// If a token defines an object in a file of the given type, return the type of object it defines.
+///
+/// `[CREATURE:X]` defines a creature in a creature file, but references one in an entity file, so
+/// the file's object type has to be taken into account.
+fn defined_object_type(file_type: &ObjectType, key: &str) -> Option<ObjectType> {
+    if key == "OBJECT" {
+        return None;
+    }
+    let object_type = OBJECT_TOKEN_MAP.get(key)?;
+    let defines = object_type == file_type
+        || (*file_type == ObjectType::Item && key.starts_with("ITEM_"))
+        || (*file_type == ObjectType::Building && key.starts_with("BUILDING_"))
+        || (*file_type == ObjectType::Graphics
+            && matches!(object_type, ObjectType::TilePage | ObjectType::Palette));
+
+    defines.then(|| object_type.clone())
+}
+
+/// The type of object a token argument references, for tokens where that is known.
+fn referenced_object_type(
+    file_type: &ObjectType,
+    key: &str,
+    arg_index: usize,
+) -> Option<ObjectType> {
+    match (key, arg_index) {
+        (
+            "COPY_TAGS_FROM" | "SELECT_CREATURE" | "CREATURE_GRAPHICS" | "CREATURE_CASTE_GRAPHICS",
+            0,
+        ) => Some(ObjectType::Creature),
+        ("CREATURE", 0) if *file_type != ObjectType::Creature => Some(ObjectType::Creature),
+        ("APPLY_CREATURE_VARIATION", 0) => Some(ObjectType::CreatureVariation),
+        ("PLANT_GRAPHICS", 0) => Some(ObjectType::Plant),
+        ("USE_MATERIAL_TEMPLATE", 1) => Some(ObjectType::MaterialTemplate),
+        ("BODY_DETAIL_PLAN", 0) if *file_type == ObjectType::Creature => {
+            Some(ObjectType::BodyDetailPlan)
+        }
+        ("BODY", _) if *file_type == ObjectType::Creature => Some(ObjectType::Body),
+        ("USE_TISSUE_TEMPLATE", 1) => Some(ObjectType::TissueTemplate),
+        _ => None,
+    }
+}
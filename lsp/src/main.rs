@@ -0,0 +1,66 @@
+//! A language server for Dwarf Fortress raw files.
+//!
+//! The server speaks the Language Server Protocol over stdio, so any editor with LSP support
+//! (VS Code, Neovim, Helix, etc.) can use it. It provides:
+//!
+//! - diagnostics from the parser: malformed tokens, invalid `[OBJECT]` headers, unknown or
+//!   misplaced tokens, and parse warnings
+//! - hover documentation for creature, caste and entity tokens
+//! - completion of token names
+//! - go-to-definition for object identifiers (e.g. `COPY_TAGS_FROM` targets and tile page IDs)
+//!
+//! # Usage
+//!
+//! ```sh
+//! dfraw_json_parser-lsp
+//! ```
+//!
+//! Logs are written to stderr, since stdout is used for the protocol. Increase the log
+//! verbosity with `-v` (debug) or `-vv` (trace).
+
+mod completion;
+mod diagnostics;
+mod document;
+mod hover;
+mod index;
+mod server;
+
+use lsp_server::Connection;
+use tracing::{error, info, Level};
+use tracing_subscriber::FmtSubscriber;
+
+fn main() {
+    let log_level = match std::env::args().skip(1).find(|arg| arg.starts_with("-v")) {
+        Some(arg) if arg == "-vv" => Level::TRACE,
+        Some(_) => Level::DEBUG,
+        None => Level::INFO,
+    };
+
+    // Stdout is the protocol channel, so all logging has to go to stderr.
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(log_level)
+        .with_writer(std::io::stderr)
+        .with_ansi(false)
+        .compact()
+        .finish();
+
+    if let Err(e) = tracing::subscriber::set_global_default(subscriber) {
+        eprintln!("Failed to set global default subscriber: {e:?}");
+    }
+
+    info!("Starting dfraw language server");
+
+    let (connection, io_threads) = Connection::stdio();
+
+    if let Err(e) = server::run(&connection) {
+        error!("Language server stopped with an error: {e}");
+    }
+
+    // Drop the connection so the writer thread can finish before we join it.
+    drop(connection);
+    if let Err(e) = io_threads.join() {
+        error!("Failed to shut down IO threads: {e}");
+    }
+
+    info!("Language server shut down");
+}
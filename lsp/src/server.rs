@@ -0,0 +1,277 @@
+//! The language server main loop and request handlers.
+
+use std::{collections::HashMap, error::Error};
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+        Notification as _, PublishDiagnostics,
+    },
+    request::{Completion, GotoDefinition, HoverRequest, Request as _},
+    CompletionOptions, CompletionParams, CompletionResponse, Diagnostic,
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DidSaveTextDocumentParams, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents,
+    HoverParams, HoverProviderCapability, InitializeParams, MarkupContent, MarkupKind, OneOf,
+    PublishDiagnosticsParams, ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind,
+    Url,
+};
+use tracing::{debug, info, warn};
+
+use crate::{
+    completion::completions, diagnostics::diagnostics, document::Document, hover::token_docs,
+    index::Index,
+};
+
+/// The error type used by the server loop.
+pub type ServerError = Box<dyn Error + Send + Sync>;
+
+/// The state of the language server.
+struct Server<'a> {
+    /// The connection to the client.
+    connection: &'a Connection,
+    /// The documents which are open in the editor.
+    documents: HashMap<Url, Document>,
+    /// The objects defined across the workspace.
+    index: Index,
+}
+
+/// Run the language server on a connection until the client shuts it down.
+///
+/// This performs the initialize handshake, indexes the workspace folders, and then handles
+/// messages until a shutdown request arrives.
+///
+/// # Errors
+///
+/// * If the initialize handshake fails.
+/// * If a message can't be sent to the client.
+pub fn run(connection: &Connection) -> Result<(), ServerError> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec!["[".to_string()]),
+            ..CompletionOptions::default()
+        }),
+        definition_provider: Some(OneOf::Left(true)),
+        ..ServerCapabilities::default()
+    };
+
+    let params = connection.initialize(serde_json::to_value(capabilities)?)?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+
+    let mut server = Server {
+        connection,
+        documents: HashMap::new(),
+        index: Index::default(),
+    };
+    server.index_workspace(&params);
+    server.main_loop()
+}
+
+impl Server<'_> {
+    /// Index every workspace folder (or the root URI, for older clients).
+    #[allow(deprecated)]
+    fn index_workspace(&mut self, params: &InitializeParams) {
+        let roots: Vec<Url> = params.workspace_folders.as_ref().map_or_else(
+            || params.root_uri.iter().cloned().collect(),
+            |folders| folders.iter().map(|folder| folder.uri.clone()).collect(),
+        );
+        for root in roots {
+            if let Ok(path) = root.to_file_path() {
+                self.index.index_directory(&path);
+            } else {
+                warn!("Not indexing non-file workspace folder {root}");
+            }
+        }
+    }
+    /// Handle messages until the client asks us to shut down.
+    fn main_loop(&mut self) -> Result<(), ServerError> {
+        for message in &self.connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        info!("Received shutdown request");
+                        return Ok(());
+                    }
+                    self.handle_request(request)?;
+                }
+                Message::Notification(notification) => {
+                    self.handle_notification(notification)?;
+                }
+                Message::Response(response) => {
+                    debug!("Ignoring response {:?}", response.id);
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Dispatch a request to its handler and send the response.
+    ///
+    /// A request with parameters which can't be read gets an `InvalidParams` error response.
+    fn handle_request(&self, request: Request) -> Result<(), ServerError> {
+        let Request { id, method, params } = request;
+        let result = match method.as_str() {
+            HoverRequest::METHOD => match serde_json::from_value::<HoverParams>(params) {
+                Ok(params) => serde_json::to_value(self.hover(&params))?,
+                Err(error) => return self.respond_invalid_params(id, &method, &error),
+            },
+            Completion::METHOD => match serde_json::from_value::<CompletionParams>(params) {
+                Ok(params) => serde_json::to_value(self.completion(&params))?,
+                Err(error) => return self.respond_invalid_params(id, &method, &error),
+            },
+            GotoDefinition::METHOD => {
+                match serde_json::from_value::<GotoDefinitionParams>(params) {
+                    Ok(params) => serde_json::to_value(self.goto_definition(&params))?,
+                    Err(error) => return self.respond_invalid_params(id, &method, &error),
+                }
+            }
+            method => {
+                debug!("Unhandled request {method}");
+                return self.respond(Response::new_err(
+                    id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("Unhandled method {method}"),
+                ));
+            }
+        };
+        self.respond(Response::new_ok(id, result))
+    }
+    /// Handle a notification from the client.
+    fn handle_notification(&mut self, notification: Notification) -> Result<(), ServerError> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                self.set_document(uri, Document::new(params.text_document.text))?;
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                // We asked for full sync, so the last change has the whole text.
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    let uri = params.text_document.uri;
+                    self.set_document(uri, Document::new(change.text))?;
+                }
+            }
+            DidSaveTextDocument::METHOD => {
+                let params: DidSaveTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                if let Some(text) = params.text {
+                    self.set_document(params.text_document.uri, Document::new(text))?;
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                // Clear the diagnostics, since the client won't show them for closed documents
+                self.publish_diagnostics(uri, Vec::new())?;
+            }
+            method => debug!("Unhandled notification {method}"),
+        }
+        Ok(())
+    }
+    /// Store a new version of a document, re-index it and publish its diagnostics.
+    fn set_document(&mut self, uri: Url, document: Document) -> Result<(), ServerError> {
+        let is_raw_file = !uri
+            .path_segments()
+            .and_then(Iterator::last)
+            .is_some_and(|name| name.eq_ignore_ascii_case("info.txt"));
+
+        self.publish_diagnostics(uri.clone(), diagnostics(&document, is_raw_file))?;
+
+        self.index.update(uri.clone(), &document);
+        self.documents.insert(uri, document);
+        Ok(())
+    }
+    /// Replace the diagnostics the client shows for a document.
+    fn publish_diagnostics(
+        &self,
+        uri: Url,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Result<(), ServerError> {
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        };
+        self.connection
+            .sender
+            .send(Message::Notification(Notification::new(
+                PublishDiagnostics::METHOD.to_string(),
+                params,
+            )))?;
+        Ok(())
+    }
+    /// Send a response to the client.
+    fn respond(&self, response: Response) -> Result<(), ServerError> {
+        self.connection.sender.send(Message::Response(response))?;
+        Ok(())
+    }
+    /// Send an error response for a request whose parameters can't be read.
+    fn respond_invalid_params(
+        &self,
+        id: RequestId,
+        method: &str,
+        error: &serde_json::Error,
+    ) -> Result<(), ServerError> {
+        warn!("Invalid parameters for {method}: {error}");
+        self.respond(Response::new_err(
+            id,
+            ErrorCode::InvalidParams as i32,
+            format!("Invalid parameters for {method}: {error}"),
+        ))
+    }
+    /// Show the documentation for the token key under the cursor.
+    fn hover(&self, params: &HoverParams) -> Option<Hover> {
+        let position = &params.text_document_position_params;
+        let document = self.documents.get(&position.text_document.uri)?;
+        let offset = document.offset_at(position.position);
+        let token = document.token_at(offset)?;
+
+        let key_span = token.key_span();
+        if !key_span.contains(offset) {
+            return None;
+        }
+        let docs = token_docs(token.key, &document.object_type)?;
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: docs.to_markdown(token.key),
+            }),
+            range: Some(document.range_of(key_span)),
+        })
+    }
+    /// Complete token names.
+    fn completion(&self, params: &CompletionParams) -> Option<CompletionResponse> {
+        let position = &params.text_document_position;
+        let document = self.documents.get(&position.text_document.uri)?;
+        let offset = document.offset_at(position.position);
+        let items = completions(document, offset);
+
+        (!items.is_empty()).then_some(CompletionResponse::Array(items))
+    }
+    /// Find the definition of the identifier under the cursor.
+    fn goto_definition(&self, params: &GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let position = &params.text_document_position_params;
+        let document = self.documents.get(&position.text_document.uri)?;
+        let offset = document.offset_at(position.position);
+        let token = document.token_at(offset)?;
+
+        // The cursor may be right at the end of an argument, so include the end of the span.
+        let arg_index = (0..token.args.len()).find(|&index| {
+            token
+                .arg_span(index)
+                .is_some_and(|span| span.start <= offset && offset <= span.end)
+        })?;
+        let locations = self
+            .index
+            .find_definitions(&token, arg_index, &document.object_type);
+
+        (!locations.is_empty()).then_some(GotoDefinitionResponse::Array(locations))
+    }
+}